@@ -3,11 +3,11 @@
 //! Do not use this crate directly.
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::{Literal, Span};
 use quote::{ToTokens, quote};
 use syn::{
-    Data, DeriveInput, GenericParam, Generics, Ident, Lifetime, LifetimeParam, Member,
-    parse_macro_input,
+    Data, DeriveInput, GenericParam, Generics, Ident, Lifetime, LifetimeParam, LitInt, LitStr,
+    Member, Token, meta::ParseNestedMeta, parse_macro_input,
 };
 
 #[derive(Clone)]
@@ -16,6 +16,108 @@ enum ParseMode {
     Dynamic,
 }
 
+/// RISC-V SBI specification versions known to RustSBI, as `(major, minor)`.
+const SPEC_VERSIONS: &[(usize, usize)] = &[(0, 2), (0, 3), (1, 0), (2, 0), (3, 0)];
+
+/// Reported SBI specification version when `#[rustsbi(spec_version = "...")]` is not provided.
+const DEFAULT_SPEC_VERSION: (usize, usize) = (2, 0);
+
+/// Minimum SBI specification version which defines each standard extension.
+const EXTENSION_SPEC_VERSIONS: &[(&str, (usize, usize))] = &[
+    ("hsm", (0, 2)),
+    ("reset", (0, 3)),
+    ("pmu", (0, 3)),
+    ("console", (2, 0)),
+    ("susp", (2, 0)),
+    ("cppc", (2, 0)),
+    ("nacl", (2, 0)),
+    ("sta", (2, 0)),
+    ("fwft", (3, 0)),
+    ("sse", (3, 0)),
+    ("dbtr", (3, 0)),
+    ("mpxy", (3, 0)),
+];
+
+#[derive(Clone, Copy)]
+struct SpecVersion {
+    major: usize,
+    minor: usize,
+    // span of the `spec_version` attribute value, `None` if the default version is used
+    span: Option<Span>,
+}
+
+impl Default for SpecVersion {
+    fn default() -> Self {
+        let (major, minor) = DEFAULT_SPEC_VERSION;
+        SpecVersion {
+            major,
+            minor,
+            span: None,
+        }
+    }
+}
+
+impl SpecVersion {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+        let parsed = value
+            .split_once('.')
+            .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)));
+        match parsed {
+            Some(version) if SPEC_VERSIONS.contains(&version) => Ok(SpecVersion {
+                major: version.0,
+                minor: version.1,
+                span: Some(lit.span()),
+            }),
+            _ => Err(syn::Error::new(
+                lit.span(),
+                format_args!(
+                    "unknown RISC-V SBI specification version \"{}\"; expected one of {}",
+                    value,
+                    SPEC_VERSIONS
+                        .iter()
+                        .map(|(major, minor)| format!("\"{}.{}\"", major, minor))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )),
+        }
+    }
+
+    /// Encoded value returned by the SBI `get_spec_version` function.
+    fn encoded(&self) -> Literal {
+        Literal::usize_unsuffixed((self.major << 24) | self.minor)
+    }
+
+    /// Checks that every implemented extension is defined in this specification version.
+    fn check_extensions(&self, implemented: &[(&str, bool)], ans: &mut TokenStream) {
+        for &(extension_name, is_implemented) in implemented {
+            let Some(&(_, required)) = EXTENSION_SPEC_VERSIONS
+                .iter()
+                .find(|(name, _)| *name == extension_name)
+            else {
+                continue;
+            };
+            if !is_implemented || (self.major, self.minor) >= required {
+                continue;
+            }
+            let message = format!(
+                "SBI extension '{}' requires RISC-V SBI specification version {}.{} or later, \
+                but RustSBI reports version {}.{}; consider using #[rustsbi(spec_version = \"{}.{}\")]",
+                extension_name,
+                required.0,
+                required.1,
+                self.major,
+                self.minor,
+                required.0,
+                required.1,
+            );
+            let error = syn::Error::new(self.span.unwrap_or_else(Span::call_site), message);
+            ans.extend(TokenStream::from(error.to_compile_error()));
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 struct StaticImpl {
     fence: Option<Member>,
@@ -29,6 +131,11 @@ struct StaticImpl {
     cppc: Option<Member>,
     nacl: Option<Member>,
     sta: Option<Member>,
+    fwft: Option<Member>,
+    sse: Option<Member>,
+    dbtr: Option<Member>,
+    mpxy: Option<Member>,
+    custom: Vec<CustomExtension>,
     env_info: Option<Member>,
 }
 
@@ -50,10 +157,54 @@ impl StaticImpl {
             "cppc" => (true, self.cppc.replace(member)),
             "nacl" => (true, self.nacl.replace(member)),
             "sta" => (true, self.sta.replace(member)),
+            "fwft" => (true, self.fwft.replace(member)),
+            "sse" => (true, self.sse.replace(member)),
+            "dbtr" => (true, self.dbtr.replace(member)),
+            "mpxy" => (true, self.mpxy.replace(member)),
             "info" | "env_info" => (true, self.env_info.replace(member)),
             _ => (false, None),
         }
     }
+
+    fn implemented_extensions(&self) -> [(&'static str, bool); 15] {
+        [
+            ("fence", self.fence.is_some()),
+            ("hsm", self.hsm.is_some()),
+            ("ipi", self.ipi.is_some()),
+            ("reset", self.reset.is_some()),
+            ("timer", self.timer.is_some()),
+            ("pmu", self.pmu.is_some()),
+            ("console", self.console.is_some()),
+            ("susp", self.susp.is_some()),
+            ("cppc", self.cppc.is_some()),
+            ("nacl", self.nacl.is_some()),
+            ("sta", self.sta.is_some()),
+            ("fwft", self.fwft.is_some()),
+            ("sse", self.sse.is_some()),
+            ("dbtr", self.dbtr.is_some()),
+            ("mpxy", self.mpxy.is_some()),
+        ]
+    }
+}
+
+/// Vendor or firmware specific extension bound to an inclusive range of extension IDs.
+#[derive(Clone)]
+struct CustomExtension {
+    eid_start: u64,
+    eid_end: u64,
+    member: Member,
+}
+
+impl CustomExtension {
+    fn overlaps(&self, other: &CustomExtension) -> bool {
+        self.eid_start <= other.eid_end && other.eid_start <= self.eid_end
+    }
+
+    fn pattern(&self) -> proc_macro2::TokenStream {
+        let start = Literal::u64_unsuffixed(self.eid_start);
+        let end = Literal::u64_unsuffixed(self.eid_end);
+        quote! { #start..=#end }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -69,6 +220,11 @@ struct DynamicImpl {
     cppc: Vec<Member>,
     nacl: Vec<Member>,
     sta: Vec<Member>,
+    fwft: Vec<Member>,
+    sse: Vec<Member>,
+    dbtr: Vec<Member>,
+    mpxy: Vec<Member>,
+    custom: Vec<CustomExtension>,
     env_info: Option<Member>,
 }
 
@@ -86,11 +242,35 @@ impl DynamicImpl {
             "cppc" => self.cppc.push(member),
             "nacl" => self.nacl.push(member),
             "sta" => self.sta.push(member),
+            "fwft" => self.fwft.push(member),
+            "sse" => self.sse.push(member),
+            "dbtr" => self.dbtr.push(member),
+            "mpxy" => self.mpxy.push(member),
             "info" | "env_info" => return self.env_info.replace(member).is_none(),
             _ => return false,
         }
         true
     }
+
+    fn implemented_extensions(&self) -> [(&'static str, bool); 15] {
+        [
+            ("fence", !self.fence.is_empty()),
+            ("hsm", !self.hsm.is_empty()),
+            ("ipi", !self.ipi.is_empty()),
+            ("reset", !self.reset.is_empty()),
+            ("timer", !self.timer.is_empty()),
+            ("pmu", !self.pmu.is_empty()),
+            ("console", !self.console.is_empty()),
+            ("susp", !self.susp.is_empty()),
+            ("cppc", !self.cppc.is_empty()),
+            ("nacl", !self.nacl.is_empty()),
+            ("sta", !self.sta.is_empty()),
+            ("fwft", !self.fwft.is_empty()),
+            ("sse", !self.sse.is_empty()),
+            ("dbtr", !self.dbtr.is_empty()),
+            ("mpxy", !self.mpxy.is_empty()),
+        ]
+    }
 }
 
 /// This macro should be used in `rustsbi` crate as `rustsbi::RustSBI`.
@@ -104,6 +284,7 @@ pub fn derive_rustsbi(input: TokenStream) -> TokenStream {
 
     let mut ans = TokenStream::new();
     let mut parse_mode = ParseMode::Static;
+    let mut spec_version = SpecVersion::default();
 
     for attr in &input.attrs {
         if !attr.path().is_ident("rustsbi") {
@@ -113,6 +294,9 @@ pub fn derive_rustsbi(input: TokenStream) -> TokenStream {
             if meta.path.is_ident("dynamic") {
                 parse_mode = ParseMode::Dynamic;
                 Ok(())
+            } else if meta.path.is_ident("spec_version") {
+                spec_version = SpecVersion::parse(&meta.value()?.parse()?)?;
+                Ok(())
             } else {
                 let path = meta.path.to_token_stream().to_string().replace(' ', "");
                 Err(meta.error(format_args!("unknown RustSBI struct attribute `{}`", path)))
@@ -141,6 +325,27 @@ pub fn derive_rustsbi(input: TokenStream) -> TokenStream {
                 if meta.path.is_ident("skip") {
                     // accept meta but do nothing, effectively skip this field in RustSBI
                     current_meta_accepted = true;
+                } else if meta.path.is_ident("vendor") || meta.path.is_ident("firmware") {
+                    let (eid_start, eid_end) = parse_custom_extension_eid(&meta)?;
+                    let extension = CustomExtension {
+                        eid_start,
+                        eid_end,
+                        member: member.clone(),
+                    };
+                    match parse_mode {
+                        ParseMode::Static => {
+                            if static_impl.custom.iter().any(|c| c.overlaps(&extension)) {
+                                return Err(meta.error(format_args!(
+                                    "extension ID range {:#010x}..={:#010x} overlaps with another field. \
+                                    At most one field should handle the same SBI extension ID in static mode.",
+                                    eid_start, eid_end
+                                )));
+                            }
+                            static_impl.custom.push(extension);
+                        }
+                        ParseMode::Dynamic => dynamic_impl.custom.push(extension),
+                    }
+                    current_meta_accepted = true;
                 } else if let Some(meta_path_ident) = meta.path.get_ident() {
                     let extension_name = &meta_path_ident.to_string();
                     match parse_mode {
@@ -193,16 +398,24 @@ pub fn derive_rustsbi(input: TokenStream) -> TokenStream {
         }
     }
     match parse_mode {
-        ParseMode::Static => ans.extend(impl_derive_rustsbi_static(
-            &input.ident,
-            static_impl,
-            &input.generics,
-        )),
-        ParseMode::Dynamic => ans.extend(impl_derive_rustsbi_dynamic(
-            &input.ident,
-            dynamic_impl,
-            &input.generics,
-        )),
+        ParseMode::Static => {
+            spec_version.check_extensions(&static_impl.implemented_extensions(), &mut ans);
+            ans.extend(impl_derive_rustsbi_static(
+                &input.ident,
+                static_impl,
+                spec_version,
+                &input.generics,
+            ))
+        }
+        ParseMode::Dynamic => {
+            spec_version.check_extensions(&dynamic_impl.implemented_extensions(), &mut ans);
+            ans.extend(impl_derive_rustsbi_dynamic(
+                &input.ident,
+                dynamic_impl,
+                spec_version,
+                &input.generics,
+            ))
+        }
     };
     ans
 }
@@ -231,7 +444,53 @@ fn check_already_exists(
     }
 }
 
-fn impl_derive_rustsbi_static(name: &Ident, imp: StaticImpl, generics: &Generics) -> TokenStream {
+/// Parses `eid = <start>` or `eid = <start>..=<end>` inside `vendor(...)` or `firmware(...)`.
+fn parse_custom_extension_eid(meta: &ParseNestedMeta) -> syn::Result<(u64, u64)> {
+    let (kind, allowed_start, allowed_end) = if meta.path.is_ident("vendor") {
+        ("vendor", 0x0900_0000, 0x09FF_FFFF)
+    } else {
+        ("firmware", 0x0A00_0000, 0x0AFF_FFFF)
+    };
+    let mut range = None;
+    meta.parse_nested_meta(|inner| {
+        if !inner.path.is_ident("eid") {
+            let path = inner.path.to_token_stream().to_string().replace(' ', "");
+            return Err(inner.error(format_args!(
+                "unknown {} extension attribute `{}`",
+                kind, path
+            )));
+        }
+        let value = inner.value()?;
+        let start: LitInt = value.parse()?;
+        let end = if value.peek(Token![..=]) {
+            value.parse::<Token![..=]>()?;
+            value.parse::<LitInt>()?
+        } else {
+            start.clone()
+        };
+        let (eid_start, eid_end) = (start.base10_parse::<u64>()?, end.base10_parse::<u64>()?);
+        if eid_start > eid_end || eid_start < allowed_start || eid_end > allowed_end {
+            return Err(syn::Error::new(
+                start.span(),
+                format_args!(
+                    "{} extension ID range {:#010x}..={:#010x} is not within {:#010x}..={:#010x}",
+                    kind, eid_start, eid_end, allowed_start, allowed_end
+                ),
+            ));
+        }
+        range = Some((eid_start, eid_end));
+        Ok(())
+    })?;
+    range.ok_or_else(|| meta.error(format_args!("expected `{}(eid = ...)`", kind)))
+}
+
+fn impl_derive_rustsbi_static(
+    name: &Ident,
+    imp: StaticImpl,
+    spec_version: SpecVersion,
+    generics: &Generics,
+) -> TokenStream {
+    let spec_version = spec_version.encoded();
     let base_probe: usize = 1;
     let fence_probe: usize = if imp.fence.is_some() { 1 } else { 0 };
     let hsm_probe: usize = if imp.hsm.is_some() { 1 } else { 0 };
@@ -244,6 +503,10 @@ fn impl_derive_rustsbi_static(name: &Ident, imp: StaticImpl, generics: &Generics
     let cppc_probe: usize = if imp.cppc.is_some() { 1 } else { 0 };
     let nacl_probe: usize = if imp.nacl.is_some() { 1 } else { 0 };
     let sta_probe: usize = if imp.sta.is_some() { 1 } else { 0 };
+    let fwft_probe: usize = if imp.fwft.is_some() { 1 } else { 0 };
+    let sse_probe: usize = if imp.sse.is_some() { 1 } else { 0 };
+    let dbtr_probe: usize = if imp.dbtr.is_some() { 1 } else { 0 };
+    let mpxy_probe: usize = if imp.mpxy.is_some() { 1 } else { 0 };
     let probe = quote! {
         ::rustsbi::_StandardExtensionProbe {
             base: #base_probe,
@@ -258,12 +521,54 @@ fn impl_derive_rustsbi_static(name: &Ident, imp: StaticImpl, generics: &Generics
             cppc: #cppc_probe,
             nacl: #nacl_probe,
             sta: #sta_probe,
+            fwft: #fwft_probe,
+            sse: #sse_probe,
+            dbtr: #dbtr_probe,
+            mpxy: #mpxy_probe,
         }
     };
     let mut match_arms = quote! {};
+    // Custom extensions are probed by a prober wrapping the standard extension probe.
+    let (define_prober, probe) = if imp.custom.is_empty() {
+        (quote! {}, probe)
+    } else {
+        let mut prober_custom = quote! {};
+        for custom in &imp.custom {
+            let pattern = custom.pattern();
+            let member = &custom.member;
+            prober_custom.extend(quote! {
+                #pattern => ::rustsbi::_rustsbi_extension_handler_probe(&self.0.#member, extension),
+            });
+        }
+        let (_, origin_ty_generics, _) = generics.split_for_impl();
+        let prober_generics = {
+            let mut ans = generics.clone();
+            let lifetime = Lifetime::new("'_lt", Span::mixed_site());
+            ans.params
+                .insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+            ans
+        };
+        let (impl_generics, ty_generics, where_clause) = prober_generics.split_for_impl();
+        let define_prober = quote! {
+            struct _Prober #impl_generics (&'_lt #name #origin_ty_generics, ::rustsbi::_StandardExtensionProbe) #where_clause;
+            impl #impl_generics ::rustsbi::_ExtensionProbe for _Prober #ty_generics #where_clause {
+                #[inline(always)]
+                fn probe_extension(&self, extension: usize) -> usize {
+                    match extension {
+                        #prober_custom
+                        _ => ::rustsbi::_ExtensionProbe::probe_extension(&self.1, extension),
+                    }
+                }
+            }
+        };
+        (define_prober, quote! { _Prober(&self, #probe) })
+    };
     let base_procedure = if let Some(env_info) = imp.env_info {
         quote! {
-            ::rustsbi::spec::base::EID_BASE => ::rustsbi::_rustsbi_base_env_info(param, function, &self.#env_info, #probe),
+            ::rustsbi::spec::base::EID_BASE => {
+                #define_prober
+                ::rustsbi::_rustsbi_base_env_info(param, function, #spec_version, &self.#env_info, #probe)
+            }
         }
     } else {
         match () {
@@ -277,7 +582,10 @@ fn impl_derive_rustsbi_static(name: &Ident, imp: StaticImpl, generics: &Generics
             },
             #[cfg(feature = "machine")]
             () => quote! {
-                ::rustsbi::spec::base::EID_BASE => ::rustsbi::_rustsbi_base_bare(param, function, #probe),
+                ::rustsbi::spec::base::EID_BASE => {
+                    #define_prober
+                    ::rustsbi::_rustsbi_base_bare(param, function, #spec_version, #probe)
+                }
             },
         }
     };
@@ -337,6 +645,33 @@ fn impl_derive_rustsbi_static(name: &Ident, imp: StaticImpl, generics: &Generics
             ::rustsbi::spec::sta::EID_STA => ::rustsbi::_rustsbi_sta(&self.#sta, param, function),
         })
     }
+    if let Some(fwft) = &imp.fwft {
+        match_arms.extend(quote! {
+            ::rustsbi::spec::fwft::EID_FWFT => ::rustsbi::_rustsbi_fwft(&self.#fwft, param, function),
+        })
+    }
+    if let Some(sse) = &imp.sse {
+        match_arms.extend(quote! {
+            ::rustsbi::spec::sse::EID_SSE => ::rustsbi::_rustsbi_sse(&self.#sse, param, function),
+        })
+    }
+    if let Some(dbtr) = &imp.dbtr {
+        match_arms.extend(quote! {
+            ::rustsbi::spec::dbtr::EID_DBTR => ::rustsbi::_rustsbi_dbtr(&self.#dbtr, param, function),
+        })
+    }
+    if let Some(mpxy) = &imp.mpxy {
+        match_arms.extend(quote! {
+            ::rustsbi::spec::mpxy::EID_MPXY => ::rustsbi::_rustsbi_mpxy(&self.#mpxy, param, function),
+        })
+    }
+    for custom in &imp.custom {
+        let pattern = custom.pattern();
+        let member = &custom.member;
+        match_arms.extend(quote! {
+            #pattern => ::rustsbi::_rustsbi_extension_handler(&self.#member, extension, param, function),
+        })
+    }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let generated = quote! {
     impl #impl_generics ::rustsbi::RustSBI for #name #ty_generics #where_clause {
@@ -352,7 +687,13 @@ fn impl_derive_rustsbi_static(name: &Ident, imp: StaticImpl, generics: &Generics
     generated.into()
 }
 
-fn impl_derive_rustsbi_dynamic(name: &Ident, imp: DynamicImpl, generics: &Generics) -> TokenStream {
+fn impl_derive_rustsbi_dynamic(
+    name: &Ident,
+    imp: DynamicImpl,
+    spec_version: SpecVersion,
+    generics: &Generics,
+) -> TokenStream {
+    let spec_version = spec_version.encoded();
     let mut fence_contents = quote! {};
     let mut prober_fence = quote! {};
     for fence in &imp.fence {
@@ -518,6 +859,85 @@ fn impl_derive_rustsbi_dynamic(name: &Ident, imp: DynamicImpl, generics: &Generi
             }
         });
     }
+    let mut fwft_contents = quote! {};
+    let mut prober_fwft = quote! {};
+    for fwft in &imp.fwft {
+        fwft_contents.extend(quote! {
+            if ::rustsbi::_rustsbi_fwft_probe(&self.#fwft) != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return ::rustsbi::_rustsbi_fwft(&self.#fwft, param, function)
+            }
+        });
+        prober_fwft.extend(quote! {
+            let value = ::rustsbi::_rustsbi_fwft_probe(&self.0.#fwft);
+            if value != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return value
+            }
+        });
+    }
+    let mut sse_contents = quote! {};
+    let mut prober_sse = quote! {};
+    for sse in &imp.sse {
+        sse_contents.extend(quote! {
+            if ::rustsbi::_rustsbi_sse_probe(&self.#sse) != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return ::rustsbi::_rustsbi_sse(&self.#sse, param, function)
+            }
+        });
+        prober_sse.extend(quote! {
+            let value = ::rustsbi::_rustsbi_sse_probe(&self.0.#sse);
+            if value != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return value
+            }
+        });
+    }
+    let mut dbtr_contents = quote! {};
+    let mut prober_dbtr = quote! {};
+    for dbtr in &imp.dbtr {
+        dbtr_contents.extend(quote! {
+            if ::rustsbi::_rustsbi_dbtr_probe(&self.#dbtr) != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return ::rustsbi::_rustsbi_dbtr(&self.#dbtr, param, function)
+            }
+        });
+        prober_dbtr.extend(quote! {
+            let value = ::rustsbi::_rustsbi_dbtr_probe(&self.0.#dbtr);
+            if value != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return value
+            }
+        });
+    }
+    let mut mpxy_contents = quote! {};
+    let mut prober_mpxy = quote! {};
+    for mpxy in &imp.mpxy {
+        mpxy_contents.extend(quote! {
+            if ::rustsbi::_rustsbi_mpxy_probe(&self.#mpxy) != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return ::rustsbi::_rustsbi_mpxy(&self.#mpxy, param, function)
+            }
+        });
+        prober_mpxy.extend(quote! {
+            let value = ::rustsbi::_rustsbi_mpxy_probe(&self.0.#mpxy);
+            if value != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return value
+            }
+        });
+    }
+    let mut custom_contents = quote! {};
+    let mut prober_custom = quote! {};
+    for custom in &imp.custom {
+        let pattern = custom.pattern();
+        let member = &custom.member;
+        custom_contents.extend(quote! {
+            if matches!(extension, #pattern) && ::rustsbi::_rustsbi_extension_handler_probe(&self.#member, extension) != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                return ::rustsbi::_rustsbi_extension_handler(&self.#member, extension, param, function)
+            }
+        });
+        prober_custom.extend(quote! {
+            if matches!(extension, #pattern) {
+                let value = ::rustsbi::_rustsbi_extension_handler_probe(&self.0.#member, extension);
+                if value != ::rustsbi::spec::base::UNAVAILABLE_EXTENSION {
+                    return value
+                }
+            }
+        });
+    }
 
     let (_, origin_ty_generics, _) = generics.split_for_impl();
     let prober_generics = {
@@ -546,15 +966,19 @@ fn impl_derive_rustsbi_dynamic(name: &Ident, imp: DynamicImpl, generics: &Generi
                     ::rustsbi::spec::susp::EID_SUSP => { #prober_susp ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
                     ::rustsbi::spec::cppc::EID_CPPC => { #prober_cppc ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
                     ::rustsbi::spec::nacl::EID_NACL => { #prober_nacl ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
-                    ::rustsbi::spec::sta::EID_STA => { #prober_sta ::rustsbi::spec::base::UNAVAILABLE_EXTENSION}
-                    _ => ::rustsbi::spec::base::UNAVAILABLE_EXTENSION,
+                    ::rustsbi::spec::sta::EID_STA => { #prober_sta ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
+                    ::rustsbi::spec::fwft::EID_FWFT => { #prober_fwft ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
+                    ::rustsbi::spec::sse::EID_SSE => { #prober_sse ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
+                    ::rustsbi::spec::dbtr::EID_DBTR => { #prober_dbtr ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
+                    ::rustsbi::spec::mpxy::EID_MPXY => { #prober_mpxy ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
+                    _ => { #prober_custom ::rustsbi::spec::base::UNAVAILABLE_EXTENSION },
                 }
             }
         }
     };
     let base_result = if let Some(env_info) = imp.env_info {
         quote! {
-            ::rustsbi::_rustsbi_base_env_info(param, function, &self.#env_info, prober)
+            ::rustsbi::_rustsbi_base_env_info(param, function, #spec_version, &self.#env_info, prober)
         }
     } else {
         match () {
@@ -568,7 +992,7 @@ fn impl_derive_rustsbi_dynamic(name: &Ident, imp: DynamicImpl, generics: &Generi
             },
             #[cfg(feature = "machine")]
             () => quote! {
-                ::rustsbi::_rustsbi_base_bare(param, function, prober)
+                ::rustsbi::_rustsbi_base_bare(param, function, #spec_version, prober)
             },
         }
     };
@@ -589,12 +1013,16 @@ fn impl_derive_rustsbi_dynamic(name: &Ident, imp: DynamicImpl, generics: &Generi
                     ::rustsbi::spec::cppc::EID_CPPC => { #cppc_contents ::rustsbi::SbiRet::not_supported() },
                     ::rustsbi::spec::nacl::EID_NACL => { #nacl_contents ::rustsbi::SbiRet::not_supported() },
                     ::rustsbi::spec::sta::EID_STA => { #sta_contents ::rustsbi::SbiRet::not_supported() },
+                    ::rustsbi::spec::fwft::EID_FWFT => { #fwft_contents ::rustsbi::SbiRet::not_supported() },
+                    ::rustsbi::spec::sse::EID_SSE => { #sse_contents ::rustsbi::SbiRet::not_supported() },
+                    ::rustsbi::spec::dbtr::EID_DBTR => { #dbtr_contents ::rustsbi::SbiRet::not_supported() },
+                    ::rustsbi::spec::mpxy::EID_MPXY => { #mpxy_contents ::rustsbi::SbiRet::not_supported() },
                     ::rustsbi::spec::base::EID_BASE => {
                         #define_prober
                         let prober = _Prober(&self);
                         #base_result
                     }
-                    _ => ::rustsbi::SbiRet::not_supported(),
+                    _ => { #custom_contents ::rustsbi::SbiRet::not_supported() },
                 }
             }
         }
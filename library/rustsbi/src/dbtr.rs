@@ -0,0 +1,242 @@
+use sbi_spec::binary::{SbiRet, SharedPtr, TriggerMask};
+
+/// Debug Triggers extension.
+///
+/// The RISC-V Sdtrig extension allows machine-mode software to directly configure
+/// debug triggers, which in turn allows native (or hosted) debugging in machine mode
+/// without any external debugger. However, debug triggers are only accessible to
+/// machine mode. The SBI Debug Triggers extension defines an SBI based abstraction
+/// to provide native debugging for supervisor-mode software, such that guest (VS-mode)
+/// and hypervisor (HS-mode) software can share the debug triggers on a hart.
+///
+/// Each hart has a fixed number of debug triggers, referred to as `trig_max`. Each
+/// debug trigger is assigned a logical index `trig_idx` by the SBI implementation
+/// where `-1 < trig_idx < trig_max`.
+///
+/// Trigger configurations are exchanged through a per-hart shared memory set by
+/// [`set_shmem`](Dbtr::set_shmem); each entry of the shared memory holds the
+/// `tstate`, `tdata1`, `tdata2` and `tdata3` words of a trigger.
+pub trait Dbtr {
+    /// Get the number of debug triggers on the calling hart which can support
+    /// the trigger configuration specified by `trig_tdata1` parameter.
+    ///
+    /// If the `trig_tdata1` parameter is zero, this function returns the total
+    /// number of debug triggers available on the calling hart.
+    fn num_triggers(&self, trig_tdata1: usize) -> usize;
+    /// Set and enable the shared memory for debug trigger configuration on the calling hart.
+    ///
+    /// If both `phys_addr_lo` and `phys_addr_hi` of `shmem` are all-ones bitwise, the
+    /// shared memory for debug trigger configuration on the calling hart is cleared and
+    /// disabled. Otherwise, `shmem` specifies the physical base address of the shared
+    /// memory, which MUST be `XLEN / 8` bytes aligned and large enough to hold
+    /// `trig_max` trigger entries.
+    ///
+    /// The `flags` parameter is reserved for future use and MUST be zero.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Shared memory was set or cleared successfully.
+    /// | `SbiRet::invalid_param()`   | The `flags` parameter is not zero or the `shmem` is not `XLEN / 8` bytes aligned.
+    /// | `SbiRet::invalid_address()` | The shared memory pointed to by the `shmem` parameter is not writable or does not satisfy other requirements of shared memory physical address range.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet;
+    /// Read the debug trigger state and configuration into shared memory for a range
+    /// of debug triggers on the calling hart.
+    ///
+    /// The `trig_idx_base` parameter specifies the starting trigger index, while
+    /// `trig_count` specifies the number of triggers to read. The `tstate`, `tdata1`,
+    /// `tdata2` and `tdata3` words of each trigger are written to consecutive entries
+    /// of the shared memory.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code             | Description
+    /// |:------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`     | State of the triggers was read successfully.
+    /// | `SbiRet::no_shmem()`    | Shared memory for debug triggers is disabled.
+    /// | `SbiRet::bad_range()`   | Either `trig_idx_base >= trig_max` or `trig_idx_base + trig_count >= trig_max`.
+    fn read_triggers(&self, trig_idx_base: usize, trig_count: usize) -> SbiRet;
+    /// Install debug triggers based on an array of trigger configurations in the
+    /// shared memory of the calling hart.
+    ///
+    /// The `trig_count` parameter represents the number of entries in the shared memory.
+    /// Upon success, the index of each installed trigger is written back into the `idx`
+    /// field of the corresponding entry.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Triggers were installed successfully.
+    /// | `SbiRet::no_shmem()`        | Shared memory for debug triggers is disabled.
+    /// | `SbiRet::bad_range()`       | `trig_count >= trig_max`.
+    /// | `SbiRet::invalid_param()`   | One of the trigger configuration words `tdata1`, `tdata2` or `tdata3` has an invalid value; `SbiRet.value` is set to the array index of the failing entry.
+    /// | `SbiRet::failed()`          | Failed to assign a trigger index for at least one trigger configuration; `SbiRet.value` is set to the array index of the failing entry.
+    fn install_triggers(&self, trig_count: usize) -> SbiRet;
+    /// Update already installed debug triggers based on a trigger configuration array
+    /// in the shared memory of the calling hart.
+    ///
+    /// The `trig_count` parameter represents the number of entries in the shared memory.
+    /// Each entry specifies the index of an installed trigger and its new configuration.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Triggers were updated successfully.
+    /// | `SbiRet::no_shmem()`        | Shared memory for debug triggers is disabled.
+    /// | `SbiRet::bad_range()`       | `trig_count >= trig_max`, or a trigger index in an entry is not valid.
+    /// | `SbiRet::invalid_param()`   | A trigger in the configuration array is not installed, or one of its configuration words has an invalid value; `SbiRet.value` is set to the array index of the failing entry.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn update_triggers(&self, trig_count: usize) -> SbiRet;
+    /// Uninstall a set of debug triggers on the calling hart.
+    ///
+    /// The `triggers` parameter specifies the trigger indices to be uninstalled,
+    /// as a bitmask `trig_idx_mask` relative to a starting index `trig_idx_base`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Triggers were uninstalled successfully.
+    /// | `SbiRet::invalid_param()`   | At least one of the trigger indices specified by `triggers` is invalid or not installed.
+    fn uninstall_triggers(&self, triggers: TriggerMask) -> SbiRet;
+    /// Enable a set of debug triggers on the calling hart.
+    ///
+    /// The `triggers` parameter specifies the trigger indices to be enabled,
+    /// as a bitmask `trig_idx_mask` relative to a starting index `trig_idx_base`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Triggers were enabled successfully.
+    /// | `SbiRet::invalid_param()`   | At least one of the trigger indices specified by `triggers` is invalid or not installed.
+    fn enable_triggers(&self, triggers: TriggerMask) -> SbiRet;
+    /// Disable a set of debug triggers on the calling hart.
+    ///
+    /// The `triggers` parameter specifies the trigger indices to be disabled,
+    /// as a bitmask `trig_idx_mask` relative to a starting index `trig_idx_base`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Triggers were disabled successfully.
+    /// | `SbiRet::invalid_param()`   | At least one of the trigger indices specified by `triggers` is invalid or not installed.
+    fn disable_triggers(&self, triggers: TriggerMask) -> SbiRet;
+    /// Function internal to macros. Do not use.
+    #[doc(hidden)]
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1)
+    }
+}
+
+impl<T: Dbtr> Dbtr for &T {
+    #[inline]
+    fn num_triggers(&self, trig_tdata1: usize) -> usize {
+        T::num_triggers(self, trig_tdata1)
+    }
+    #[inline]
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet {
+        T::set_shmem(self, shmem, flags)
+    }
+    #[inline]
+    fn read_triggers(&self, trig_idx_base: usize, trig_count: usize) -> SbiRet {
+        T::read_triggers(self, trig_idx_base, trig_count)
+    }
+    #[inline]
+    fn install_triggers(&self, trig_count: usize) -> SbiRet {
+        T::install_triggers(self, trig_count)
+    }
+    #[inline]
+    fn update_triggers(&self, trig_count: usize) -> SbiRet {
+        T::update_triggers(self, trig_count)
+    }
+    #[inline]
+    fn uninstall_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        T::uninstall_triggers(self, triggers)
+    }
+    #[inline]
+    fn enable_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        T::enable_triggers(self, triggers)
+    }
+    #[inline]
+    fn disable_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        T::disable_triggers(self, triggers)
+    }
+}
+
+impl<T: Dbtr> Dbtr for Option<T> {
+    #[inline]
+    fn num_triggers(&self, trig_tdata1: usize) -> usize {
+        self.as_ref()
+            .map_or(0, |inner| T::num_triggers(inner, trig_tdata1))
+    }
+    #[inline]
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::set_shmem(inner, shmem, flags)
+        })
+    }
+    #[inline]
+    fn read_triggers(&self, trig_idx_base: usize, trig_count: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::read_triggers(inner, trig_idx_base, trig_count)
+        })
+    }
+    #[inline]
+    fn install_triggers(&self, trig_count: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::install_triggers(inner, trig_count)
+        })
+    }
+    #[inline]
+    fn update_triggers(&self, trig_count: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::update_triggers(inner, trig_count)
+        })
+    }
+    #[inline]
+    fn uninstall_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::uninstall_triggers(inner, triggers)
+        })
+    }
+    #[inline]
+    fn enable_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::enable_triggers(inner, triggers)
+        })
+    }
+    #[inline]
+    fn disable_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::disable_triggers(inner, triggers)
+        })
+    }
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        match self {
+            Some(_) => sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1),
+            None => sbi_spec::base::UNAVAILABLE_EXTENSION,
+        }
+    }
+}
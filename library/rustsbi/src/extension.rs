@@ -0,0 +1,84 @@
+use sbi_spec::binary::SbiRet;
+
+/// Handler for a vendor-specific or firmware-specific SBI extension.
+///
+/// The RISC-V SBI specification reserves extension IDs `0x0900_0000` to `0x09FF_FFFF`
+/// for vendor-specific extensions, and `0x0A00_0000` to `0x0AFF_FFFF` for firmware-specific
+/// extensions. Unlike standard extensions, the functions of such extensions are not defined
+/// by the specification; an `ExtensionHandler` receives the raw extension ID, function ID
+/// and parameters of every environment call routed to it.
+///
+/// Fields implementing this trait are bound to an extension ID, or an inclusive range of
+/// extension IDs, using `#[rustsbi(vendor(eid = ...))]` or `#[rustsbi(firmware(eid = ...))]`
+/// on the structure deriving [`RustSBI`](derive.RustSBI.html):
+///
+/// ```rust
+/// # use rustsbi::{ExtensionHandler, RustSBI};
+/// # use sbi_spec::binary::SbiRet;
+/// #[derive(RustSBI)]
+/// struct MySBI {
+///     #[rustsbi(vendor(eid = 0x0900_0000..=0x0900_00FF))]
+///     platform: MyPlatformExtension,
+///     info: MyEnvInfo,
+/// }
+///
+/// struct MyPlatformExtension;
+///
+/// impl ExtensionHandler for MyPlatformExtension {
+///     fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+///         match function {
+///             0 => SbiRet::success(extension - 0x0900_0000),
+///             _ => SbiRet::not_supported(),
+///         }
+///     }
+/// }
+/// # struct MyEnvInfo;
+/// # impl rustsbi::EnvInfo for MyEnvInfo {
+/// #     fn mvendorid(&self) -> usize { 1 }
+/// #     fn marchid(&self) -> usize { 2 }
+/// #     fn mimpid(&self) -> usize { 3 }
+/// # }
+/// ```
+pub trait ExtensionHandler {
+    /// Handle supervisor environment call on the given extension with given function and parameters.
+    ///
+    /// The `extension` parameter is always within the extension ID range this handler is bound to.
+    fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet;
+    /// Probe the availability of the given extension.
+    ///
+    /// Returns `0` if the extension is not available, `1` if it is available, or any other
+    /// non-zero value defined by the extension. By default, all extension IDs within the bound
+    /// range are reported as available.
+    #[inline]
+    fn probe_extension(&self, extension: usize) -> usize {
+        let _ = extension;
+        sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1)
+    }
+}
+
+impl<T: ExtensionHandler> ExtensionHandler for &T {
+    #[inline]
+    fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+        T::handle_ecall(self, extension, function, param)
+    }
+    #[inline]
+    fn probe_extension(&self, extension: usize) -> usize {
+        T::probe_extension(self, extension)
+    }
+}
+
+impl<T: ExtensionHandler> ExtensionHandler for Option<T> {
+    #[inline]
+    fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::handle_ecall(inner, extension, function, param)
+        })
+    }
+    #[inline]
+    fn probe_extension(&self, extension: usize) -> usize {
+        self.as_ref()
+            .map_or(sbi_spec::base::UNAVAILABLE_EXTENSION, |inner| {
+                T::probe_extension(inner, extension)
+            })
+    }
+}
@@ -0,0 +1,113 @@
+use sbi_spec::binary::SbiRet;
+
+/// Firmware Features extension.
+///
+/// Some features of the RISC-V platform may be controlled only by machine mode
+/// (or hypervisor mode) software, for example the misaligned access exception
+/// delegation or hardware updating of page table entry A/D bits. The SBI Firmware
+/// Features extension allows the supervisor-mode software to request the SBI
+/// implementation to set or get the configuration of such features.
+///
+/// Feature identifiers are 32-bit values; the standard feature types are defined
+/// in [`sbi_spec::fwft::feature_type`]. Each feature is configured per hart, and
+/// features set by one hart do not affect other harts unless stated otherwise.
+///
+/// | Feature ID | Feature name            | Description
+/// |:-----------|:------------------------|:----------------------------------------------
+/// | 0x00000000 | `MISALIGNED_EXC_DELEG`  | Control misaligned access exception delegation to supervisor-mode.
+/// | 0x00000001 | `LANDING_PAD`           | Control landing pad support for supervisor-mode.
+/// | 0x00000002 | `SHADOW_STACK`          | Control shadow stack support for supervisor-mode.
+/// | 0x00000003 | `DOUBLE_TRAP`           | Control double trap support.
+/// | 0x00000004 | `PTE_AD_HW_UPDATING`    | Control hardware updating of PTE A/D bits.
+/// | 0x00000005 | `POINTER_MASKING_PMLEN` | Control the pointer masking tag length.
+/// | 0x00000006 - 0x3FFFFFFF | | Reserved for future use.
+/// | 0x40000000 - 0x7FFFFFFF | | Platform-specific features.
+/// | 0x80000000 - 0xBFFFFFFF | | Reserved for future use.
+/// | 0xC0000000 - 0xFFFFFFFF | | Platform-specific features.
+pub trait Fwft {
+    /// Set the configuration value of a specific firmware feature.
+    ///
+    /// # Parameters
+    ///
+    /// The `feature` parameter specifies the identifier of the feature to set,
+    /// and `value` is the new configuration value of this feature.
+    ///
+    /// The `flags` parameter modifies the behavior of the set operation; if
+    /// `flags::SetFlags::LOCK` is set, the feature value can no longer be modified
+    /// until the next hart reset once it is set successfully.
+    ///
+    /// # Return value
+    ///
+    /// A successful return results in the requested firmware feature to be set
+    /// according to the `value` and `flags` parameters. In case of failure,
+    /// `feature` value is not modified and the possible error codes returned in
+    /// `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                | Description
+    /// |:---------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`        | `feature` was set successfully.
+    /// | `SbiRet::not_supported()`  | `feature` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_param()`  | Provided `value` or `flags` parameter is invalid.
+    /// | `SbiRet::denied()`         | `feature` set operation failed because either it was denied by the SBI implementation, or `feature` is reserved or is platform-specific and unimplemented.
+    /// | `SbiRet::denied_locked()`  | `feature` set operation failed because the `feature` is locked.
+    /// | `SbiRet::failed()`         | The set operation failed for unspecified or unknown other reasons.
+    fn set(&self, feature: u32, value: usize, flags: usize) -> SbiRet;
+    /// Get the configuration value of a specific firmware feature.
+    ///
+    /// # Parameters
+    ///
+    /// The `feature` parameter specifies the identifier of the feature to get.
+    ///
+    /// # Return value
+    ///
+    /// A successful return results in the firmware feature configuration value
+    /// to be returned in `SbiRet.value`. In case of failure, the content of
+    /// `SbiRet.value` is zero and the possible error codes returned in
+    /// `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                | Description
+    /// |:---------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`        | Feature status was retrieved successfully.
+    /// | `SbiRet::not_supported()`  | `feature` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::denied()`         | `feature` is reserved or is platform-specific and unimplemented.
+    /// | `SbiRet::failed()`         | The get operation failed for unspecified or unknown other reasons.
+    fn get(&self, feature: u32) -> SbiRet;
+    /// Function internal to macros. Do not use.
+    #[doc(hidden)]
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1)
+    }
+}
+
+impl<T: Fwft> Fwft for &T {
+    #[inline]
+    fn set(&self, feature: u32, value: usize, flags: usize) -> SbiRet {
+        T::set(self, feature, value, flags)
+    }
+    #[inline]
+    fn get(&self, feature: u32) -> SbiRet {
+        T::get(self, feature)
+    }
+}
+
+impl<T: Fwft> Fwft for Option<T> {
+    #[inline]
+    fn set(&self, feature: u32, value: usize, flags: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::set(inner, feature, value, flags)
+        })
+    }
+    #[inline]
+    fn get(&self, feature: u32) -> SbiRet {
+        self.as_ref()
+            .map_or(SbiRet::not_supported(), |inner| T::get(inner, feature))
+    }
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        match self {
+            Some(_) => sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1),
+            None => sbi_spec::base::UNAVAILABLE_EXTENSION,
+        }
+    }
+}
@@ -468,6 +468,10 @@
 //!
 //! RustSBI would interact well with custom extension environments in this way.
 //!
+//! To inspect which SBI calls the guest makes, any RustSBI implementation may be wrapped in
+//! the [`Traced`] middleware, which counts calls on each extension and records every call
+//! into a trace sink such as [`trace::RingBuffer`], without modifying the extensions themselves.
+//!
 //! ## Emulators using RustSBI
 //!
 //! RustSBI library may be used to write RISC-V emulators. Other than hardware accelerated binary
@@ -542,17 +546,23 @@
 
 mod console;
 mod cppc;
+mod dbtr;
+mod extension;
+mod fwft;
 mod hsm;
 mod ipi;
+mod mpxy;
 mod nacl;
 mod pmu;
 mod reset;
 mod rfence;
+mod sse;
 mod sta;
 mod susp;
 mod timer;
 
 mod forward;
+pub mod trace;
 mod traits;
 
 /// The RustSBI logo without blank lines on the beginning.
@@ -563,10 +573,6 @@ pub const LOGO: &str = r".______       __    __      _______.___________.  _____
 |  |\  \----.|  `--'  |.----)   |      |  |  .----)   |   |  |_)  ||  |
 | _| `._____| \______/ |_______/       |__|  |_______/    |______/ |__|";
 
-// RustSBI supports RISC-V SBI specification 2.0 ratified.
-const SBI_SPEC_MAJOR: usize = 2;
-const SBI_SPEC_MINOR: usize = 0;
-
 /// RustSBI implementation ID: 4.
 ///
 /// Ref: <https://github.com/riscv-non-isa/riscv-sbi-doc/pull/61>
@@ -583,7 +589,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub extern crate sbi_spec as spec;
 
-pub use sbi_spec::binary::{CounterMask, HartMask, Physical, SbiRet, SharedPtr};
+pub use sbi_spec::binary::{CounterMask, HartMask, Physical, SbiRet, SharedPtr, TriggerMask};
 
 /// Generate `RustSBI` implementation for structure of each extension.
 ///
@@ -842,6 +848,24 @@ pub use sbi_spec::binary::{CounterMask, HartMask, Physical, SbiRet, SharedPtr};
 /// | `cppc` | [`Cppc`](trait.Cppc.html) | SBI CPPC extension |
 /// | `nacl` | [`Nacl`](trait.Nacl.html) | Nested Acceleration extension |
 /// | `sta` | [`Sta`](trait.Sta.html) | Steal Time Accounting extension |
+/// | `fwft` | [`Fwft`](trait.Fwft.html) | Firmware Features extension |
+/// | `sse` | [`Sse`](trait.Sse.html) | Supervisor Software Events extension |
+/// | `dbtr` | [`Dbtr`](trait.Dbtr.html) | Debug Triggers extension |
+/// | `mpxy` | [`Mpxy`](trait.Mpxy.html) | Message Proxy extension |
+///
+/// Vendor-specific and firmware-specific extensions are not identified by field names; instead, fields
+/// implementing [`ExtensionHandler`](trait.ExtensionHandler.html) are bound to an extension ID or an
+/// inclusive range of extension IDs using the `vendor` or `firmware` attribute:
+///
+/// | Field attribute | Extension ID range |
+/// |:----------------|:-------------------|
+/// | `#[rustsbi(vendor(eid = ...))]` | `0x0900_0000` to `0x09FF_FFFF` |
+/// | `#[rustsbi(firmware(eid = ...))]` | `0x0A00_0000` to `0x0AFF_FFFF` |
+///
+/// The `eid` value is either a single integer literal, or an inclusive range like `0x0900_0000..=0x0900_00FF`.
+/// Extension IDs outside the range of the attribute are rejected at compile time. In static mode, extension
+/// ID ranges of different fields must not overlap; in dynamic mode, the first field which probes the extension
+/// as available handles the call.
 ///
 /// The `EnvInfo` parameter is used by RISC-V SBI Base extension which is always supported on all
 /// RISC-V SBI implementations. RustSBI provides the Base extension with additional `EnvInfo` by default.
@@ -856,6 +880,34 @@ pub use sbi_spec::binary::{CounterMask, HartMask, Physical, SbiRet, SharedPtr};
 /// If we are targeting other environments (virtualization etc.), we should provide `EnvInfo` instead
 /// of using the machine feature.
 ///
+/// The SBI specification version reported by the Base extension is selected using the
+/// `#[rustsbi(spec_version = "...")]` structure attribute, and defaults to version `"2.0"`.
+/// Extensions introduced in a later version of the specification than the reported one are
+/// rejected at compile time; for example, implementations providing `fwft`, `sse`, `dbtr` or
+/// `mpxy` should report version `"3.0"`:
+///
+/// ```rust
+/// # use rustsbi::RustSBI;
+/// #[derive(RustSBI)]
+/// #[rustsbi(spec_version = "3.0")]
+/// struct MySBI {
+///     fwft: MyFwft,
+///     info: MyEnvInfo,
+/// }
+/// # use sbi_spec::binary::SbiRet;
+/// # struct MyFwft;
+/// # impl rustsbi::Fwft for MyFwft {
+/// #     fn set(&self, _: u32, _: usize, _: usize) -> SbiRet { unimplemented!() }
+/// #     fn get(&self, _: u32) -> SbiRet { unimplemented!() }
+/// # }
+/// # struct MyEnvInfo;
+/// # impl rustsbi::EnvInfo for MyEnvInfo {
+/// #     fn mvendorid(&self) -> usize { 1 }
+/// #     fn marchid(&self) -> usize { 2 }
+/// #     fn mimpid(&self) -> usize { 3 }
+/// # }
+/// ```
+///
 /// # Examples
 ///
 /// This macro should be used over a struct of RISC-V SBI extension implementations.
@@ -1127,17 +1179,23 @@ pub use rustsbi_macros::RustSBI;
 
 pub use console::Console;
 pub use cppc::Cppc;
+pub use dbtr::Dbtr;
+pub use extension::ExtensionHandler;
+pub use fwft::Fwft;
 pub use hsm::Hsm;
 pub use ipi::Ipi;
+pub use mpxy::Mpxy;
 pub use nacl::Nacl;
 pub use pmu::Pmu;
 pub use reset::Reset;
 pub use rfence::Rfence as Fence;
+pub use sse::Sse;
 pub use sta::Sta;
 pub use susp::Susp;
 pub use timer::Timer;
 
 pub use forward::Forward;
+pub use trace::Traced;
 pub use traits::{EnvInfo, RustSBI};
 
 // Macro internal functions and structures
@@ -1148,12 +1206,15 @@ pub use traits::_rustsbi_base_bare;
 #[doc(hidden)]
 pub use traits::{
     _ExtensionProbe, _StandardExtensionProbe, _rustsbi_base_env_info, _rustsbi_console,
-    _rustsbi_cppc, _rustsbi_fence, _rustsbi_hsm, _rustsbi_ipi, _rustsbi_nacl, _rustsbi_pmu,
-    _rustsbi_reset, _rustsbi_sta, _rustsbi_susp, _rustsbi_timer,
+    _rustsbi_cppc, _rustsbi_dbtr, _rustsbi_extension_handler, _rustsbi_fence, _rustsbi_fwft,
+    _rustsbi_hsm, _rustsbi_ipi, _rustsbi_mpxy, _rustsbi_nacl, _rustsbi_pmu, _rustsbi_reset,
+    _rustsbi_sse, _rustsbi_sta, _rustsbi_susp, _rustsbi_timer,
 };
 #[doc(hidden)]
 pub use traits::{
-    _rustsbi_console_probe, _rustsbi_cppc_probe, _rustsbi_fence_probe, _rustsbi_hsm_probe,
-    _rustsbi_ipi_probe, _rustsbi_nacl_probe, _rustsbi_pmu_probe, _rustsbi_reset_probe,
-    _rustsbi_sta_probe, _rustsbi_susp_probe, _rustsbi_timer_probe,
+    _rustsbi_console_probe, _rustsbi_cppc_probe, _rustsbi_dbtr_probe,
+    _rustsbi_extension_handler_probe, _rustsbi_fence_probe, _rustsbi_fwft_probe,
+    _rustsbi_hsm_probe, _rustsbi_ipi_probe, _rustsbi_mpxy_probe, _rustsbi_nacl_probe,
+    _rustsbi_pmu_probe, _rustsbi_reset_probe, _rustsbi_sse_probe, _rustsbi_sta_probe,
+    _rustsbi_susp_probe, _rustsbi_timer_probe,
 };
@@ -0,0 +1,322 @@
+use sbi_spec::binary::{SbiRet, SharedPtr};
+
+/// Message Proxy extension.
+///
+/// The SBI Message Proxy (MPXY) extension provides a generic interface for supervisor
+/// software to send and receive messages through message channels managed by the SBI
+/// implementation. Each message channel is identified by a 32-bit unsigned `channel_id`
+/// and is bound to a message protocol, such as RPMI or the Management Mode protocol;
+/// the format of messages sent through a channel is defined by its message protocol.
+///
+/// A message channel is described by a set of 32-bit attributes; standard attribute
+/// identifiers are defined in [`sbi_spec::mpxy::attr_id`]. The `CHANNEL_CAPABILITY`
+/// attribute tells supervisor software which optional functions of this extension the
+/// channel supports.
+///
+/// Messages, channel IDs, attributes and notification events are exchanged through a
+/// per-hart shared memory set up by [`set_shmem`](Mpxy::set_shmem).
+pub trait Mpxy {
+    /// Get the shared memory size in number of bytes for sending and receiving messages.
+    ///
+    /// The shared memory size MUST be the same for all harts, MUST be at least 4096 bytes
+    /// and a multiple of 4096 bytes, and MUST not be less than the biggest `MSG_MAX_LEN`
+    /// attribute value across all message channels.
+    fn get_shmem_size(&self) -> usize;
+    /// Set the shared memory for sending and receiving messages on the calling hart.
+    ///
+    /// If `shmem` is not all-ones bitwise, it specifies the shared memory physical base
+    /// address, which MUST be 4096 bytes aligned; the size of the shared memory is the
+    /// value returned by [`get_shmem_size`](Mpxy::get_shmem_size). If `shmem` is all-ones
+    /// bitwise, the shared memory is disabled.
+    ///
+    /// The lower two bits of `flags` select the shared memory setup mode: `0b00` overwrites
+    /// the current shared memory state, while `0b01` additionally writes the old shared memory
+    /// address to offset `0x0` of the new shared memory. Other bits are reserved and MUST be zero.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Shared memory was set or cleared successfully.
+    /// | `SbiRet::invalid_param()`   | The `flags` parameter has an invalid value or reserved bits set, or `shmem` is not 4096 bytes aligned.
+    /// | `SbiRet::invalid_address()` | The shared memory pointed to by the `shmem` parameter does not satisfy the requirements.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet;
+    /// Get channel IDs of the message channels accessible to the supervisor software,
+    /// written into the shared memory of the calling hart.
+    ///
+    /// The SBI implementation writes the number of remaining channel IDs at offset `0x0`,
+    /// the number `N` of returned channel IDs at offset `0x4`, followed by `N` 32-bit
+    /// channel IDs starting from array index `start_index` at offset `0x8`.
+    ///
+    /// # Return value
+    ///
+    /// The `SbiRet.value` is always set to zero whereas the possible return error codes
+    /// returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | The channel ID array has been written successfully.
+    /// | `SbiRet::invalid_param()`   | `start_index` is invalid.
+    /// | `SbiRet::no_shmem()`        | The shared memory setup is not done or disabled for the calling hart.
+    /// | `SbiRet::denied()`          | Getting channel ID array is not allowed on the calling hart.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn get_channel_ids(&self, start_index: u32) -> SbiRet;
+    /// Read a range of message channel attributes into the shared memory of the calling hart.
+    ///
+    /// The value of the attribute with ID `base_attribute_id + i` is written at offset
+    /// `4 * i` of the shared memory.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Message channel attributes have been read successfully.
+    /// | `SbiRet::invalid_param()`   | `attribute_count` is 0, or `attribute_count > (shared memory size) / 4`, or `base_attribute_id` is not valid.
+    /// | `SbiRet::not_supported()`   | `channel_id` is not supported or invalid.
+    /// | `SbiRet::bad_range()`       | One of the attributes in the range specified by `base_attribute_id` and `attribute_count` does not exist.
+    /// | `SbiRet::no_shmem()`        | The shared memory setup is not done or disabled for the calling hart.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn read_attributes(
+        &self,
+        channel_id: u32,
+        base_attribute_id: u32,
+        attribute_count: u32,
+    ) -> SbiRet;
+    /// Write a range of message channel attributes from the shared memory of the calling hart.
+    ///
+    /// The value of the attribute with ID `base_attribute_id + i` is read from offset
+    /// `4 * i` of the shared memory.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Message channel attributes have been written successfully.
+    /// | `SbiRet::invalid_param()`   | `attribute_count` is 0, or `attribute_count > (shared memory size) / 4`, or `base_attribute_id` is not valid.
+    /// | `SbiRet::not_supported()`   | `channel_id` is not supported or invalid.
+    /// | `SbiRet::bad_range()`       | One of the attributes in the range specified by `base_attribute_id` and `attribute_count` does not exist, or is read-only.
+    /// | `SbiRet::no_shmem()`        | The shared memory setup is not done or disabled for the calling hart.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn write_attributes(
+        &self,
+        channel_id: u32,
+        base_attribute_id: u32,
+        attribute_count: u32,
+    ) -> SbiRet;
+    /// Send a message to the message channel and wait until a message response is received.
+    ///
+    /// The `message_id` parameter is the message protocol specific identification of the
+    /// message, and the message data of `message_data_len` bytes is located at offset `0x0`
+    /// of the shared memory of the calling hart.
+    ///
+    /// Upon success, the response message data is written at offset `0x0` of the shared
+    /// memory, and its length in bytes is returned in `SbiRet.value`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Message sent and response received successfully.
+    /// | `SbiRet::invalid_param()`   | `message_data_len` is greater than `MSG_MAX_LEN` of the channel or than the size of the shared memory.
+    /// | `SbiRet::not_supported()`   | `channel_id` or `message_id` is not supported or invalid, or this function is not supported.
+    /// | `SbiRet::no_shmem()`        | The shared memory setup is not done or disabled for the calling hart.
+    /// | `SbiRet::timeout()`         | Waiting for response timed out.
+    /// | `SbiRet::io()`              | Failed due to I/O error.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn send_message_with_response(
+        &self,
+        channel_id: u32,
+        message_id: u32,
+        message_data_len: usize,
+    ) -> SbiRet;
+    /// Send a message to the message channel without waiting for a message response.
+    ///
+    /// The `message_id` parameter is the message protocol specific identification of the
+    /// message, and the message data of `message_data_len` bytes is located at offset `0x0`
+    /// of the shared memory of the calling hart.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Message sent successfully.
+    /// | `SbiRet::invalid_param()`   | `message_data_len` is greater than `MSG_MAX_LEN` of the channel or than the size of the shared memory.
+    /// | `SbiRet::not_supported()`   | `channel_id` or `message_id` is not supported or invalid, or this function is not supported.
+    /// | `SbiRet::no_shmem()`        | The shared memory setup is not done or disabled for the calling hart.
+    /// | `SbiRet::timeout()`         | Message send timed out.
+    /// | `SbiRet::io()`              | Failed due to I/O error.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn send_message_without_response(
+        &self,
+        channel_id: u32,
+        message_id: u32,
+        message_data_len: usize,
+    ) -> SbiRet;
+    /// Get the message protocol specific notification events on the message channel.
+    ///
+    /// The events state (`REMAINING`, `RETURNED` and `LOST` counters) is written to the
+    /// first 16 bytes of the shared memory of the calling hart, and the notification events
+    /// data is written starting at offset `0x10`. The number of bytes of events data is
+    /// returned in `SbiRet.value`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Notifications received successfully.
+    /// | `SbiRet::not_supported()`   | `channel_id` is not supported or invalid, or this function is not supported.
+    /// | `SbiRet::no_shmem()`        | The shared memory setup is not done or disabled for the calling hart.
+    /// | `SbiRet::io()`              | Failed due to I/O error.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn get_notification_events(&self, channel_id: u32) -> SbiRet;
+    /// Function internal to macros. Do not use.
+    #[doc(hidden)]
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1)
+    }
+}
+
+impl<T: Mpxy> Mpxy for &T {
+    #[inline]
+    fn get_shmem_size(&self) -> usize {
+        T::get_shmem_size(self)
+    }
+    #[inline]
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet {
+        T::set_shmem(self, shmem, flags)
+    }
+    #[inline]
+    fn get_channel_ids(&self, start_index: u32) -> SbiRet {
+        T::get_channel_ids(self, start_index)
+    }
+    #[inline]
+    fn read_attributes(
+        &self,
+        channel_id: u32,
+        base_attribute_id: u32,
+        attribute_count: u32,
+    ) -> SbiRet {
+        T::read_attributes(self, channel_id, base_attribute_id, attribute_count)
+    }
+    #[inline]
+    fn write_attributes(
+        &self,
+        channel_id: u32,
+        base_attribute_id: u32,
+        attribute_count: u32,
+    ) -> SbiRet {
+        T::write_attributes(self, channel_id, base_attribute_id, attribute_count)
+    }
+    #[inline]
+    fn send_message_with_response(
+        &self,
+        channel_id: u32,
+        message_id: u32,
+        message_data_len: usize,
+    ) -> SbiRet {
+        T::send_message_with_response(self, channel_id, message_id, message_data_len)
+    }
+    #[inline]
+    fn send_message_without_response(
+        &self,
+        channel_id: u32,
+        message_id: u32,
+        message_data_len: usize,
+    ) -> SbiRet {
+        T::send_message_without_response(self, channel_id, message_id, message_data_len)
+    }
+    #[inline]
+    fn get_notification_events(&self, channel_id: u32) -> SbiRet {
+        T::get_notification_events(self, channel_id)
+    }
+}
+
+impl<T: Mpxy> Mpxy for Option<T> {
+    #[inline]
+    fn get_shmem_size(&self) -> usize {
+        self.as_ref().map_or(0, |inner| T::get_shmem_size(inner))
+    }
+    #[inline]
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::set_shmem(inner, shmem, flags)
+        })
+    }
+    #[inline]
+    fn get_channel_ids(&self, start_index: u32) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::get_channel_ids(inner, start_index)
+        })
+    }
+    #[inline]
+    fn read_attributes(
+        &self,
+        channel_id: u32,
+        base_attribute_id: u32,
+        attribute_count: u32,
+    ) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::read_attributes(inner, channel_id, base_attribute_id, attribute_count)
+        })
+    }
+    #[inline]
+    fn write_attributes(
+        &self,
+        channel_id: u32,
+        base_attribute_id: u32,
+        attribute_count: u32,
+    ) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::write_attributes(inner, channel_id, base_attribute_id, attribute_count)
+        })
+    }
+    #[inline]
+    fn send_message_with_response(
+        &self,
+        channel_id: u32,
+        message_id: u32,
+        message_data_len: usize,
+    ) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::send_message_with_response(inner, channel_id, message_id, message_data_len)
+        })
+    }
+    #[inline]
+    fn send_message_without_response(
+        &self,
+        channel_id: u32,
+        message_id: u32,
+        message_data_len: usize,
+    ) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::send_message_without_response(inner, channel_id, message_id, message_data_len)
+        })
+    }
+    #[inline]
+    fn get_notification_events(&self, channel_id: u32) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::get_notification_events(inner, channel_id)
+        })
+    }
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        match self {
+            Some(_) => sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1),
+            None => sbi_spec::base::UNAVAILABLE_EXTENSION,
+        }
+    }
+}
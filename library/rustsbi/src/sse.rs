@@ -0,0 +1,341 @@
+use sbi_spec::binary::{Physical, SbiRet};
+
+/// Supervisor Software Events extension.
+///
+/// The SBI Supervisor Software Events (SSE) extension provides a mechanism to
+/// inject software events from an SBI implementation to supervisor software such
+/// that it preempts all other traps and interrupts. The supervisor software will
+/// receive software events only on harts which are ready to receive them.
+/// A software event is delivered only after supervisor software has registered
+/// an event handler and enabled the software event.
+///
+/// Software events are identified by a 32-bit unsigned integer called `event_id`;
+/// standard event identifiers are defined in [`sbi_spec::sse::event_id`]. A software
+/// event can be either local (specific to a hart) or global, where bit 15 of
+/// `event_id` is set for global events. Each software event has a set of XLEN-bit
+/// attributes identified by 32-bit `attr_id`s, defined in [`sbi_spec::sse::attr_id`].
+///
+/// For local events, the functions of this extension operate on the calling hart
+/// only; for global events, they operate on all harts.
+pub trait Sse {
+    /// Read a range of event attribute values from a software event.
+    ///
+    /// # Parameters
+    ///
+    /// The `event_id` parameter specifies the software event ID whereas `base_attr_id`
+    /// specifies the first attribute ID of the range to be read.
+    ///
+    /// The `output` parameter specifies the output shared memory, including its length
+    /// and memory physical base address (both lower and upper bits). It MUST be `XLEN / 8`
+    /// bytes aligned, and its length is `(XLEN / 8) * attr_count` bytes where `attr_count`
+    /// is the number of attributes to be read. The value of event attribute with ID
+    /// `base_attr_id + i` should be written to offset `(XLEN / 8) * i` of the output memory.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event attribute values read successfully.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid or `attr_count` is zero.
+    /// | `SbiRet::bad_range()`       | One of the event attribute IDs in the range specified by `base_attr_id` and `attr_count` is reserved.
+    /// | `SbiRet::invalid_address()` | The shared memory pointed to by the `output` parameter does not satisfy the requirements.
+    /// | `SbiRet::failed()`          | The read failed for unspecified or unknown other reasons.
+    fn read_attrs(
+        &self,
+        event_id: u32,
+        base_attr_id: u32,
+        output: Physical<&mut [usize]>,
+    ) -> SbiRet;
+    /// Write a range of event attribute values to a software event.
+    ///
+    /// # Parameters
+    ///
+    /// The `event_id` parameter specifies the software event ID whereas `base_attr_id`
+    /// specifies the first attribute ID of the range to be written.
+    ///
+    /// The `input` parameter specifies the input shared memory, including its length
+    /// and memory physical base address (both lower and upper bits). It MUST be `XLEN / 8`
+    /// bytes aligned, and its length is `(XLEN / 8) * attr_count` bytes where `attr_count`
+    /// is the number of attributes to be written. The value of event attribute with ID
+    /// `base_attr_id + i` should be read from offset `(XLEN / 8) * i` of the input memory.
+    ///
+    /// For local events, the event attributes are updated only for the calling hart.
+    /// For global events, the event attributes are updated for all the harts.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event attribute values written successfully.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid or `attr_count` is zero.
+    /// | `SbiRet::bad_range()`       | One of the event attribute IDs in the range specified by `base_attr_id` and `attr_count` is reserved or is read-only.
+    /// | `SbiRet::invalid_address()` | The shared memory pointed to by the `input` parameter does not satisfy the requirements.
+    /// | `SbiRet::failed()`          | The write failed for unspecified or unknown other reasons.
+    fn write_attrs(&self, event_id: u32, base_attr_id: u32, input: Physical<&[usize]>) -> SbiRet;
+    /// Register an event handler for the software event.
+    ///
+    /// # Parameters
+    ///
+    /// The `event_id` parameter specifies the event ID for which an event handler is being
+    /// registered. The `handler_entry_pc` parameter MUST be 2-bytes aligned and specifies
+    /// the `ENTRY_PC` event attribute of the software event, whereas the `handler_entry_arg`
+    /// parameter specifies the `ENTRY_ARG` event attribute of the software event.
+    ///
+    /// The event MUST be in `UNUSED` state otherwise this function will fail. Upon success,
+    /// the event state moves from `UNUSED` to `REGISTERED`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event handler is registered successfully.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_state()`   | `event_id` is valid but the event is not in `UNUSED` state.
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid or `handler_entry_pc` is not 2-bytes aligned.
+    fn register(&self, event_id: u32, handler_entry_pc: usize, handler_entry_arg: usize) -> SbiRet;
+    /// Unregister the event handler for given `event_id`.
+    ///
+    /// The event MUST be in `REGISTERED` state otherwise this function will fail. Upon success,
+    /// the event state moves from `REGISTERED` to `UNUSED`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event handler is unregistered successfully.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_state()`   | `event_id` is valid but the event is not in `REGISTERED` state.
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid.
+    fn unregister(&self, event_id: u32) -> SbiRet;
+    /// Enable the software event specified by the `event_id` parameter.
+    ///
+    /// The event MUST be in `REGISTERED` state otherwise this function will fail. Upon success,
+    /// the event state moves from `REGISTERED` to `ENABLED`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event is successfully enabled.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid.
+    /// | `SbiRet::invalid_state()`   | `event_id` is valid but the event is not in `REGISTERED` state.
+    fn enable(&self, event_id: u32) -> SbiRet;
+    /// Disable the software event specified by the `event_id` parameter.
+    ///
+    /// The event MUST be in `ENABLED` state otherwise this function will fail. Upon success,
+    /// the event state moves from `ENABLED` to `REGISTERED`.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event is successfully disabled.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid.
+    /// | `SbiRet::invalid_state()`   | `event_id` is valid but the event is not in `ENABLED` state.
+    fn disable(&self, event_id: u32) -> SbiRet;
+    /// Complete the supervisor event handling for the highest priority event in `RUNNING`
+    /// state on the calling hart.
+    ///
+    /// If there were no events in `RUNNING` state on the calling hart then this function
+    /// does nothing and returns `SbiRet::success()`; otherwise it moves the highest priority
+    /// event in `RUNNING` state to `REGISTERED` if the event is configured as one-shot,
+    /// or to `ENABLED` state otherwise.
+    ///
+    /// It then resumes the interrupted supervisor state; the SBI implementation is expected
+    /// to restore the interrupted `sepc`, flags, `a6` and `a7` from the event attributes
+    /// instead of returning to the caller.
+    fn complete(&self) -> SbiRet;
+    /// Inject a software event.
+    ///
+    /// # Parameters
+    ///
+    /// The `event_id` parameter refers to the ID of the event to be injected.
+    ///
+    /// For local events, the `hart_id` parameter refers to the hart on which the event
+    /// is to be injected. For global events, the `hart_id` parameter is ignored.
+    ///
+    /// An event can only be injected if it is allowed by the event attribute. If an event
+    /// is injected from within an SSE event handler and it is ready to be run, it will be
+    /// handled according to the priority rules: if it has a higher priority than the one
+    /// currently running, it is handled immediately, effectively preempting the currently
+    /// running one; if it has a lower priority, it runs after the current one completes.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Event is successfully injected.
+    /// | `SbiRet::not_supported()`   | `event_id` is not reserved and valid, but the platform does not support it due to one or more missing dependencies (hardware or SBI implementation).
+    /// | `SbiRet::invalid_param()`   | `event_id` is invalid or `hart_id` is invalid.
+    /// | `SbiRet::failed()`          | The injection failed for unspecified or unknown other reasons.
+    fn inject(&self, event_id: u32, hart_id: usize) -> SbiRet;
+    /// Start receiving (or unmask) software events on the calling hart.
+    ///
+    /// Software events are masked initially on all harts, so the supervisor software
+    /// must explicitly unmask software events on relevant harts at boot-time.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Software events unmasked successfully on the calling hart.
+    /// | `SbiRet::already_started()` | Software events were already unmasked on the calling hart.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn hart_unmask(&self) -> SbiRet;
+    /// Stop receiving (or mask) software events on the calling hart.
+    ///
+    /// # Return value
+    ///
+    /// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+    ///
+    /// | Return code                 | Description
+    /// |:----------------------------|:----------------------------------------------
+    /// | `SbiRet::success()`         | Software events masked successfully on the calling hart.
+    /// | `SbiRet::already_stopped()` | Software events were already masked on the calling hart.
+    /// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+    fn hart_mask(&self) -> SbiRet;
+    /// Function internal to macros. Do not use.
+    #[doc(hidden)]
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1)
+    }
+}
+
+impl<T: Sse> Sse for &T {
+    #[inline]
+    fn read_attrs(
+        &self,
+        event_id: u32,
+        base_attr_id: u32,
+        output: Physical<&mut [usize]>,
+    ) -> SbiRet {
+        T::read_attrs(self, event_id, base_attr_id, output)
+    }
+    #[inline]
+    fn write_attrs(&self, event_id: u32, base_attr_id: u32, input: Physical<&[usize]>) -> SbiRet {
+        T::write_attrs(self, event_id, base_attr_id, input)
+    }
+    #[inline]
+    fn register(&self, event_id: u32, handler_entry_pc: usize, handler_entry_arg: usize) -> SbiRet {
+        T::register(self, event_id, handler_entry_pc, handler_entry_arg)
+    }
+    #[inline]
+    fn unregister(&self, event_id: u32) -> SbiRet {
+        T::unregister(self, event_id)
+    }
+    #[inline]
+    fn enable(&self, event_id: u32) -> SbiRet {
+        T::enable(self, event_id)
+    }
+    #[inline]
+    fn disable(&self, event_id: u32) -> SbiRet {
+        T::disable(self, event_id)
+    }
+    #[inline]
+    fn complete(&self) -> SbiRet {
+        T::complete(self)
+    }
+    #[inline]
+    fn inject(&self, event_id: u32, hart_id: usize) -> SbiRet {
+        T::inject(self, event_id, hart_id)
+    }
+    #[inline]
+    fn hart_unmask(&self) -> SbiRet {
+        T::hart_unmask(self)
+    }
+    #[inline]
+    fn hart_mask(&self) -> SbiRet {
+        T::hart_mask(self)
+    }
+}
+
+impl<T: Sse> Sse for Option<T> {
+    #[inline]
+    fn read_attrs(
+        &self,
+        event_id: u32,
+        base_attr_id: u32,
+        output: Physical<&mut [usize]>,
+    ) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::read_attrs(inner, event_id, base_attr_id, output)
+        })
+    }
+    #[inline]
+    fn write_attrs(&self, event_id: u32, base_attr_id: u32, input: Physical<&[usize]>) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::write_attrs(inner, event_id, base_attr_id, input)
+        })
+    }
+    #[inline]
+    fn register(&self, event_id: u32, handler_entry_pc: usize, handler_entry_arg: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::register(inner, event_id, handler_entry_pc, handler_entry_arg)
+        })
+    }
+    #[inline]
+    fn unregister(&self, event_id: u32) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::unregister(inner, event_id)
+        })
+    }
+    #[inline]
+    fn enable(&self, event_id: u32) -> SbiRet {
+        self.as_ref()
+            .map_or(SbiRet::not_supported(), |inner| T::enable(inner, event_id))
+    }
+    #[inline]
+    fn disable(&self, event_id: u32) -> SbiRet {
+        self.as_ref()
+            .map_or(SbiRet::not_supported(), |inner| T::disable(inner, event_id))
+    }
+    #[inline]
+    fn complete(&self) -> SbiRet {
+        self.as_ref()
+            .map_or(SbiRet::not_supported(), |inner| T::complete(inner))
+    }
+    #[inline]
+    fn inject(&self, event_id: u32, hart_id: usize) -> SbiRet {
+        self.as_ref().map_or(SbiRet::not_supported(), |inner| {
+            T::inject(inner, event_id, hart_id)
+        })
+    }
+    #[inline]
+    fn hart_unmask(&self) -> SbiRet {
+        self.as_ref()
+            .map_or(SbiRet::not_supported(), |inner| T::hart_unmask(inner))
+    }
+    #[inline]
+    fn hart_mask(&self) -> SbiRet {
+        self.as_ref()
+            .map_or(SbiRet::not_supported(), |inner| T::hart_mask(inner))
+    }
+    #[inline]
+    fn _rustsbi_probe(&self) -> usize {
+        match self {
+            Some(_) => sbi_spec::base::UNAVAILABLE_EXTENSION.wrapping_add(1),
+            None => sbi_spec::base::UNAVAILABLE_EXTENSION,
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! SBI call tracing and metrics.
+//!
+//! This module provides [`Traced`], a middleware recording every SBI call handled by a wrapped
+//! RustSBI implementation, along with trace sinks receiving the records of these calls.
+
+use crate::RustSBI;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use sbi_spec::binary::SbiRet;
+
+/// Tracing and metrics middleware over any RustSBI implementation.
+///
+/// `Traced` wraps an inner [`RustSBI`] implementation and forwards every environment call to it
+/// unmodified. For each call, it counts the call and its failure (if any) on the extension the call
+/// targets, and hands a [`TraceRecord`] of the call over to the trace sink `H`.
+///
+/// Tracing could be added to any RustSBI implementation this way without patching each extension,
+/// for example to inspect which SBI calls a guest kernel makes, how often and which of them fail.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustsbi::{RustSBI, Traced, trace::RingBuffer};
+/// # use sbi_spec::binary::SbiRet;
+///
+/// #[derive(RustSBI)]
+/// struct MySBI {
+///     info: MyEnvInfo,
+/// }
+/// # struct MyEnvInfo;
+/// # impl rustsbi::EnvInfo for MyEnvInfo {
+/// #     fn mvendorid(&self) -> usize { 1 }
+/// #     fn marchid(&self) -> usize { 2 }
+/// #     fn mimpid(&self) -> usize { 3 }
+/// # }
+///
+/// let sbi = Traced::new(MySBI { info: MyEnvInfo }, RingBuffer::<16>::new());
+/// // Call the Base extension `get_mvendorid` function.
+/// assert_eq!(sbi.handle_ecall(0x10, 4, [0; 6]), SbiRet::success(1));
+/// // Call a function which is not provided by the Timer extension.
+/// assert!(sbi.handle_ecall(0x54494D45, 0, [0; 6]).is_err());
+///
+/// assert_eq!(sbi.counters().calls(0x10), 1);
+/// assert_eq!(sbi.counters().errors(0x54494D45), 1);
+/// let last = sbi.sink().last().unwrap();
+/// assert_eq!((last.extension, last.function), (0x54494D45, 0));
+/// ```
+pub struct Traced<T, H> {
+    inner: T,
+    sink: H,
+    counters: CallCounters,
+}
+
+impl<T, H> Traced<T, H> {
+    /// Create a tracing middleware over `inner`, which records calls into `sink`.
+    #[inline]
+    pub const fn new(inner: T, sink: H) -> Self {
+        Self {
+            inner,
+            sink,
+            counters: CallCounters::new(),
+        }
+    }
+    /// Get a reference to the inner RustSBI implementation.
+    #[inline]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+    /// Get a mutable reference to the inner RustSBI implementation.
+    #[inline]
+    pub const fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+    /// Get a reference to the trace sink.
+    #[inline]
+    pub const fn sink(&self) -> &H {
+        &self.sink
+    }
+    /// Get the per-extension call counters.
+    #[inline]
+    pub const fn counters(&self) -> &CallCounters {
+        &self.counters
+    }
+    /// Unwrap this middleware, returning the inner RustSBI implementation and the trace sink.
+    #[inline]
+    pub fn into_parts(self) -> (T, H) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<T: RustSBI, H: TraceSink> RustSBI for Traced<T, H> {
+    #[inline]
+    fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+        let ret = self.inner.handle_ecall(extension, function, param);
+        self.counters.count(extension, ret);
+        self.sink.record(&TraceRecord {
+            extension,
+            function,
+            param,
+            ret,
+        });
+        ret
+    }
+}
+
+/// Record of a single SBI call handled by [`Traced`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceRecord {
+    /// Extension ID of the call, i.e. register `a7`.
+    pub extension: usize,
+    /// Function ID of the call, i.e. register `a6`.
+    pub function: usize,
+    /// Parameters of the call, i.e. registers `a0` to `a5`.
+    pub param: [usize; 6],
+    /// Return value of the call.
+    pub ret: SbiRet,
+}
+
+/// Destination of SBI call trace records.
+///
+/// Sinks are called on every SBI call with a shared reference; implementations should use
+/// interior mutability if they store records, and must be cheap enough to run on the SBI call path.
+///
+/// `()` is a sink which discards all records, and a tuple `(A, B)` of sinks records into both.
+pub trait TraceSink {
+    /// Record a handled SBI call.
+    fn record(&self, record: &TraceRecord);
+}
+
+impl<T: TraceSink> TraceSink for &T {
+    #[inline]
+    fn record(&self, record: &TraceRecord) {
+        T::record(self, record)
+    }
+}
+
+impl TraceSink for () {
+    #[inline]
+    fn record(&self, _: &TraceRecord) {}
+}
+
+impl<A: TraceSink, B: TraceSink> TraceSink for (A, B) {
+    #[inline]
+    fn record(&self, record: &TraceRecord) {
+        self.0.record(record);
+        self.1.record(record);
+    }
+}
+
+/// Trace sink keeping the latest `N` records in a fixed-size ring buffer.
+///
+/// The ring buffer does not allocate, and may be recorded into concurrently from multiple harts.
+/// A record overwritten while being read is skipped by the reader instead of returned torn.
+/// When writers lap the buffer and contend for one slot, only one of them writes its record,
+/// and the other record is dropped.
+pub struct RingBuffer<const N: usize> {
+    // total number of records ever started; the next record is written to `next % N`
+    next: AtomicUsize,
+    slots: [Slot; N],
+}
+
+// A slot is guarded by a sequence lock: `seq` is odd while the slot is being written,
+// and equals `2 * (index + 1)` after record number `index` is completely written.
+// Writers claim the slot by compare-and-swap from an even `seq` of an older record.
+struct Slot {
+    seq: AtomicUsize,
+    // extension, function, param[0..6], ret.error, ret.value
+    words: [AtomicUsize; 10],
+}
+
+impl Slot {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: Slot = Slot {
+        seq: AtomicUsize::new(0),
+        words: [const { AtomicUsize::new(0) }; 10],
+    };
+}
+
+impl<const N: usize> RingBuffer<N> {
+    /// Create an empty ring buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        assert!(N > 0, "ring buffer capacity must not be zero");
+        Self {
+            next: AtomicUsize::new(0),
+            slots: [Slot::EMPTY; N],
+        }
+    }
+    /// Maximum number of records kept by this ring buffer.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    /// Total number of records ever written into this ring buffer, including overwritten and
+    /// dropped ones.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.next.load(Ordering::Acquire)
+    }
+    /// Get the record with the given sequence index, if it is still kept by the ring buffer.
+    ///
+    /// Sequence indices start from zero for the first record ever written.
+    pub fn get(&self, index: usize) -> Option<TraceRecord> {
+        let slot = &self.slots[index % N];
+        let expected = index.wrapping_add(1).wrapping_mul(2);
+        if slot.seq.load(Ordering::Acquire) != expected {
+            return None;
+        }
+        let words: [usize; 10] = core::array::from_fn(|i| slot.words[i].load(Ordering::Relaxed));
+        core::sync::atomic::fence(Ordering::Acquire);
+        if slot.seq.load(Ordering::Relaxed) != expected {
+            return None;
+        }
+        let [extension, function, a0, a1, a2, a3, a4, a5, error, value] = words;
+        Some(TraceRecord {
+            extension,
+            function,
+            param: [a0, a1, a2, a3, a4, a5],
+            ret: SbiRet { error, value },
+        })
+    }
+    /// Get the latest record in this ring buffer.
+    #[inline]
+    pub fn last(&self) -> Option<TraceRecord> {
+        self.total()
+            .checked_sub(1)
+            .and_then(|index| self.get(index))
+    }
+    /// Iterate over records kept by this ring buffer, from the oldest to the latest.
+    pub fn iter(&self) -> impl Iterator<Item = TraceRecord> + '_ {
+        let end = self.total();
+        (end.saturating_sub(N)..end).filter_map(|index| self.get(index))
+    }
+}
+
+impl<const N: usize> Default for RingBuffer<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> TraceSink for RingBuffer<N> {
+    fn record(&self, record: &TraceRecord) {
+        let index = self.next.fetch_add(1, Ordering::AcqRel);
+        let slot = &self.slots[index % N];
+        let written = index.wrapping_add(1).wrapping_mul(2);
+        let mut seq = slot.seq.load(Ordering::Relaxed);
+        loop {
+            // drop this record if another writer holds the slot, or it holds a newer record
+            if seq % 2 == 1 || written.wrapping_sub(seq) as isize <= 0 {
+                return;
+            }
+            match slot.seq.compare_exchange_weak(
+                seq,
+                written.wrapping_sub(1),
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => seq = current,
+            }
+        }
+        core::sync::atomic::fence(Ordering::Release);
+        let [a0, a1, a2, a3, a4, a5] = record.param;
+        let words = [
+            record.extension,
+            record.function,
+            a0,
+            a1,
+            a2,
+            a3,
+            a4,
+            a5,
+            record.ret.error,
+            record.ret.value,
+        ];
+        for (slot_word, word) in slot.words.iter().zip(words) {
+            slot_word.store(word, Ordering::Relaxed);
+        }
+        slot.seq.store(written, Ordering::Release);
+    }
+}
+
+/// Trace sink writing every record into the `log` facade.
+///
+/// Records of successful calls are logged at `log::Level::Trace`, and records of failed calls
+/// are logged at `log::Level::Debug`, both under log target `rustsbi::trace`.
+#[cfg(feature = "log")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LogSink;
+
+#[cfg(feature = "log")]
+impl TraceSink for LogSink {
+    #[inline]
+    fn record(&self, record: &TraceRecord) {
+        let level = if record.ret.is_ok() {
+            log::Level::Trace
+        } else {
+            log::Level::Debug
+        };
+        log::log!(
+            target: "rustsbi::trace",
+            level,
+            "ecall eid {:#x} fid {:#x} param {:x?} -> {:?}",
+            record.extension,
+            record.function,
+            record.param,
+            record.ret
+        );
+    }
+}
+
+/// Standard extensions counted separately by [`CallCounters`].
+const COUNTED_EXTENSIONS: [usize; 16] = [
+    sbi_spec::base::EID_BASE,
+    sbi_spec::time::EID_TIME,
+    sbi_spec::spi::EID_SPI,
+    sbi_spec::rfnc::EID_RFNC,
+    sbi_spec::hsm::EID_HSM,
+    sbi_spec::srst::EID_SRST,
+    sbi_spec::pmu::EID_PMU,
+    sbi_spec::dbcn::EID_DBCN,
+    sbi_spec::susp::EID_SUSP,
+    sbi_spec::cppc::EID_CPPC,
+    sbi_spec::nacl::EID_NACL,
+    sbi_spec::sta::EID_STA,
+    sbi_spec::fwft::EID_FWFT,
+    sbi_spec::sse::EID_SSE,
+    sbi_spec::dbtr::EID_DBTR,
+    sbi_spec::mpxy::EID_MPXY,
+];
+
+/// Per-extension SBI call counters maintained by [`Traced`].
+///
+/// Each standard extension has its own call and error counters. Calls to all other extension IDs,
+/// including legacy, vendor-specific, firmware-specific and unknown extensions, share one pair of
+/// counters; querying any of these extension IDs returns the shared count.
+pub struct CallCounters {
+    calls: [AtomicUsize; COUNTED_EXTENSIONS.len() + 1],
+    errors: [AtomicUsize; COUNTED_EXTENSIONS.len() + 1],
+}
+
+impl CallCounters {
+    /// Create counters with all counts being zero.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            calls: [const { AtomicUsize::new(0) }; COUNTED_EXTENSIONS.len() + 1],
+            errors: [const { AtomicUsize::new(0) }; COUNTED_EXTENSIONS.len() + 1],
+        }
+    }
+    /// Number of calls made to the given extension.
+    #[inline]
+    pub fn calls(&self, extension: usize) -> usize {
+        self.calls[Self::slot(extension)].load(Ordering::Relaxed)
+    }
+    /// Number of calls made to the given extension which returned an error.
+    #[inline]
+    pub fn errors(&self, extension: usize) -> usize {
+        self.errors[Self::slot(extension)].load(Ordering::Relaxed)
+    }
+    /// Total number of calls made to all extensions.
+    #[inline]
+    pub fn total_calls(&self) -> usize {
+        self.calls.iter().map(|c| c.load(Ordering::Relaxed)).sum()
+    }
+    /// Iterate over `(extension, calls, errors)` of all standard extensions, followed by
+    /// `(None, calls, errors)` for all other extension IDs.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<usize>, usize, usize)> + '_ {
+        COUNTED_EXTENSIONS
+            .iter()
+            .map(|&extension| Some(extension))
+            .chain([None])
+            .zip(self.calls.iter().zip(&self.errors))
+            .map(|(extension, (calls, errors))| {
+                (
+                    extension,
+                    calls.load(Ordering::Relaxed),
+                    errors.load(Ordering::Relaxed),
+                )
+            })
+    }
+    /// Reset all counts to zero.
+    pub fn reset(&self) {
+        for counter in self.calls.iter().chain(&self.errors) {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    fn count(&self, extension: usize, ret: SbiRet) {
+        let slot = Self::slot(extension);
+        self.calls[slot].fetch_add(1, Ordering::Relaxed);
+        if ret.is_err() {
+            self.errors[slot].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    fn slot(extension: usize) -> usize {
+        COUNTED_EXTENSIONS
+            .iter()
+            .position(|&e| e == extension)
+            .unwrap_or(COUNTED_EXTENSIONS.len())
+    }
+}
+
+impl Default for CallCounters {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(feature = "machine")]
 use riscv::register::{marchid, mimpid, mvendorid};
-use spec::binary::{HartMask, Physical, SbiRet, SharedPtr};
+use spec::binary::{HartMask, Physical, SbiRet, SharedPtr, TriggerMask};
 
 /// RustSBI environment call handler.
 pub trait RustSBI {
@@ -57,11 +57,12 @@ impl<T: EnvInfo> EnvInfo for &T {
 pub fn _rustsbi_base_bare<U: _ExtensionProbe>(
     param: [usize; 6],
     function: usize,
+    spec_version: usize,
     probe: U,
 ) -> SbiRet {
     let [param0] = [param[0]];
     let value = match function {
-        spec::base::GET_SBI_SPEC_VERSION => (crate::SBI_SPEC_MAJOR << 24) | (crate::SBI_SPEC_MINOR),
+        spec::base::GET_SBI_SPEC_VERSION => spec_version,
         spec::base::GET_SBI_IMPL_ID => crate::IMPL_ID_RUSTSBI,
         spec::base::GET_SBI_IMPL_VERSION => crate::RUSTSBI_VERSION,
         spec::base::PROBE_EXTENSION => probe.probe_extension(param0),
@@ -78,12 +79,13 @@ pub fn _rustsbi_base_bare<U: _ExtensionProbe>(
 pub fn _rustsbi_base_env_info<T: EnvInfo, U: _ExtensionProbe>(
     param: [usize; 6],
     function: usize,
+    spec_version: usize,
     env_info: &T,
     probe: U,
 ) -> SbiRet {
     let [param0] = [param[0]];
     let value = match function {
-        spec::base::GET_SBI_SPEC_VERSION => (crate::SBI_SPEC_MAJOR << 24) | (crate::SBI_SPEC_MINOR),
+        spec::base::GET_SBI_SPEC_VERSION => spec_version,
         spec::base::GET_SBI_IMPL_ID => crate::IMPL_ID_RUSTSBI,
         spec::base::GET_SBI_IMPL_VERSION => crate::RUSTSBI_VERSION,
         spec::base::PROBE_EXTENSION => probe.probe_extension(param0),
@@ -118,6 +120,10 @@ pub struct _StandardExtensionProbe {
     pub cppc: usize,
     pub nacl: usize,
     pub sta: usize,
+    pub fwft: usize,
+    pub sse: usize,
+    pub dbtr: usize,
+    pub mpxy: usize,
     // NOTE: remember to add to `fn probe_extension` in `impl _ExtensionProbe` as well
 }
 
@@ -137,6 +143,10 @@ impl _ExtensionProbe for _StandardExtensionProbe {
             spec::cppc::EID_CPPC => self.cppc,
             spec::nacl::EID_NACL => self.nacl,
             spec::sta::EID_STA => self.sta,
+            spec::fwft::EID_FWFT => self.fwft,
+            spec::sse::EID_SSE => self.sse,
+            spec::dbtr::EID_DBTR => self.dbtr,
+            spec::mpxy::EID_MPXY => self.mpxy,
             _ => spec::base::UNAVAILABLE_EXTENSION,
         }
     }
@@ -393,6 +403,160 @@ pub fn _rustsbi_sta<T: crate::Sta>(sta: &T, param: [usize; 6], function: usize)
     }
 }
 
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_fwft<T: crate::Fwft>(fwft: &T, param: [usize; 6], function: usize) -> SbiRet {
+    let [param0, param1, param2] = [param[0], param[1], param[2]];
+    match function {
+        spec::fwft::SET => match u32::try_from(param0) {
+            Ok(feature) => fwft.set(feature, param1, param2),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::fwft::GET => match u32::try_from(param0) {
+            Ok(feature) => fwft.get(feature),
+            _ => SbiRet::invalid_param(),
+        },
+        _ => SbiRet::not_supported(),
+    }
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_sse<T: crate::Sse>(sse: &T, param: [usize; 6], function: usize) -> SbiRet {
+    let [param0, param1, param2, param3, param4] =
+        [param[0], param[1], param[2], param[3], param[4]];
+    match function {
+        spec::sse::READ_ATTRS => match (u32::try_from(param0), u32::try_from(param1)) {
+            (Ok(event_id), Ok(base_attr_id)) => match sse_attrs_bytes(param2) {
+                Some(num_bytes) => {
+                    let output = Physical::new(num_bytes, param3, param4);
+                    sse.read_attrs(event_id, base_attr_id, output)
+                }
+                None => SbiRet::bad_range(),
+            },
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::WRITE_ATTRS => match (u32::try_from(param0), u32::try_from(param1)) {
+            (Ok(event_id), Ok(base_attr_id)) => match sse_attrs_bytes(param2) {
+                Some(num_bytes) => {
+                    let input = Physical::new(num_bytes, param3, param4);
+                    sse.write_attrs(event_id, base_attr_id, input)
+                }
+                None => SbiRet::bad_range(),
+            },
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::REGISTER => match u32::try_from(param0) {
+            Ok(event_id) => sse.register(event_id, param1, param2),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::UNREGISTER => match u32::try_from(param0) {
+            Ok(event_id) => sse.unregister(event_id),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::ENABLE => match u32::try_from(param0) {
+            Ok(event_id) => sse.enable(event_id),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::DISABLE => match u32::try_from(param0) {
+            Ok(event_id) => sse.disable(event_id),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::COMPLETE => sse.complete(),
+        spec::sse::INJECT => match u32::try_from(param0) {
+            Ok(event_id) => sse.inject(event_id, param1),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::sse::HART_UNMASK => sse.hart_unmask(),
+        spec::sse::HART_MASK => sse.hart_mask(),
+        _ => SbiRet::not_supported(),
+    }
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_dbtr<T: crate::Dbtr>(dbtr: &T, param: [usize; 6], function: usize) -> SbiRet {
+    let [param0, param1, param2] = [param[0], param[1], param[2]];
+    match function {
+        spec::dbtr::NUM_TRIGGERS => SbiRet::success(dbtr.num_triggers(param0)),
+        spec::dbtr::SET_SHMEM => dbtr.set_shmem(SharedPtr::new(param0, param1), param2),
+        spec::dbtr::READ_TRIGGERS => dbtr.read_triggers(param0, param1),
+        spec::dbtr::INSTALL_TRIGGERS => dbtr.install_triggers(param0),
+        spec::dbtr::UPDATE_TRIGGERS => dbtr.update_triggers(param0),
+        spec::dbtr::UNINSTALL_TRIGGERS => {
+            dbtr.uninstall_triggers(TriggerMask::from_mask_base(param1, param0))
+        }
+        spec::dbtr::ENABLE_TRIGGERS => {
+            dbtr.enable_triggers(TriggerMask::from_mask_base(param1, param0))
+        }
+        spec::dbtr::DISABLE_TRIGGERS => {
+            dbtr.disable_triggers(TriggerMask::from_mask_base(param1, param0))
+        }
+        _ => SbiRet::not_supported(),
+    }
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_mpxy<T: crate::Mpxy>(mpxy: &T, param: [usize; 6], function: usize) -> SbiRet {
+    let [param0, param1, param2] = [param[0], param[1], param[2]];
+    match function {
+        spec::mpxy::GET_SHMEM_SIZE => SbiRet::success(mpxy.get_shmem_size()),
+        spec::mpxy::SET_SHMEM => mpxy.set_shmem(SharedPtr::new(param0, param1), param2),
+        spec::mpxy::GET_CHANNEL_IDS => match u32::try_from(param0) {
+            Ok(start_index) => mpxy.get_channel_ids(start_index),
+            _ => SbiRet::invalid_param(),
+        },
+        spec::mpxy::READ_ATTRIBUTE => match (
+            u32::try_from(param0),
+            u32::try_from(param1),
+            u32::try_from(param2),
+        ) {
+            (Ok(channel_id), Ok(base_attribute_id), Ok(attribute_count)) => {
+                mpxy.read_attributes(channel_id, base_attribute_id, attribute_count)
+            }
+            _ => SbiRet::invalid_param(),
+        },
+        spec::mpxy::WRITE_ATTRIBUTE => match (
+            u32::try_from(param0),
+            u32::try_from(param1),
+            u32::try_from(param2),
+        ) {
+            (Ok(channel_id), Ok(base_attribute_id), Ok(attribute_count)) => {
+                mpxy.write_attributes(channel_id, base_attribute_id, attribute_count)
+            }
+            _ => SbiRet::invalid_param(),
+        },
+        spec::mpxy::SEND_MESSAGE_WITH_RESPONSE => {
+            match (u32::try_from(param0), u32::try_from(param1)) {
+                (Ok(channel_id), Ok(message_id)) => {
+                    mpxy.send_message_with_response(channel_id, message_id, param2)
+                }
+                _ => SbiRet::invalid_param(),
+            }
+        }
+        spec::mpxy::SEND_MESSAGE_WITHOUT_RESPONSE => {
+            match (u32::try_from(param0), u32::try_from(param1)) {
+                (Ok(channel_id), Ok(message_id)) => {
+                    mpxy.send_message_without_response(channel_id, message_id, param2)
+                }
+                _ => SbiRet::invalid_param(),
+            }
+        }
+        spec::mpxy::GET_NOTIFICATION_EVENTS => match u32::try_from(param0) {
+            Ok(channel_id) => mpxy.get_notification_events(channel_id),
+            _ => SbiRet::invalid_param(),
+        },
+        _ => SbiRet::not_supported(),
+    }
+}
+
+/// Size in bytes of `attr_count` XLEN-bit event attributes, or `None` on overflow.
+#[inline]
+const fn sse_attrs_bytes(attr_count: usize) -> Option<usize> {
+    attr_count.checked_mul(size_of::<usize>())
+}
+
 #[cfg(target_pointer_width = "32")]
 #[inline]
 const fn concat_u32(h: usize, l: usize) -> u64 {
@@ -464,3 +628,47 @@ pub fn _rustsbi_nacl_probe<T: crate::Nacl>(nacl: &T) -> usize {
 pub fn _rustsbi_sta_probe<T: crate::Sta>(sta: &T) -> usize {
     sta._rustsbi_probe()
 }
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_fwft_probe<T: crate::Fwft>(fwft: &T) -> usize {
+    fwft._rustsbi_probe()
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_sse_probe<T: crate::Sse>(sse: &T) -> usize {
+    sse._rustsbi_probe()
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_dbtr_probe<T: crate::Dbtr>(dbtr: &T) -> usize {
+    dbtr._rustsbi_probe()
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_mpxy_probe<T: crate::Mpxy>(mpxy: &T) -> usize {
+    mpxy._rustsbi_probe()
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_extension_handler<T: crate::ExtensionHandler>(
+    handler: &T,
+    extension: usize,
+    param: [usize; 6],
+    function: usize,
+) -> SbiRet {
+    handler.handle_ecall(extension, function, param)
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn _rustsbi_extension_handler_probe<T: crate::ExtensionHandler>(
+    handler: &T,
+    extension: usize,
+) -> usize {
+    handler.probe_extension(extension)
+}
@@ -0,0 +1,160 @@
+use rustsbi::{ExtensionHandler, RustSBI, SbiRet};
+
+#[derive(RustSBI)]
+struct StaticCustom {
+    #[rustsbi(vendor(eid = 0x0900_0000..=0x0900_00FF))]
+    platform: DummyVendor,
+    #[rustsbi(firmware(eid = 0x0A00_0001))]
+    firmware: DummyFirmware,
+    info: DummyEnvInfo,
+}
+
+#[test]
+fn static_custom_extensions() {
+    let sbi = StaticCustom {
+        platform: DummyVendor(1),
+        firmware: DummyFirmware,
+        info: DummyEnvInfo,
+    };
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0000, 0, [0; 6]),
+        SbiRet::success(0x1000)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0042, 3, [7, 0, 0, 0, 0, 0]),
+        SbiRet::success(0x1042 + 3 + 7)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x0A00_0001, 5, [0; 6]),
+        SbiRet::success(0x2005)
+    );
+    // Extension IDs outside of bound ranges are not supported
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0100, 0, [0; 6]),
+        SbiRet::not_supported()
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x0A00_0000, 0, [0; 6]),
+        SbiRet::not_supported()
+    );
+    // Probe custom extensions, including handler defined probe values
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x0900_0000, 0, 0, 0, 0, 0]),
+        SbiRet::success(1)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x0900_00FF, 0, 0, 0, 0, 0]),
+        SbiRet::success(0)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x0A00_0001, 0, 0, 0, 0, 0]),
+        SbiRet::success(1)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x0A00_0002, 0, 0, 0, 0, 0]),
+        SbiRet::success(0)
+    );
+    // RustSBI reports SBI specification version 2.0 by default
+    assert_eq!(
+        sbi.handle_ecall(0x10, 0, [0; 6]),
+        SbiRet::success(0x02000000)
+    );
+    // Standard extensions are still probed as usual
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x10, 0, 0, 0, 0, 0]),
+        SbiRet::success(1)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x54494D45, 0, 0, 0, 0, 0]),
+        SbiRet::success(0)
+    );
+}
+
+#[derive(RustSBI)]
+#[rustsbi(dynamic)]
+struct DynamicCustom {
+    #[rustsbi(vendor(eid = 0x0900_0000..=0x0900_000F))]
+    vendor_one: Option<DummyVendor>,
+    #[rustsbi(vendor(eid = 0x0900_0000..=0x0900_00FF))]
+    vendor_two: Option<DummyVendor>,
+    info: DummyEnvInfo,
+}
+
+#[test]
+fn dynamic_custom_extensions() {
+    let sbi = DynamicCustom {
+        vendor_one: Some(DummyVendor(1)),
+        vendor_two: Some(DummyVendor(2)),
+        info: DummyEnvInfo,
+    };
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0001, 0, [0; 6]),
+        SbiRet::success(0x1001)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0010, 0, [0; 6]),
+        SbiRet::success(0x2010)
+    );
+    let sbi = DynamicCustom {
+        vendor_one: None,
+        vendor_two: Some(DummyVendor(2)),
+        info: DummyEnvInfo,
+    };
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0001, 0, [0; 6]),
+        SbiRet::success(0x2001)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x0900_0001, 0, 0, 0, 0, 0]),
+        SbiRet::success(1)
+    );
+    let sbi = DynamicCustom {
+        vendor_one: None,
+        vendor_two: None,
+        info: DummyEnvInfo,
+    };
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0001, 0, [0; 6]),
+        SbiRet::not_supported()
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [0x0900_0001, 0, 0, 0, 0, 0]),
+        SbiRet::success(0)
+    );
+}
+
+struct DummyVendor(usize);
+
+impl ExtensionHandler for DummyVendor {
+    fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+        SbiRet::success((self.0 << 12) + (extension - 0x0900_0000) + function + param[0])
+    }
+
+    fn probe_extension(&self, extension: usize) -> usize {
+        if extension == 0x0900_00FF { 0 } else { 1 }
+    }
+}
+
+struct DummyFirmware;
+
+impl ExtensionHandler for DummyFirmware {
+    fn handle_ecall(&self, _: usize, function: usize, _: [usize; 6]) -> SbiRet {
+        SbiRet::success(0x2000 + function)
+    }
+}
+
+struct DummyEnvInfo;
+
+impl rustsbi::EnvInfo for DummyEnvInfo {
+    fn mvendorid(&self) -> usize {
+        36
+    }
+
+    fn marchid(&self) -> usize {
+        37
+    }
+
+    fn mimpid(&self) -> usize {
+        38
+    }
+}
@@ -1,12 +1,13 @@
 use core::cell::RefCell;
 use rustsbi::RustSBI;
 use sbi_spec::{
-    binary::{HartMask, Physical, SbiRet, SharedPtr},
+    binary::{HartMask, Physical, SbiRet, SharedPtr, TriggerMask},
     nacl::shmem_size::NATIVE,
     pmu::shmem_size::SIZE,
 };
 
 #[derive(RustSBI)]
+#[rustsbi(spec_version = "3.0")]
 struct FullyImplemented {
     console: DummyConsole,
     cppc: DummyCppc,
@@ -19,10 +20,15 @@ struct FullyImplemented {
     sta: DummySta,
     susp: DummySusp,
     timer: DummyTimer,
+    fwft: DummyFwft,
+    sse: DummySse,
+    dbtr: DummyDbtr,
+    mpxy: DummyMpxy,
     info: DummyEnvInfo,
 }
 
 #[derive(RustSBI)]
+#[rustsbi(spec_version = "3.0")]
 struct AlternateName {
     dbcn: DummyConsole,
     cppc: DummyCppc,
@@ -35,10 +41,15 @@ struct AlternateName {
     sta: DummySta,
     susp: DummySusp,
     time: DummyTimer,
+    fwft: DummyFwft,
+    sse: DummySse,
+    dbtr: DummyDbtr,
+    mpxy: DummyMpxy,
     info: DummyEnvInfo,
 }
 
 #[derive(RustSBI)]
+#[rustsbi(spec_version = "3.0")]
 struct TupleStruct(
     #[rustsbi(dbcn)] DummyConsole,
     #[rustsbi(cppc)] DummyCppc,
@@ -51,6 +62,10 @@ struct TupleStruct(
     #[rustsbi(sta)] DummySta,
     #[rustsbi(susp)] DummySusp,
     #[rustsbi(time)] DummyTimer,
+    #[rustsbi(fwft)] DummyFwft,
+    #[rustsbi(sse)] DummySse,
+    #[rustsbi(dbtr)] DummyDbtr,
+    #[rustsbi(mpxy)] DummyMpxy,
     #[rustsbi(info)] DummyEnvInfo,
 );
 
@@ -72,6 +87,10 @@ fn rustsbi_impl_id() {
         sta: DummySta,
         susp: DummySusp,
         timer: DummyTimer(RefCell::new(0)),
+        fwft: DummyFwft,
+        sse: DummySse,
+        dbtr: DummyDbtr,
+        mpxy: DummyMpxy,
         info: DummyEnvInfo,
     };
     assert_eq!(sbi.handle_ecall(0x10, 0x1, [0; 6]).value, 4);
@@ -87,6 +106,10 @@ fn rustsbi_impl_id() {
         sta: DummySta,
         susp: DummySusp,
         time: DummyTimer(RefCell::new(0)),
+        fwft: DummyFwft,
+        sse: DummySse,
+        dbtr: DummyDbtr,
+        mpxy: DummyMpxy,
         info: DummyEnvInfo,
     };
     assert_eq!(sbi.handle_ecall(0x10, 0x1, [0; 6]).value, 4);
@@ -102,6 +125,10 @@ fn rustsbi_impl_id() {
         DummySta,
         DummySusp,
         DummyTimer(RefCell::new(0)),
+        DummyFwft,
+        DummySse,
+        DummyDbtr,
+        DummyMpxy,
         DummyEnvInfo,
     );
     assert_eq!(sbi.handle_ecall(0x10, 0x1, [0; 6]).value, 4);
@@ -128,18 +155,22 @@ fn generated_extensions() {
         sta: DummySta,
         susp: DummySusp,
         timer: DummyTimer(RefCell::new(0)),
+        fwft: DummyFwft,
+        sse: DummySse,
+        dbtr: DummyDbtr,
+        mpxy: DummyMpxy,
         info: DummyEnvInfo,
     };
     assert_eq!(
         sbi.handle_ecall(0x10, 0, [0; 6]),
-        SbiRet::success(0x02000000)
+        SbiRet::success(0x03000000)
     );
     assert_eq!(sbi.handle_ecall(0x10, 1, [0; 6]), SbiRet::success(4));
     assert!(sbi.handle_ecall(0x10, 2, [0; 6]).is_ok());
-    // All SBI 2.0 extensions, including Base, are supported
+    // All implemented extensions, including Base, are supported
     for eid in [
         0x10, 0x54494d45, 0x735049, 0x52464e43, 0x48534d, 0x53525354, 0x504d55, 0x4442434e,
-        0x53555350, 0x4e41434c, 0x535441, 0x43505043,
+        0x53555350, 0x4e41434c, 0x535441, 0x43505043, 0x46574654, 0x535345, 0x44425452, 0x4d505859,
     ] {
         assert_eq!(
             sbi.handle_ecall(0x10, 3, [eid, 0, 0, 0, 0, 0]),
@@ -194,6 +225,34 @@ fn generated_extensions() {
     assert_eq!(sbi.handle_ecall(0x10, 4, [0; 6]), SbiRet::success(37));
     assert_eq!(sbi.handle_ecall(0x10, 5, [0; 6]), SbiRet::success(38));
     assert_eq!(sbi.handle_ecall(0x10, 6, [0; 6]), SbiRet::success(39));
+    assert_eq!(sbi.handle_ecall(0x46574654, 0, [0; 6]), SbiRet::success(40));
+    assert_eq!(sbi.handle_ecall(0x46574654, 1, [0; 6]), SbiRet::success(41));
+    assert_eq!(sbi.handle_ecall(0x535345, 0, [0; 6]), SbiRet::success(42));
+    assert_eq!(sbi.handle_ecall(0x535345, 1, [0; 6]), SbiRet::success(43));
+    assert_eq!(sbi.handle_ecall(0x535345, 2, [0; 6]), SbiRet::success(44));
+    assert_eq!(sbi.handle_ecall(0x535345, 3, [0; 6]), SbiRet::success(45));
+    assert_eq!(sbi.handle_ecall(0x535345, 4, [0; 6]), SbiRet::success(46));
+    assert_eq!(sbi.handle_ecall(0x535345, 5, [0; 6]), SbiRet::success(47));
+    assert_eq!(sbi.handle_ecall(0x535345, 6, [0; 6]), SbiRet::success(48));
+    assert_eq!(sbi.handle_ecall(0x535345, 7, [0; 6]), SbiRet::success(49));
+    assert_eq!(sbi.handle_ecall(0x535345, 8, [0; 6]), SbiRet::success(50));
+    assert_eq!(sbi.handle_ecall(0x535345, 9, [0; 6]), SbiRet::success(51));
+    assert_eq!(sbi.handle_ecall(0x44425452, 0, [0; 6]), SbiRet::success(52));
+    assert_eq!(sbi.handle_ecall(0x44425452, 1, [0; 6]), SbiRet::success(53));
+    assert_eq!(sbi.handle_ecall(0x44425452, 2, [0; 6]), SbiRet::success(54));
+    assert_eq!(sbi.handle_ecall(0x44425452, 3, [0; 6]), SbiRet::success(55));
+    assert_eq!(sbi.handle_ecall(0x44425452, 4, [0; 6]), SbiRet::success(56));
+    assert_eq!(sbi.handle_ecall(0x44425452, 5, [0; 6]), SbiRet::success(57));
+    assert_eq!(sbi.handle_ecall(0x44425452, 6, [0; 6]), SbiRet::success(58));
+    assert_eq!(sbi.handle_ecall(0x44425452, 7, [0; 6]), SbiRet::success(59));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 0, [0; 6]), SbiRet::success(60));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 1, [0; 6]), SbiRet::success(61));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 2, [0; 6]), SbiRet::success(62));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 3, [0; 6]), SbiRet::success(63));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 4, [0; 6]), SbiRet::success(64));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 5, [0; 6]), SbiRet::success(65));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 6, [0; 6]), SbiRet::success(66));
+    assert_eq!(sbi.handle_ecall(0x4d505859, 7, [0; 6]), SbiRet::success(67));
 }
 
 struct DummyConsole;
@@ -384,6 +443,134 @@ impl rustsbi::Timer for DummyTimer {
     }
 }
 
+struct DummyFwft;
+
+impl rustsbi::Fwft for DummyFwft {
+    fn set(&self, _: u32, _: usize, _: usize) -> SbiRet {
+        SbiRet::success(40)
+    }
+
+    fn get(&self, _: u32) -> SbiRet {
+        SbiRet::success(41)
+    }
+}
+
+struct DummySse;
+
+impl rustsbi::Sse for DummySse {
+    fn read_attrs(&self, _: u32, _: u32, _: Physical<&mut [usize]>) -> SbiRet {
+        SbiRet::success(42)
+    }
+
+    fn write_attrs(&self, _: u32, _: u32, _: Physical<&[usize]>) -> SbiRet {
+        SbiRet::success(43)
+    }
+
+    fn register(&self, _: u32, _: usize, _: usize) -> SbiRet {
+        SbiRet::success(44)
+    }
+
+    fn unregister(&self, _: u32) -> SbiRet {
+        SbiRet::success(45)
+    }
+
+    fn enable(&self, _: u32) -> SbiRet {
+        SbiRet::success(46)
+    }
+
+    fn disable(&self, _: u32) -> SbiRet {
+        SbiRet::success(47)
+    }
+
+    fn complete(&self) -> SbiRet {
+        SbiRet::success(48)
+    }
+
+    fn inject(&self, _: u32, _: usize) -> SbiRet {
+        SbiRet::success(49)
+    }
+
+    fn hart_unmask(&self) -> SbiRet {
+        SbiRet::success(50)
+    }
+
+    fn hart_mask(&self) -> SbiRet {
+        SbiRet::success(51)
+    }
+}
+
+struct DummyDbtr;
+
+impl rustsbi::Dbtr for DummyDbtr {
+    fn num_triggers(&self, _: usize) -> usize {
+        52
+    }
+
+    fn set_shmem(&self, _: SharedPtr<u8>, _: usize) -> SbiRet {
+        SbiRet::success(53)
+    }
+
+    fn read_triggers(&self, _: usize, _: usize) -> SbiRet {
+        SbiRet::success(54)
+    }
+
+    fn install_triggers(&self, _: usize) -> SbiRet {
+        SbiRet::success(55)
+    }
+
+    fn update_triggers(&self, _: usize) -> SbiRet {
+        SbiRet::success(56)
+    }
+
+    fn uninstall_triggers(&self, _: TriggerMask) -> SbiRet {
+        SbiRet::success(57)
+    }
+
+    fn enable_triggers(&self, _: TriggerMask) -> SbiRet {
+        SbiRet::success(58)
+    }
+
+    fn disable_triggers(&self, _: TriggerMask) -> SbiRet {
+        SbiRet::success(59)
+    }
+}
+
+struct DummyMpxy;
+
+impl rustsbi::Mpxy for DummyMpxy {
+    fn get_shmem_size(&self) -> usize {
+        60
+    }
+
+    fn set_shmem(&self, _: SharedPtr<u8>, _: usize) -> SbiRet {
+        SbiRet::success(61)
+    }
+
+    fn get_channel_ids(&self, _: u32) -> SbiRet {
+        SbiRet::success(62)
+    }
+
+    fn read_attributes(&self, _: u32, _: u32, _: u32) -> SbiRet {
+        SbiRet::success(63)
+    }
+
+    fn write_attributes(&self, _: u32, _: u32, _: u32) -> SbiRet {
+        SbiRet::success(64)
+    }
+
+    fn send_message_with_response(&self, _: u32, _: u32, _: usize) -> SbiRet {
+        SbiRet::success(65)
+    }
+
+    fn send_message_without_response(&self, _: u32, _: u32, _: usize) -> SbiRet {
+        SbiRet::success(66)
+    }
+
+    fn get_notification_events(&self, _: u32) -> SbiRet {
+        SbiRet::success(67)
+    }
+}
+
 struct DummyEnvInfo;
 
 impl rustsbi::EnvInfo for DummyEnvInfo {
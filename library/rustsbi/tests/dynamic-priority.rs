@@ -1,5 +1,5 @@
 use rustsbi::{HartMask, RustSBI, SbiRet};
-use sbi_spec::{rfnc::EID_RFNC, spi::EID_SPI, time::EID_TIME};
+use sbi_spec::{fwft::EID_FWFT, rfnc::EID_RFNC, spi::EID_SPI, time::EID_TIME};
 
 #[derive(RustSBI)]
 #[rustsbi(dynamic)]
@@ -104,6 +104,50 @@ fn priority_assign_multiple() {
     assert_eq!(sbi_ret, SbiRet::not_supported());
 }
 
+#[derive(RustSBI)]
+#[rustsbi(dynamic, spec_version = "3.0")]
+struct MultipleFwft {
+    #[rustsbi(fwft)]
+    fwft_one: Option<FwftOne>,
+    #[rustsbi(fwft)]
+    fwft_two: Option<FwftTwo>,
+    env_info: DummyEnvInfo,
+}
+
+#[test]
+fn priority_multiple_fwft() {
+    let sbi = MultipleFwft {
+        fwft_one: None,
+        fwft_two: Some(FwftTwo),
+        env_info: DummyEnvInfo,
+    };
+    assert_eq!(sbi.handle_ecall(EID_FWFT, 0x1, [0; 6]), SbiRet::success(12));
+    assert_eq!(
+        sbi.handle_ecall(0x10, 0x3, [EID_FWFT, 0, 0, 0, 0, 0]),
+        SbiRet::success(1)
+    );
+    let sbi = MultipleFwft {
+        fwft_one: Some(FwftOne),
+        fwft_two: Some(FwftTwo),
+        env_info: DummyEnvInfo,
+    };
+    assert_eq!(sbi.handle_ecall(EID_FWFT, 0x0, [0; 6]), SbiRet::success(9));
+    assert_eq!(sbi.handle_ecall(EID_FWFT, 0x1, [0; 6]), SbiRet::success(10));
+    let sbi = MultipleFwft {
+        fwft_one: None,
+        fwft_two: None,
+        env_info: DummyEnvInfo,
+    };
+    assert_eq!(
+        sbi.handle_ecall(EID_FWFT, 0x0, [0; 6]),
+        SbiRet::not_supported()
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x10, 0x3, [EID_FWFT, 0, 0, 0, 0, 0]),
+        SbiRet::success(0)
+    );
+}
+
 struct FenceOne;
 
 impl rustsbi::Fence for FenceOne {
@@ -185,6 +229,30 @@ impl rustsbi::Ipi for AclintThree {
     }
 }
 
+struct FwftOne;
+
+impl rustsbi::Fwft for FwftOne {
+    fn set(&self, _: u32, _: usize, _: usize) -> SbiRet {
+        SbiRet::success(9)
+    }
+
+    fn get(&self, _: u32) -> SbiRet {
+        SbiRet::success(10)
+    }
+}
+
+struct FwftTwo;
+
+impl rustsbi::Fwft for FwftTwo {
+    fn set(&self, _: u32, _: usize, _: usize) -> SbiRet {
+        SbiRet::success(11)
+    }
+
+    fn get(&self, _: u32) -> SbiRet {
+        SbiRet::success(12)
+    }
+}
+
 struct DummyEnvInfo;
 
 impl rustsbi::EnvInfo for DummyEnvInfo {
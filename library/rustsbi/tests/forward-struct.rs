@@ -4,9 +4,11 @@ use rustsbi::{Forward, RustSBI};
 
 #[allow(unused)] // FIXME: hot fix, use it on unit test in the future.
 #[derive(RustSBI)]
+#[rustsbi(spec_version = "3.0")]
 struct ForwardAll {
     #[rustsbi(
-        console, cppc, hsm, ipi, nacl, pmu, reset, fence, sta, susp, timer, info
+        console, cppc, hsm, ipi, nacl, pmu, reset, fence, sta, susp, timer, fwft, sse, dbtr, mpxy,
+        info
     )]
     forward: Forward,
 }
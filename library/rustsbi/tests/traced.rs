@@ -0,0 +1,134 @@
+use rustsbi::{
+    RustSBI, SbiRet, Traced,
+    trace::{RingBuffer, TraceRecord, TraceSink},
+};
+use std::cell::RefCell;
+
+struct DummySBI;
+
+impl RustSBI for DummySBI {
+    fn handle_ecall(&self, extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+        match extension {
+            0x10 => SbiRet::success(function + param[0]),
+            _ => SbiRet::not_supported(),
+        }
+    }
+}
+
+#[test]
+fn traced_forwards_and_counts() {
+    let sbi = Traced::new(DummySBI, ());
+    assert_eq!(
+        sbi.handle_ecall(0x10, 3, [4, 0, 0, 0, 0, 0]),
+        SbiRet::success(7)
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x54494D45, 0, [0; 6]),
+        SbiRet::not_supported()
+    );
+    assert_eq!(
+        sbi.handle_ecall(0x0900_0000, 0, [0; 6]),
+        SbiRet::not_supported()
+    );
+    assert_eq!(sbi.handle_ecall(0x01, 0, [0; 6]), SbiRet::not_supported());
+
+    let counters = sbi.counters();
+    assert_eq!((counters.calls(0x10), counters.errors(0x10)), (1, 0));
+    assert_eq!(
+        (counters.calls(0x54494D45), counters.errors(0x54494D45)),
+        (1, 1)
+    );
+    assert_eq!(counters.calls(0x48534D), 0);
+    // Legacy, vendor and unknown extensions share the same counters
+    assert_eq!(counters.calls(0x0900_0000), 2);
+    assert_eq!(counters.errors(0x01), 2);
+    assert_eq!(counters.total_calls(), 4);
+    assert_eq!(counters.iter().last(), Some((None, 2, 2)));
+    counters.reset();
+    assert_eq!(counters.total_calls(), 0);
+}
+
+#[test]
+fn ring_buffer_keeps_latest_records() {
+    let sbi = Traced::new(DummySBI, RingBuffer::<4>::new());
+    assert_eq!(sbi.sink().last(), None);
+    for function in 0..6 {
+        sbi.handle_ecall(0x10, function, [function; 6]);
+    }
+    let buffer = sbi.sink();
+    assert_eq!(buffer.capacity(), 4);
+    assert_eq!(buffer.total(), 6);
+    assert_eq!(buffer.get(1), None);
+    assert_eq!(
+        buffer.get(2),
+        Some(TraceRecord {
+            extension: 0x10,
+            function: 2,
+            param: [2; 6],
+            ret: SbiRet::success(4),
+        })
+    );
+    let functions: Vec<usize> = buffer.iter().map(|record| record.function).collect();
+    assert_eq!(functions, [2, 3, 4, 5]);
+    assert_eq!(
+        buffer.last().map(|record| record.ret),
+        Some(SbiRet::success(10))
+    );
+}
+
+#[derive(Default)]
+struct VecSink(RefCell<Vec<TraceRecord>>);
+
+impl TraceSink for VecSink {
+    fn record(&self, record: &TraceRecord) {
+        self.0.borrow_mut().push(*record);
+    }
+}
+
+#[test]
+fn combined_sinks() {
+    let sbi = Traced::new(&DummySBI, (RingBuffer::<2>::new(), VecSink::default()));
+    sbi.handle_ecall(0x10, 1, [0; 6]);
+    sbi.handle_ecall(0x535345, 2, [0; 6]);
+    let (_, (buffer, records)) = sbi.into_parts();
+    assert_eq!(buffer.total(), 2);
+    let records = records.0.into_inner();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].extension, 0x535345);
+    assert_eq!(records[1].ret, SbiRet::not_supported());
+}
+
+#[test]
+fn ring_buffer_concurrent_records_not_torn() {
+    // Writers lap a small buffer; every record kept must come from one writer only
+    let buffer = RingBuffer::<2>::new();
+    let consistent = |record: TraceRecord| {
+        let v = record.extension;
+        record.function == v
+            && record.param == [v; 6]
+            && record.ret == SbiRet { error: v, value: v }
+    };
+    std::thread::scope(|s| {
+        for v in 1..=4 {
+            let buffer = &buffer;
+            s.spawn(move || {
+                let record = TraceRecord {
+                    extension: v,
+                    function: v,
+                    param: [v; 6],
+                    ret: SbiRet { error: v, value: v },
+                };
+                for _ in 0..10000 {
+                    buffer.record(&record);
+                }
+            });
+        }
+        s.spawn(|| {
+            while buffer.total() < 40000 {
+                assert!(buffer.iter().all(consistent));
+            }
+        });
+    });
+    assert_eq!(buffer.total(), 40000);
+    assert!(buffer.iter().all(consistent));
+}
@@ -1,14 +1,26 @@
 //! Chapter 3. Binary Encoding
 // This module is designated to use under RISC-V only, but it builds under non-RISC-V targets
-// to allow unit tests and `cargo fix` operations.
+// to allow unit tests and `cargo fix` operations; with feature `mock`, it builds on all targets
+// to allow host tests on supervisor code.
 
 // `sbi_call_6` has 8 arguments which is allowed
 #![allow(clippy::too_many_arguments)]
 
+#[cfg(not(feature = "mock"))]
 use sbi_spec::binary::SbiRet;
 
+// With feature `mock`, SBI calls are delivered to the handler installed on the current thread
+// instead of the SBI environment.
+#[cfg(feature = "mock")]
+pub(crate) use crate::mock::{
+    sbi_call_0, sbi_call_1, sbi_call_2, sbi_call_3, sbi_call_4, sbi_call_5, sbi_call_6,
+};
+
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 pub(crate) fn sbi_call_0(eid: usize, fid: usize) -> SbiRet {
     let (error, value);
     unsafe {
@@ -24,13 +36,16 @@ pub(crate) fn sbi_call_0(eid: usize, fid: usize) -> SbiRet {
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 pub(crate) fn sbi_call_0(_eid: usize, _fid: usize) -> SbiRet {
     unimplemented!("unsupported architecture")
 }
 
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 pub(crate) fn sbi_call_1(eid: usize, fid: usize, arg0: usize) -> SbiRet {
     let (error, value);
     unsafe {
@@ -46,13 +61,16 @@ pub(crate) fn sbi_call_1(eid: usize, fid: usize, arg0: usize) -> SbiRet {
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 pub(crate) fn sbi_call_1(_eid: usize, _fid: usize, _arg0: usize) -> SbiRet {
     unimplemented!("unsupported architecture")
 }
 
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 pub(crate) fn sbi_call_2(eid: usize, fid: usize, arg0: usize, arg1: usize) -> SbiRet {
     let (error, value);
     unsafe {
@@ -68,13 +86,16 @@ pub(crate) fn sbi_call_2(eid: usize, fid: usize, arg0: usize, arg1: usize) -> Sb
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 pub(crate) fn sbi_call_2(_eid: usize, _fid: usize, _arg0: usize, _arg1: usize) -> SbiRet {
     unimplemented!("unsupported architecture")
 }
 
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 pub(crate) fn sbi_call_3(eid: usize, fid: usize, arg0: usize, arg1: usize, arg2: usize) -> SbiRet {
     let (error, value);
     unsafe {
@@ -91,7 +112,7 @@ pub(crate) fn sbi_call_3(eid: usize, fid: usize, arg0: usize, arg1: usize, arg2:
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 pub(crate) fn sbi_call_3(
     _eid: usize,
     _fid: usize,
@@ -103,7 +124,10 @@ pub(crate) fn sbi_call_3(
 }
 
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 pub(crate) fn sbi_call_4(
     eid: usize,
     fid: usize,
@@ -128,7 +152,7 @@ pub(crate) fn sbi_call_4(
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 pub(crate) fn sbi_call_4(
     _eid: usize,
     _fid: usize,
@@ -141,7 +165,10 @@ pub(crate) fn sbi_call_4(
 }
 
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 pub(crate) fn sbi_call_5(
     eid: usize,
     fid: usize,
@@ -168,7 +195,7 @@ pub(crate) fn sbi_call_5(
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 pub(crate) fn sbi_call_5(
     _eid: usize,
     _fid: usize,
@@ -182,7 +209,10 @@ pub(crate) fn sbi_call_5(
 }
 
 #[inline(always)]
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
 #[allow(unused)] // only used on RV32 for RISC-V SBI 2.0 specification
 pub(crate) fn sbi_call_6(
     eid: usize,
@@ -212,7 +242,7 @@ pub(crate) fn sbi_call_6(
 }
 
 #[inline(always)]
-#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock")))]
 #[allow(unused)] // only used on RV32 for RISC-V SBI 2.0 specification
 pub(crate) fn sbi_call_6(
     _eid: usize,
@@ -0,0 +1,266 @@
+//! Formatted console output over the Debug Console extension.
+//!
+//! With feature `console` enabled, this module provides the [`Console`] type implementing
+//! [`core::fmt::Write`], and the [`print!`](crate::print) and [`println!`](crate::println) macros
+//! which print to the SBI console. With feature `log` enabled, [`Logger`] implements the
+//! [`log::Log`] trait over the same console.
+//!
+//! The SBI Debug Console extension (DBCN) is probed on first use or on [`init`]; if it is not
+//! available, output falls back to the legacy `console_putchar` function. Output is collected
+//! into a buffer on the stack, so that each `print!` call or log record is usually written
+//! to the debug console by one `console_write` call, rather than one SBI call per byte.
+//!
+//! # Physical addresses
+//!
+//! The `console_write` function takes the physical address of the output buffer. If the
+//! supervisor runs with virtual memory where stacks are not identity mapped, call
+//! [`set_phys_offset`] with the difference between virtual and physical addresses of
+//! the stack before printing.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! sbi_rt::console::init();
+//! sbi_rt::println!("Hello from {}!", "sbi-rt");
+//! ```
+
+use crate::{binary::sbi_call_1, console_write, console_write_byte, probe_extension};
+use core::{
+    fmt,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+use sbi_spec::binary::Physical;
+
+/// Size of the output buffer of a [`Console`].
+pub const BUFFER_SIZE: usize = 256;
+
+/// SBI extension used to write to the console.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Debug Console extension, using `console_write`.
+    DebugConsole,
+    /// Legacy `console_putchar` function.
+    Legacy,
+}
+
+const UNINIT: u8 = 0;
+const DBCN: u8 = 1;
+const LEGACY: u8 = 2;
+
+static BACKEND: AtomicU8 = AtomicU8::new(UNINIT);
+static PHYS_OFFSET: AtomicUsize = AtomicUsize::new(0);
+
+/// Probe the SBI console extensions, and return the backend used by later outputs.
+#[inline]
+pub fn init() -> Backend {
+    let backend = if probe_extension(crate::Console).is_available() {
+        DBCN
+    } else {
+        LEGACY
+    };
+    BACKEND.store(backend, Ordering::Relaxed);
+    decode(backend)
+}
+
+/// Backend used to write to the console; the SBI extensions are probed on first call.
+#[inline]
+pub fn backend() -> Backend {
+    match BACKEND.load(Ordering::Relaxed) {
+        UNINIT => init(),
+        backend => decode(backend),
+    }
+}
+
+#[inline]
+const fn decode(backend: u8) -> Backend {
+    match backend {
+        DBCN => Backend::DebugConsole,
+        _ => Backend::Legacy,
+    }
+}
+
+/// Set the offset of virtual addresses from physical addresses of output buffers.
+///
+/// The physical address of a buffer is its virtual address minus `phys_offset`.
+#[inline]
+pub fn set_phys_offset(phys_offset: usize) {
+    PHYS_OFFSET.store(phys_offset, Ordering::Relaxed);
+}
+
+/// Number of consecutive debug console writes accepting no byte before falling back to
+/// writing byte by byte.
+const MAX_STALLED_WRITES: usize = 16;
+
+/// Write all `bytes` to the console.
+pub fn write_bytes(bytes: &[u8]) {
+    match backend() {
+        Backend::DebugConsole => {
+            let phys_offset = PHYS_OFFSET.load(Ordering::Relaxed);
+            let mut rest = bytes;
+            let mut stalled = 0;
+            while !rest.is_empty() {
+                let phys_addr = (rest.as_ptr() as usize).wrapping_sub(phys_offset);
+                match console_write(Physical::new(rest.len(), phys_addr, 0)).into_result() {
+                    Ok(0) if stalled < MAX_STALLED_WRITES => stalled += 1,
+                    Ok(written) if written != 0 => {
+                        stalled = 0;
+                        rest = &rest[written.min(rest.len())..];
+                    }
+                    // the buffer may not be accessible by its physical address,
+                    // or the console keeps accepting nothing;
+                    // write remaining bytes one by one
+                    _ => {
+                        for &byte in rest {
+                            let _ = console_write_byte(byte);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        Backend::Legacy => {
+            for &byte in bytes {
+                console_putchar(byte);
+            }
+        }
+    }
+}
+
+/// Legacy `console_putchar` function, without enabling the deprecated `legacy` module.
+///
+/// Legacy extensions ignore the function ID in `a6` and return error code in `a0` only.
+#[inline]
+fn console_putchar(byte: u8) {
+    const LEGACY_CONSOLE_PUTCHAR: usize = 0x01;
+    let _ = sbi_call_1(LEGACY_CONSOLE_PUTCHAR, 0, byte as usize);
+}
+
+/// Buffered writer to the SBI console.
+///
+/// Bytes are written to the console when the buffer is full, on [`Console::flush`],
+/// or when the console is dropped.
+pub struct Console {
+    buf: [u8; BUFFER_SIZE],
+    len: usize,
+}
+
+impl Console {
+    /// Create a console writer with an empty buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; BUFFER_SIZE],
+            len: 0,
+        }
+    }
+
+    /// Write buffered bytes to the console.
+    #[inline]
+    pub fn flush(&mut self) {
+        if self.len != 0 {
+            write_bytes(&self.buf[..self.len]);
+            self.len = 0;
+        }
+    }
+}
+
+impl Default for Console {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s.as_bytes();
+        while !rest.is_empty() {
+            if self.len == BUFFER_SIZE {
+                self.flush();
+            }
+            let count = rest.len().min(BUFFER_SIZE - self.len);
+            self.buf[self.len..self.len + count].copy_from_slice(&rest[..count]);
+            self.len += count;
+            rest = &rest[count..];
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Console {
+    #[inline]
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments, newline: bool) {
+    use fmt::Write;
+    let mut console = Console::new();
+    let _ = console.write_fmt(args);
+    if newline {
+        let _ = console.write_str("\n");
+    }
+}
+
+/// Print to the SBI console.
+///
+/// Equivalent to the [`println!`](crate::println) macro except that a newline is not printed
+/// at the end of the message.
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::console::_print(core::format_args!($($arg)*), false)
+    };
+}
+
+/// Print to the SBI console, with a newline.
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::console::_print(core::format_args!(""), true)
+    };
+    ($($arg:tt)*) => {
+        $crate::console::_print(core::format_args!($($arg)*), true)
+    };
+}
+
+/// Logger printing records to the SBI console.
+///
+/// Each record is printed as one line in the form `[LEVEL] message`.
+#[cfg(feature = "log")]
+pub struct Logger;
+
+#[cfg(feature = "log")]
+impl Logger {
+    /// Install the SBI console logger with maximum log level `level`.
+    #[inline]
+    pub fn init(level: log::LevelFilter) -> Result<(), log::SetLoggerError> {
+        static LOGGER: Logger = Logger;
+        log::set_logger(&LOGGER)?;
+        log::set_max_level(level);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "log")]
+impl log::Log for Logger {
+    #[inline]
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    #[inline]
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            _print(
+                format_args!("[{:>5}] {}", record.level(), record.args()),
+                true,
+            );
+        }
+    }
+
+    #[inline]
+    fn flush(&self) {}
+}
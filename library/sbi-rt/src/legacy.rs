@@ -5,6 +5,11 @@
 
 use sbi_spec::legacy::*;
 
+#[cfg(all(target_pointer_width = "32", feature = "mock"))]
+use crate::mock::sbi_call_legacy_2;
+#[cfg(feature = "mock")]
+use crate::mock::{sbi_call_legacy_0, sbi_call_legacy_1, sbi_call_legacy_3, sbi_call_legacy_4};
+
 /// Use [`set_timer`](super::set_timer) from [`TIME`](crate::base::Timer) extension instead.
 #[deprecated = "replaced by `set_timer` from Timer extension"]
 #[inline]
@@ -75,6 +80,7 @@ pub fn shutdown() -> ! {
     unreachable!()
 }
 
+#[cfg(not(feature = "mock"))]
 #[inline(always)]
 fn sbi_call_legacy_0(eid: usize) -> usize {
     let error;
@@ -88,6 +94,7 @@ fn sbi_call_legacy_0(eid: usize) -> usize {
     error
 }
 
+#[cfg(not(feature = "mock"))]
 #[inline(always)]
 fn sbi_call_legacy_1(eid: usize, arg0: usize) -> usize {
     let error;
@@ -101,7 +108,7 @@ fn sbi_call_legacy_1(eid: usize, arg0: usize) -> usize {
     error
 }
 
-#[cfg(target_pointer_width = "32")]
+#[cfg(all(target_pointer_width = "32", not(feature = "mock")))]
 #[inline(always)]
 fn sbi_call_legacy_2(eid: usize, arg0: usize, arg1: usize) -> usize {
     let error;
@@ -116,6 +123,7 @@ fn sbi_call_legacy_2(eid: usize, arg0: usize, arg1: usize) -> usize {
     error
 }
 
+#[cfg(not(feature = "mock"))]
 #[inline(always)]
 fn sbi_call_legacy_3(eid: usize, arg0: usize, arg1: usize, arg2: usize) -> usize {
     let error;
@@ -131,6 +139,7 @@ fn sbi_call_legacy_3(eid: usize, arg0: usize, arg1: usize, arg2: usize) -> usize
     error
 }
 
+#[cfg(not(feature = "mock"))]
 #[inline(always)]
 fn sbi_call_legacy_4(eid: usize, arg0: usize, arg1: usize, arg2: usize, arg3: usize) -> usize {
     let error;
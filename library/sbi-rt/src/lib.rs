@@ -9,10 +9,21 @@
 //! Those calls only works at RISC-V targets when building supervisor software
 //! (e.g. kernels or hypervisors); it builds under non-RISC-V targets but for tests
 //! or `cargo fix` purposes only.
+//!
+//! To test supervisor software on the build host, enable feature `mock`; SBI calls are then
+//! delivered to handlers installed using functions in module [`mock`].
+//!
+//! Enable feature `console` for formatted output to the SBI console using module `console`
+//! and macros `print!` and `println!`; feature `log` additionally provides a `log` backend.
 #![no_std]
+#[cfg(feature = "mock")]
+extern crate std;
 #[cfg_attr(not(feature = "legacy"), deny(missing_docs))]
 // §3
 mod binary;
+// Host tests
+#[cfg(feature = "mock")]
+pub mod mock;
 // §4
 mod base;
 // §5
@@ -30,7 +41,10 @@ mod hsm;
 mod srst;
 // §11
 mod pmu;
+mod pmu_counter;
 // §12
+#[cfg(feature = "console")]
+pub mod console;
 mod dbcn;
 // §13
 mod susp;
@@ -42,6 +56,7 @@ mod nacl;
 mod sta;
 // §17
 mod sse;
+mod sse_handler;
 // §18
 mod fwft;
 // §19
@@ -66,10 +81,12 @@ pub use hsm::*;
 pub use mpxy::*;
 pub use nacl::*;
 pub use pmu::*;
+pub use pmu_counter::*;
 pub use rfnc::*;
 pub use spi::*;
 pub use srst::*;
 pub use sse::*;
+pub use sse_handler::*;
 pub use sta::*;
 pub use susp::*;
 pub use time::*;
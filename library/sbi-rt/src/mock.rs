@@ -0,0 +1,229 @@
+//! Mock SBI environment for host tests.
+//!
+//! With feature `mock` enabled, functions of this crate do not issue the `ecall` instruction.
+//! Instead, every SBI call is delivered to a handler installed on the current thread, which
+//! returns the `SbiRet` value the call would get from an SBI implementation. This allows
+//! supervisor code using `sbi-rt` to be tested using `cargo test` on the build host.
+//!
+//! A handler is any closure taking the extension ID, function ID and the six parameter
+//! registers `a0` to `a5`. Handlers are installed per thread, thus tests running in parallel
+//! don't interfere with each other; threads spawned by a test should install their own handlers.
+//! Calling an SBI function on a thread without a handler panics.
+//!
+//! Legacy extension calls are delivered with function ID 0, and only the `error` field of the
+//! returned value is used as the legacy return value. Unlike on an SBI environment, SBI calls
+//! that do not return on success (e.g. legacy `shutdown`) return to the caller if the handler
+//! returns; such functions would then panic.
+//!
+//! # Examples
+//!
+//! Record SBI calls and return a predefined value:
+//!
+//! ```
+//! use sbi_rt::{SbiRet, mock};
+//! use sbi_spec::hsm::{EID_HSM, HART_START};
+//!
+//! mock::set_handler(|extension, function, param| match (extension, function) {
+//!     (EID_HSM, HART_START) if param[0] == 1 => SbiRet::success(0),
+//!     _ => SbiRet::not_supported(),
+//! });
+//! assert_eq!(sbi_rt::hart_start(1, 0x8020_0000, 0), SbiRet::success(0));
+//! assert_eq!(sbi_rt::hart_start(2, 0x8020_0000, 0), SbiRet::not_supported());
+//! mock::clear_handler();
+//! ```
+//!
+//! An SBI implementation from the `rustsbi` crate can be used as a handler by moving it into
+//! the closure:
+//!
+//! ```ignore
+//! use rustsbi::RustSBI;
+//!
+//! let sbi = MySBI::new();
+//! sbi_rt::mock::set_handler(move |extension, function, param| {
+//!     sbi.handle_ecall(extension, function, param)
+//! });
+//! ```
+// `sbi_call_6` has 8 arguments which is allowed
+#![allow(clippy::too_many_arguments)]
+
+use sbi_spec::binary::SbiRet;
+use std::{cell::RefCell, rc::Rc};
+
+type Handler = Rc<dyn Fn(usize, usize, [usize; 6]) -> SbiRet>;
+
+std::thread_local! {
+    static HANDLER: RefCell<Option<Handler>> = const { RefCell::new(None) };
+}
+
+/// Install `handler` to handle SBI calls made on the current thread.
+///
+/// It replaces any handler previously installed on the current thread.
+#[inline]
+pub fn set_handler<F>(handler: F)
+where
+    F: Fn(usize, usize, [usize; 6]) -> SbiRet + 'static,
+{
+    HANDLER.with_borrow_mut(|current| *current = Some(Rc::new(handler)));
+}
+
+/// Remove the SBI call handler installed on the current thread, if any.
+#[inline]
+pub fn clear_handler() {
+    HANDLER.with_borrow_mut(|current| *current = None);
+}
+
+/// Whether an SBI call handler is installed on the current thread.
+#[inline]
+pub fn has_handler() -> bool {
+    HANDLER.with_borrow(Option::is_some)
+}
+
+/// Run `f` with `handler` handling SBI calls made on the current thread.
+///
+/// The previously installed handler, if any, is restored after `f` returns or panics.
+pub fn with_handler<F, R>(handler: F, f: impl FnOnce() -> R) -> R
+where
+    F: Fn(usize, usize, [usize; 6]) -> SbiRet + 'static,
+{
+    struct Restore(Option<Handler>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            HANDLER.with_borrow_mut(|current| *current = previous);
+        }
+    }
+    let handler: Handler = Rc::new(handler);
+    let _restore = Restore(HANDLER.replace(Some(handler)));
+    f()
+}
+
+/// Deliver an SBI call to the handler installed on the current thread.
+///
+/// This is the mock counterpart of the `ecall` instruction; `extension` and `function` are
+/// values of registers `a7` and `a6`, and `param` holds values of registers `a0` to `a5`.
+///
+/// # Panics
+///
+/// Panics if no handler is installed on the current thread.
+#[inline]
+pub fn ecall(extension: usize, function: usize, param: [usize; 6]) -> SbiRet {
+    // clone the handler out of thread local storage, so that it may install other handlers
+    let handler = HANDLER.with_borrow(Option::clone).unwrap_or_else(|| {
+        panic!(
+            "no mock SBI handler installed on the current thread \
+             when calling extension {extension:#x} function {function:#x}"
+        )
+    });
+    handler(extension, function, param)
+}
+
+/// Simulate the SSE entry trampoline of this crate handling a software event.
+///
+/// The handler registered using [`SseEvent::register`](crate::SseEvent::register) with
+/// `ENTRY_ARG` attribute `entry_arg` is called on hart `hart_id`, with `regs` as general
+/// registers of the interrupted context. The event is then completed using `sse_complete`,
+/// whose return value is returned.
+///
+/// # Safety
+///
+/// `entry_arg` must be the `ENTRY_ARG` attribute of an event registered by `SseEvent::register`
+/// which is not yet unregistered.
+pub unsafe fn deliver_sse(entry_arg: usize, hart_id: usize, regs: &mut crate::SseRegs) -> SbiRet {
+    unsafe { crate::sse_handler::handle_event_mock(entry_arg, hart_id, regs) }
+}
+
+#[inline(always)]
+pub(crate) fn sbi_call_0(eid: usize, fid: usize) -> SbiRet {
+    ecall(eid, fid, [0; 6])
+}
+
+#[inline(always)]
+pub(crate) fn sbi_call_1(eid: usize, fid: usize, arg0: usize) -> SbiRet {
+    ecall(eid, fid, [arg0, 0, 0, 0, 0, 0])
+}
+
+#[inline(always)]
+pub(crate) fn sbi_call_2(eid: usize, fid: usize, arg0: usize, arg1: usize) -> SbiRet {
+    ecall(eid, fid, [arg0, arg1, 0, 0, 0, 0])
+}
+
+#[inline(always)]
+pub(crate) fn sbi_call_3(eid: usize, fid: usize, arg0: usize, arg1: usize, arg2: usize) -> SbiRet {
+    ecall(eid, fid, [arg0, arg1, arg2, 0, 0, 0])
+}
+
+#[inline(always)]
+pub(crate) fn sbi_call_4(
+    eid: usize,
+    fid: usize,
+    arg0: usize,
+    arg1: usize,
+    arg2: usize,
+    arg3: usize,
+) -> SbiRet {
+    ecall(eid, fid, [arg0, arg1, arg2, arg3, 0, 0])
+}
+
+#[inline(always)]
+pub(crate) fn sbi_call_5(
+    eid: usize,
+    fid: usize,
+    arg0: usize,
+    arg1: usize,
+    arg2: usize,
+    arg3: usize,
+    arg4: usize,
+) -> SbiRet {
+    ecall(eid, fid, [arg0, arg1, arg2, arg3, arg4, 0])
+}
+
+#[inline(always)]
+#[allow(unused)] // only used on RV32 for RISC-V SBI 2.0 specification
+pub(crate) fn sbi_call_6(
+    eid: usize,
+    fid: usize,
+    arg0: usize,
+    arg1: usize,
+    arg2: usize,
+    arg3: usize,
+    arg4: usize,
+    arg5: usize,
+) -> SbiRet {
+    ecall(eid, fid, [arg0, arg1, arg2, arg3, arg4, arg5])
+}
+
+#[cfg(feature = "legacy")]
+#[inline(always)]
+pub(crate) fn sbi_call_legacy_0(eid: usize) -> usize {
+    ecall(eid, 0, [0; 6]).error
+}
+
+#[cfg(feature = "legacy")]
+#[inline(always)]
+pub(crate) fn sbi_call_legacy_1(eid: usize, arg0: usize) -> usize {
+    ecall(eid, 0, [arg0, 0, 0, 0, 0, 0]).error
+}
+
+#[cfg(all(feature = "legacy", target_pointer_width = "32"))]
+#[inline(always)]
+pub(crate) fn sbi_call_legacy_2(eid: usize, arg0: usize, arg1: usize) -> usize {
+    ecall(eid, 0, [arg0, arg1, 0, 0, 0, 0]).error
+}
+
+#[cfg(feature = "legacy")]
+#[inline(always)]
+pub(crate) fn sbi_call_legacy_3(eid: usize, arg0: usize, arg1: usize, arg2: usize) -> usize {
+    ecall(eid, 0, [arg0, arg1, arg2, 0, 0, 0]).error
+}
+
+#[cfg(feature = "legacy")]
+#[inline(always)]
+pub(crate) fn sbi_call_legacy_4(
+    eid: usize,
+    arg0: usize,
+    arg1: usize,
+    arg2: usize,
+    arg3: usize,
+) -> usize {
+    ecall(eid, 0, [arg0, arg1, arg2, arg3, 0, 0]).error
+}
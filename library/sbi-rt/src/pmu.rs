@@ -1,12 +1,13 @@
 //! Chapter 11. Performance Monitoring Unit Extension (EID #0x504D55 "PMU")
 
-use crate::binary::{sbi_call_0, sbi_call_1, sbi_call_3};
+use crate::binary::{sbi_call_0, sbi_call_1, sbi_call_3, sbi_call_4};
 
 use sbi_spec::{
     binary::{CounterMask, SbiRet, SharedPtr},
     pmu::{
         COUNTER_CONFIG_MATCHING, COUNTER_FW_READ, COUNTER_FW_READ_HI, COUNTER_GET_INFO,
-        COUNTER_START, COUNTER_STOP, EID_PMU, NUM_COUNTERS, SNAPSHOT_SET_SHMEM, shmem_size::SIZE,
+        COUNTER_START, COUNTER_STOP, EID_PMU, EVENT_GET_INFO, EventInfo, NUM_COUNTERS,
+        SNAPSHOT_SET_SHMEM, shmem_size::SIZE,
     },
 };
 
@@ -324,6 +325,43 @@ pub fn pmu_snapshot_set_shmem(shmem: SharedPtr<[u8; SIZE]>, flags: usize) -> Sbi
     )
 }
 
+/// Get details about multiple PMU events via shared memory.
+///
+/// # Parameters
+///
+/// The `shmem` parameter specifies the physical base address of an array of `num_entries`
+/// [`EventInfo`] entries. For each entry, the supervisor fills in `event_idx` and `event_data`,
+/// and the SBI implementation sets bit 0 of `output` if the event is supported.
+/// The `shmem` physical address MUST be 16 bytes aligned.
+///
+/// The `flags` parameter is reserved for future use and must be zero.
+///
+/// # Return value
+///
+/// The possible return error codes returned in `SbiRet.error` are shown in the table below:
+///
+/// | Return code                 | Description
+/// |:----------------------------|:----------------------------------------------
+/// | `SbiRet::success()`         | Event details were written to the shared memory successfully.
+/// | `SbiRet::not_supported()`   | This function is not supported by the SBI implementation.
+/// | `SbiRet::invalid_param()`   | The flags parameter is not zero or the `shmem` parameter is not 16 bytes aligned.
+/// | `SbiRet::invalid_address()` | The shared memory pointed to by the `shmem` parameter is not writable or does not satisfy other requirements of RISC-V SBI Specification chapter 3.2.
+/// | `SbiRet::failed()`          | The request failed for unspecified or unknown other reasons.
+///
+/// This function is defined in RISC-V SBI Specification chapter 11.14.
+#[inline]
+#[doc(alias = "sbi_pmu_event_get_info")]
+pub fn pmu_event_get_info(shmem: SharedPtr<EventInfo>, num_entries: usize, flags: usize) -> SbiRet {
+    sbi_call_4(
+        EID_PMU,
+        EVENT_GET_INFO,
+        shmem.phys_addr_lo(),
+        shmem.phys_addr_hi(),
+        num_entries,
+        flags,
+    )
+}
+
 /// Flag parameter accepted when configuring a performance counter.
 pub trait ConfigFlagsParam {
     /// Get a raw value to pass to SBI environment.
@@ -0,0 +1,564 @@
+//! Typed performance counters over the PMU extension.
+//!
+//! Functions such as `pmu_counter_config_matching` take raw event indexes, counter masks and
+//! flags. This module provides typed events, decoded counter information, counter handles
+//! and a view over the snapshot shared memory for profiling tools.
+
+use crate::{
+    pmu_counter_config_matching, pmu_counter_fw_read, pmu_counter_get_info, pmu_counter_start,
+    pmu_counter_stop, pmu_num_counters, pmu_snapshot_set_shmem,
+};
+use core::cell::UnsafeCell;
+use sbi_spec::{
+    binary::{CounterMask, Error, SharedPtr},
+    pmu::{
+        event_type, firmware_event,
+        flags::{ConfigFlags, StartFlags, StopFlags},
+        shmem_size::SIZE,
+    },
+};
+
+/// Event to be monitored by a performance counter.
+///
+/// An event is encoded as an event index, with the event type in bits `[19:16]` and
+/// the event code in bits `[15:0]`, and additional event data for some event types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PmuEvent {
+    event_idx: usize,
+    event_data: u64,
+}
+
+impl PmuEvent {
+    /// Hardware general event with event code `code` in [`sbi_spec::pmu::hardware_event`].
+    #[inline]
+    pub const fn general(code: usize) -> Self {
+        Self::new(event_type::HARDWARE_GENERAL, code, 0)
+    }
+
+    /// Hardware cache event on cache `cache_id` for operation `op_id` with result `result_id`.
+    ///
+    /// Parameters are defined in [`sbi_spec::pmu::cache_event`], [`sbi_spec::pmu::cache_operation`]
+    /// and [`sbi_spec::pmu::cache_result`].
+    #[inline]
+    pub const fn cache(cache_id: usize, op_id: usize, result_id: usize) -> Self {
+        let code = (cache_id << 3) | ((op_id & 0b11) << 1) | (result_id & 0b1);
+        Self::new(event_type::HARDWARE_CACHE, code, 0)
+    }
+
+    /// Hardware raw event with platform specific `event_data` of up to 48 bits.
+    #[inline]
+    pub const fn raw(event_data: u64) -> Self {
+        Self::new(event_type::HARDWARE_RAW, 0, event_data & ((1 << 48) - 1))
+    }
+
+    /// Hardware raw event with platform specific `event_data` of up to 56 bits.
+    #[inline]
+    pub const fn raw_v2(event_data: u64) -> Self {
+        Self::new(event_type::HARDWARE_RAW_V2, 0, event_data & ((1 << 56) - 1))
+    }
+
+    /// Firmware event with event code `code` in [`sbi_spec::pmu::firmware_event`].
+    #[inline]
+    pub const fn firmware(code: usize) -> Self {
+        Self::new(event_type::FIRMWARE, code, 0)
+    }
+
+    /// Platform specific firmware event with event encoding `event_data`.
+    #[inline]
+    pub const fn firmware_platform(event_data: u64) -> Self {
+        Self::new(event_type::FIRMWARE, firmware_event::PLATFORM, event_data)
+    }
+
+    /// Event from raw event index and event data.
+    #[inline]
+    pub const fn from_raw(event_idx: usize, event_data: u64) -> Self {
+        Self {
+            event_idx,
+            event_data,
+        }
+    }
+
+    #[inline]
+    const fn new(ty: usize, code: usize, event_data: u64) -> Self {
+        Self {
+            event_idx: ((ty & 0xF) << 16) | (code & 0xFFFF),
+            event_data,
+        }
+    }
+
+    /// Raw event index passed to `pmu_counter_config_matching`.
+    #[inline]
+    pub const fn event_idx(&self) -> usize {
+        self.event_idx
+    }
+
+    /// Additional event data passed to `pmu_counter_config_matching`.
+    #[inline]
+    pub const fn event_data(&self) -> u64 {
+        self.event_data
+    }
+
+    /// Event type in [`sbi_spec::pmu::event_type`].
+    #[inline]
+    pub const fn event_type(&self) -> usize {
+        (self.event_idx >> 16) & 0xF
+    }
+
+    /// Event code in the event type.
+    #[inline]
+    pub const fn event_code(&self) -> usize {
+        self.event_idx & 0xFFFF
+    }
+}
+
+/// Decoded information of a performance counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CounterInfo {
+    /// Hardware counter, read through a counter CSR.
+    Hardware {
+        /// CSR number of the counter.
+        csr: u16,
+        /// Number of bits of the counter.
+        width: u8,
+    },
+    /// Firmware counter, read using `pmu_counter_fw_read`.
+    Firmware,
+}
+
+impl CounterInfo {
+    /// Decode `counter_info` returned by `pmu_counter_get_info`.
+    #[inline]
+    pub const fn from_raw(raw: usize) -> Self {
+        if raw >> (usize::BITS - 1) != 0 {
+            Self::Firmware
+        } else {
+            Self::Hardware {
+                csr: (raw & 0xFFF) as u16,
+                width: ((raw >> 12) & 0x3F) as u8 + 1,
+            }
+        }
+    }
+
+    /// Encode as `counter_info` returned by `pmu_counter_get_info`.
+    #[inline]
+    pub const fn to_raw(self) -> usize {
+        match self {
+            Self::Hardware { csr, width } => {
+                ((width.saturating_sub(1) as usize & 0x3F) << 12) | (csr as usize & 0xFFF)
+            }
+            Self::Firmware => 1 << (usize::BITS - 1),
+        }
+    }
+
+    /// Get information of the counter `counter_idx`.
+    #[inline]
+    pub fn get(counter_idx: usize) -> Result<Self, Error> {
+        pmu_counter_get_info(counter_idx)
+            .into_result()
+            .map(Self::from_raw)
+    }
+
+    /// Whether this is a firmware counter.
+    #[inline]
+    pub const fn is_firmware(&self) -> bool {
+        matches!(self, Self::Firmware)
+    }
+}
+
+/// Handle of a performance counter on the calling hart.
+///
+/// Counters are per hart; a handle should be used on the hart it is configured on.
+/// The counter keeps its configuration when the handle is dropped; use [`Counter::release`]
+/// to stop the counter and reset its event mapping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Counter {
+    index: usize,
+    info: CounterInfo,
+}
+
+impl Counter {
+    /// Find and configure a counter in `counters` to monitor `event` with `flags`.
+    pub fn config_matching(
+        counters: CounterMask,
+        event: PmuEvent,
+        flags: ConfigFlags,
+    ) -> Result<Self, Error> {
+        let index =
+            pmu_counter_config_matching(counters, flags, event.event_idx(), event.event_data())
+                .into_result()?;
+        Self::from_index(index)
+    }
+
+    /// Find and configure any counter on the calling hart to monitor `event` with `flags`.
+    ///
+    /// Counters are searched from index 0 in sets of `XLEN` counters.
+    pub fn config_any(event: PmuEvent, flags: ConfigFlags) -> Result<Self, Error> {
+        let num_counters = pmu_num_counters();
+        let mut base = 0;
+        while base < num_counters {
+            let count = (num_counters - base).min(usize::BITS as usize);
+            let mask = if count == usize::BITS as usize {
+                usize::MAX
+            } else {
+                (1 << count) - 1
+            };
+            match Self::config_matching(CounterMask::from_mask_base(mask, base), event, flags) {
+                Err(Error::NotSupported) => base += count,
+                result => return result,
+            }
+        }
+        Err(Error::NotSupported)
+    }
+
+    /// Handle of the counter `index`, which is configured by other means.
+    #[inline]
+    pub fn from_index(index: usize) -> Result<Self, Error> {
+        let info = CounterInfo::get(index)?;
+        Ok(Self { index, info })
+    }
+
+    /// Index of this counter.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Information of this counter.
+    #[inline]
+    pub const fn info(&self) -> CounterInfo {
+        self.info
+    }
+
+    /// Counter mask selecting only this counter.
+    #[inline]
+    pub const fn mask(&self) -> CounterMask {
+        CounterMask::from_mask_base(1, self.index)
+    }
+
+    /// Start this counter, from `initial_value` if provided or from its current value otherwise.
+    #[inline]
+    pub fn start(&self, initial_value: Option<u64>) -> Result<(), Error> {
+        let (flags, value) = match initial_value {
+            Some(value) => (StartFlags::INIT_VALUE, value),
+            None => (StartFlags::empty(), 0),
+        };
+        pmu_counter_start(self.mask(), flags, value)
+            .into_result()
+            .map(drop)
+    }
+
+    /// Stop this counter.
+    #[inline]
+    pub fn stop(&self) -> Result<(), Error> {
+        pmu_counter_stop(self.mask(), StopFlags::empty())
+            .into_result()
+            .map(drop)
+    }
+
+    /// Stop this counter and save its value into the snapshot shared memory.
+    ///
+    /// The counter value is then at index 0 of [`Snapshot::counter_value`].
+    #[inline]
+    pub fn stop_with_snapshot(&self) -> Result<(), Error> {
+        pmu_counter_stop(self.mask(), StopFlags::TAKE_SNAPSHOT)
+            .into_result()
+            .map(drop)
+    }
+
+    /// Stop this counter and reset its event mapping, so that it may be configured again.
+    #[inline]
+    pub fn release(self) -> Result<(), Error> {
+        pmu_counter_stop(self.mask(), StopFlags::RESET)
+            .into_result()
+            .map(drop)
+    }
+
+    /// Read the current value of this counter.
+    ///
+    /// Hardware counters are read from their counter CSRs, and firmware counters are read
+    /// using `pmu_counter_fw_read`.
+    pub fn read(&self) -> Result<u64, Error> {
+        match self.info {
+            CounterInfo::Hardware { csr, width } => {
+                let value = read_counter_csr(csr).ok_or(Error::NotSupported)?;
+                Ok(if width >= 64 {
+                    value
+                } else {
+                    value & ((1 << width) - 1)
+                })
+            }
+            CounterInfo::Firmware => {
+                let lo = pmu_counter_fw_read(self.index).into_result()?;
+                match () {
+                    #[cfg(target_pointer_width = "32")]
+                    () => {
+                        let hi = crate::pmu_counter_fw_read_hi(self.index).into_result()?;
+                        Ok(((hi as u64) << 32) | lo as u64)
+                    }
+                    #[cfg(target_pointer_width = "64")]
+                    () => Ok(lo as u64),
+                }
+            }
+        }
+    }
+}
+
+// Reads a counter CSR in `cycle`, `time`, `instret` and `hpmcounter3` to `hpmcounter31`.
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
+fn read_counter_csr(csr: u16) -> Option<u64> {
+    macro_rules! csrr {
+        ($csr:expr) => {{
+            let value: usize;
+            unsafe { core::arch::asm!(concat!("csrr {}, ", $csr), out(reg) value) };
+            value
+        }};
+    }
+    macro_rules! read_counter {
+        ($csr:expr, $($lo:literal, $hi:literal;)*) => {
+            match $csr {
+                $(
+                    $lo => match () {
+                        #[cfg(target_pointer_width = "32")]
+                        () => loop {
+                            let hi = csrr!($hi);
+                            let lo = csrr!($lo);
+                            if hi == csrr!($hi) {
+                                break Some(((hi as u64) << 32) | lo as u64);
+                            }
+                        },
+                        #[cfg(target_pointer_width = "64")]
+                        () => Some(csrr!($lo) as u64),
+                    },
+                )*
+                _ => None,
+            }
+        };
+    }
+    read_counter!(csr,
+        0xC00, 0xC80; 0xC01, 0xC81; 0xC02, 0xC82; 0xC03, 0xC83;
+        0xC04, 0xC84; 0xC05, 0xC85; 0xC06, 0xC86; 0xC07, 0xC87;
+        0xC08, 0xC88; 0xC09, 0xC89; 0xC0A, 0xC8A; 0xC0B, 0xC8B;
+        0xC0C, 0xC8C; 0xC0D, 0xC8D; 0xC0E, 0xC8E; 0xC0F, 0xC8F;
+        0xC10, 0xC90; 0xC11, 0xC91; 0xC12, 0xC92; 0xC13, 0xC93;
+        0xC14, 0xC94; 0xC15, 0xC95; 0xC16, 0xC96; 0xC17, 0xC97;
+        0xC18, 0xC98; 0xC19, 0xC99; 0xC1A, 0xC9A; 0xC1B, 0xC9B;
+        0xC1C, 0xC9C; 0xC1D, 0xC9D; 0xC1E, 0xC9E; 0xC1F, 0xC9F;
+    )
+}
+
+#[cfg(not(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+)))]
+fn read_counter_csr(_csr: u16) -> Option<u64> {
+    match () {
+        // counter CSRs are not available in the mock environment
+        #[cfg(feature = "mock")]
+        () => None,
+        #[cfg(not(feature = "mock"))]
+        () => unimplemented!("unsupported architecture"),
+    }
+}
+
+/// PMU snapshot shared memory of a hart.
+///
+/// The SBI implementation saves counter values into the snapshot shared memory when counters
+/// are stopped with the `TAKE_SNAPSHOT` flag, and loads counter values from it when counters
+/// are started with the `INIT_SNAPSHOT` flag. Values in the snapshot are relative to the
+/// `counter_idx_base` of the counter mask of these calls.
+///
+/// The snapshot is accessed by the SBI implementation through its physical address after
+/// [`Snapshot::set_shmem`], thus all accesses of this structure are volatile.
+///
+/// A snapshot may be put in a `static` and shared between harts, but it must be set as the
+/// shared memory of one hart at a time, see [`Snapshot::set_shmem`].
+#[repr(C, align(4096))]
+pub struct Snapshot(UnsafeCell<[u64; SIZE / 8]>);
+
+// SAFETY: shared references only read the snapshot, except for the unsafe
+// `Snapshot::set_shmem` and `Snapshot::set_counter_value`, which require that the SBI
+// implementation and Rust callers write the snapshot on behalf of one hart at a time.
+unsafe impl Sync for Snapshot {}
+
+// Offsets in `u64` words.
+const OVERFLOW_BITMAP: usize = 0;
+const COUNTER_VALUES: usize = 1;
+
+/// Number of counter values in the snapshot shared memory.
+pub const SNAPSHOT_COUNTERS: usize = 64;
+
+impl Snapshot {
+    /// Create a zeroed snapshot shared memory.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(UnsafeCell::new([0; SIZE / 8]))
+    }
+
+    /// Set this snapshot as the PMU snapshot shared memory of the calling hart.
+    ///
+    /// The physical address of this snapshot is its virtual address minus `phys_offset`.
+    ///
+    /// # Safety
+    ///
+    /// This snapshot must not be set as the PMU snapshot shared memory of any other hart
+    /// until it is cleared on the calling hart with [`Snapshot::clear_shmem`].
+    #[inline]
+    pub unsafe fn set_shmem(&'static self, phys_offset: usize) -> Result<(), Error> {
+        let phys_addr = (self.0.get() as usize).wrapping_sub(phys_offset);
+        pmu_snapshot_set_shmem(SharedPtr::new(phys_addr, 0), 0)
+            .into_result()
+            .map(drop)
+    }
+
+    /// Disable the PMU snapshot shared memory of the calling hart.
+    #[inline]
+    pub fn clear_shmem() -> Result<(), Error> {
+        pmu_snapshot_set_shmem(SharedPtr::new(usize::MAX, usize::MAX), 0)
+            .into_result()
+            .map(drop)
+    }
+
+    /// Bitmap of overflown counters; valid only if the `Sscofpmf` ISA extension is available.
+    #[inline]
+    pub fn overflow_bitmap(&self) -> u64 {
+        self.read(OVERFLOW_BITMAP)
+    }
+
+    /// Whether the counter at `index` of the snapshot has overflown.
+    #[inline]
+    pub fn is_overflown(&self, index: usize) -> bool {
+        index < SNAPSHOT_COUNTERS && self.overflow_bitmap() & (1 << index) != 0
+    }
+
+    /// Value of the counter at `index` of the snapshot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`SNAPSHOT_COUNTERS`].
+    #[inline]
+    pub fn counter_value(&self, index: usize) -> u64 {
+        assert!(
+            index < SNAPSHOT_COUNTERS,
+            "snapshot counter index out of range"
+        );
+        self.read(COUNTER_VALUES + index)
+    }
+
+    /// Set the value of the counter at `index` of the snapshot, used by counters
+    /// started with the `INIT_SNAPSHOT` flag.
+    ///
+    /// # Safety
+    ///
+    /// No other hart may read or write this snapshot until this call returns, including
+    /// the SBI implementation on behalf of a hart this snapshot is set on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`SNAPSHOT_COUNTERS`].
+    #[inline]
+    pub unsafe fn set_counter_value(&self, index: usize, value: u64) {
+        assert!(
+            index < SNAPSHOT_COUNTERS,
+            "snapshot counter index out of range"
+        );
+        unsafe {
+            (self.0.get() as *mut u64)
+                .add(COUNTER_VALUES + index)
+                .write_volatile(value)
+        }
+    }
+
+    #[inline]
+    fn read(&self, offset: usize) -> u64 {
+        unsafe { (self.0.get() as *const u64).add(offset).read_volatile() }
+    }
+}
+
+impl Default for Snapshot {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbi_spec::pmu::{cache_event, cache_operation, cache_result, hardware_event};
+
+    #[test]
+    fn pmu_event_encoding() {
+        let event = PmuEvent::general(hardware_event::CPU_CYCLES);
+        assert_eq!((event.event_idx(), event.event_data()), (0x00001, 0));
+        let event = PmuEvent::cache(
+            cache_event::DTLB,
+            cache_operation::WRITE,
+            cache_result::MISS,
+        );
+        assert_eq!(event.event_idx(), 0x1001B);
+        assert_eq!((event.event_type(), event.event_code()), (1, 0x1B));
+        let event = PmuEvent::cache(cache_event::L1D, cache_operation::READ, cache_result::MISS);
+        assert_eq!(event.event_idx(), 0x10001);
+        let event = PmuEvent::raw(0xFFFF_1234_5678_9ABC);
+        assert_eq!(
+            (event.event_idx(), event.event_data()),
+            (0x20000, 0x1234_5678_9ABC)
+        );
+        let event = PmuEvent::raw_v2(0xFF12_3456_789A_BCDE);
+        assert_eq!(
+            (event.event_idx(), event.event_data()),
+            (0x30000, 0x12_3456_789A_BCDE)
+        );
+        let event = PmuEvent::firmware(firmware_event::IPI_SENT);
+        assert_eq!(event.event_idx(), 0xF0006);
+        let event = PmuEvent::firmware_platform(0x42);
+        assert_eq!((event.event_idx(), event.event_data()), (0xFFFFF, 0x42));
+    }
+
+    #[test]
+    fn counter_info_decode() {
+        let info = CounterInfo::from_raw((63 << 12) | 0xC00);
+        assert_eq!(
+            info,
+            CounterInfo::Hardware {
+                csr: 0xC00,
+                width: 64
+            }
+        );
+        assert_eq!(info.to_raw(), (63 << 12) | 0xC00);
+        let info = CounterInfo::from_raw((47 << 12) | 0xC03);
+        assert_eq!(
+            info,
+            CounterInfo::Hardware {
+                csr: 0xC03,
+                width: 48
+            }
+        );
+        let info = CounterInfo::from_raw(1 << (usize::BITS - 1));
+        assert!(info.is_firmware());
+        assert_eq!(info.to_raw(), 1 << (usize::BITS - 1));
+    }
+
+    #[test]
+    fn snapshot_layout() {
+        assert_eq!(size_of::<Snapshot>(), SIZE);
+        assert_eq!(align_of::<Snapshot>(), SIZE);
+        let snapshot = Snapshot::new();
+        unsafe {
+            let words = &mut *snapshot.0.get();
+            words[0] = 0b101;
+            words[1] = 100;
+            words[3] = 300;
+        }
+        assert_eq!(snapshot.overflow_bitmap(), 0b101);
+        assert!(snapshot.is_overflown(2) && !snapshot.is_overflown(1));
+        assert!(!snapshot.is_overflown(64));
+        assert_eq!(snapshot.counter_value(0), 100);
+        assert_eq!(snapshot.counter_value(2), 300);
+        unsafe { snapshot.set_counter_value(63, 6300) };
+        assert_eq!(unsafe { (*snapshot.0.get())[64] }, 6300);
+    }
+}
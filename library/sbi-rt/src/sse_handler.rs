@@ -0,0 +1,495 @@
+//! Software event handler registration over the SSE extension.
+//!
+//! Function `sse_register` passes raw handler entry address and argument to the SBI
+//! implementation. This module provides the handler entry trampoline, a stack for each
+//! registered event, and access to the interrupted supervisor context, so that software
+//! events can be handled by Rust functions.
+
+use crate::{
+    sse_disable, sse_enable, sse_inject, sse_read_attrs, sse_register, sse_unregister,
+    sse_write_attrs,
+};
+use core::{mem::ManuallyDrop, ptr::NonNull};
+use sbi_spec::{
+    binary::{Error, SharedPtr},
+    sse::{attr_id, flags::InterruptedFlags},
+};
+
+/// Software event handler function.
+///
+/// The handler runs on the stack of its [`SseEvent`] with supervisor interrupts disabled.
+/// The SSE extension completes the event after the handler returns, resuming the interrupted
+/// context with general registers in [`SseContext::regs`].
+pub type SseHandler = fn(&mut SseContext<'_>);
+
+/// Storage of a software event handler, including the stack it runs on.
+///
+/// Software events may interrupt supervisor software at any point where the event is enabled,
+/// even when supervisor interrupts are disabled; thus the handler runs on a separate stack of
+/// `STACK_SIZE` bytes in this structure.
+///
+/// An event storage must be `'static` as the SBI implementation may enter the handler until
+/// the event is unregistered. Each registered event needs its own storage; for local events,
+/// each hart registering the event needs its own storage as well.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sbi_rt::{SseContext, SseEvent};
+/// use sbi_spec::sse::event_id;
+///
+/// static mut EVENT: SseEvent = SseEvent::new();
+///
+/// fn on_event(ctx: &mut SseContext) {
+///     let interrupted = ctx.interrupted().unwrap();
+///     // ... handle the software event interrupting `interrupted.sepc`
+/// #   let _ = interrupted;
+/// }
+///
+/// let event = unsafe { &mut *(&raw mut EVENT) };
+/// let mut registration = event
+///     .register(event_id::LOCAL_SOFTWARE_INJECTED, on_event)
+///     .map_err(|(_, error)| error)
+///     .unwrap();
+/// registration.enable().unwrap();
+/// // the software event is disabled and unregistered when `registration` is dropped
+/// ```
+#[repr(C, align(16))]
+pub struct SseEvent<const STACK_SIZE: usize = 4096> {
+    stack: [u8; STACK_SIZE],
+    header: Header,
+}
+
+// State of a registered software event, passed to the entry trampoline as `ENTRY_ARG`.
+#[repr(C)]
+pub(crate) struct Header {
+    // initial stack pointer of the event handler
+    stack_top: usize,
+    // stack pointer of the interrupted context, used by the entry trampoline
+    saved_sp: usize,
+    handler: Option<SseHandler>,
+    event_id: u32,
+    // virtual address minus physical address of this structure
+    phys_offset: usize,
+    // shared memory for `INTERRUPTED_*` attributes
+    attrs: [usize; 4],
+}
+
+/// General registers of the context interrupted by a software event.
+///
+/// Registers `a6` and `a7` are used to pass the hart ID and the entry argument to the handler,
+/// and their interrupted values are provided by the SBI implementation in
+/// [`SseInterrupted`] instead.
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct SseRegs {
+    pub ra: usize,
+    pub sp: usize,
+    pub gp: usize,
+    pub tp: usize,
+    pub t0: usize,
+    pub t1: usize,
+    pub t2: usize,
+    pub s0: usize,
+    pub s1: usize,
+    pub a0: usize,
+    pub a1: usize,
+    pub a2: usize,
+    pub a3: usize,
+    pub a4: usize,
+    pub a5: usize,
+    pub s2: usize,
+    pub s3: usize,
+    pub s4: usize,
+    pub s5: usize,
+    pub s6: usize,
+    pub s7: usize,
+    pub s8: usize,
+    pub s9: usize,
+    pub s10: usize,
+    pub s11: usize,
+    pub t3: usize,
+    pub t4: usize,
+    pub t5: usize,
+    pub t6: usize,
+}
+
+// The entry trampoline saves registers at these offsets.
+const _: () = {
+    use core::mem::{offset_of, size_of};
+    const X: usize = size_of::<usize>();
+    assert!(offset_of!(SseRegs, sp) == X);
+    assert!(offset_of!(SseRegs, a0) == 9 * X);
+    assert!(offset_of!(SseRegs, a5) == 14 * X);
+    assert!(offset_of!(SseRegs, s2) == 15 * X);
+    assert!(offset_of!(SseRegs, t6) == 28 * X);
+    assert!(size_of::<SseRegs>().is_multiple_of(16));
+};
+
+/// Supervisor state interrupted by a software event.
+///
+/// These values are read from and written to the `INTERRUPTED_*` event attributes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SseInterrupted {
+    /// Interrupted `sepc` value.
+    pub sepc: usize,
+    /// Interrupted `sstatus` and `hstatus` bits.
+    pub flags: InterruptedFlags,
+    /// Interrupted `a6` value.
+    pub a6: usize,
+    /// Interrupted `a7` value.
+    pub a7: usize,
+}
+
+/// Context of a running software event handler.
+pub struct SseContext<'a> {
+    header: &'a mut Header,
+    hart_id: usize,
+    regs: &'a mut SseRegs,
+}
+
+/// Registered software event handler.
+///
+/// The event is disabled if enabled, and unregistered when this handle is dropped.
+#[must_use = "dropping the registration unregisters the software event"]
+pub struct SseRegistration<const STACK_SIZE: usize = 4096> {
+    event: NonNull<SseEvent<STACK_SIZE>>,
+    enabled: bool,
+}
+
+impl<const STACK_SIZE: usize> SseEvent<STACK_SIZE> {
+    /// Create a software event handler storage for identity mapped supervisor software.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_phys_offset(0)
+    }
+
+    /// Create a software event handler storage, whose physical address is its virtual address
+    /// minus `phys_offset`.
+    ///
+    /// The event handler reads and writes event attributes through this storage, which
+    /// requires physical addresses.
+    #[inline]
+    pub const fn with_phys_offset(phys_offset: usize) -> Self {
+        Self {
+            stack: [0; STACK_SIZE],
+            header: Header {
+                stack_top: 0,
+                saved_sp: 0,
+                handler: None,
+                event_id: 0,
+                phys_offset,
+                attrs: [0; 4],
+            },
+        }
+    }
+
+    /// Register `handler` for the software event `event_id`.
+    ///
+    /// On success, the event moves from `UNUSED` to `REGISTERED` state, and a handle of the
+    /// registration is returned. On failure, this storage is returned along with the error.
+    ///
+    /// For local events, the event is registered only for the calling hart.
+    /// For global events, the event is registered for all the harts.
+    pub fn register(
+        &'static mut self,
+        event_id: u32,
+        handler: SseHandler,
+    ) -> Result<SseRegistration<STACK_SIZE>, (&'static mut Self, Error)> {
+        let stack_top = self.stack.as_ptr_range().end as usize & !0xF;
+        self.header.stack_top = stack_top;
+        self.header.handler = Some(handler);
+        self.header.event_id = event_id;
+        let entry_arg = &raw mut self.header as usize;
+        match sse_register(event_id, sse_entry as *const () as usize, entry_arg).into_result() {
+            Ok(_) => Ok(SseRegistration {
+                event: NonNull::from(self),
+                enabled: false,
+            }),
+            Err(error) => Err((self, error)),
+        }
+    }
+}
+
+impl<const STACK_SIZE: usize> Default for SseEvent<STACK_SIZE> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const STACK_SIZE: usize> SseRegistration<STACK_SIZE> {
+    /// Software event ID of this registration.
+    #[inline]
+    pub fn event_id(&self) -> u32 {
+        unsafe { self.event.as_ref() }.header.event_id
+    }
+
+    /// Enable the software event, moving it from `REGISTERED` to `ENABLED` state.
+    #[inline]
+    pub fn enable(&mut self) -> Result<(), Error> {
+        sse_enable(self.event_id()).into_result()?;
+        self.enabled = true;
+        Ok(())
+    }
+
+    /// Disable the software event, moving it from `ENABLED` to `REGISTERED` state.
+    #[inline]
+    pub fn disable(&mut self) -> Result<(), Error> {
+        sse_disable(self.event_id()).into_result()?;
+        self.enabled = false;
+        Ok(())
+    }
+
+    /// Inject the software event on hart `hart_id`; the hart ID is ignored for global events.
+    #[inline]
+    pub fn inject(&self, hart_id: usize) -> Result<(), Error> {
+        sse_inject(self.event_id(), hart_id).into_result().map(drop)
+    }
+
+    /// Disable the software event if enabled, and unregister it.
+    ///
+    /// On success, the event storage is returned for later registrations. On failure, this
+    /// handle is returned along with the error.
+    pub fn unregister(mut self) -> Result<&'static mut SseEvent<STACK_SIZE>, (Self, Error)> {
+        if self.enabled
+            && let Err(error) = self.disable()
+        {
+            return Err((self, error));
+        }
+        if let Err(error) = sse_unregister(self.event_id()).into_result() {
+            return Err((self, error));
+        }
+        let mut this = ManuallyDrop::new(self);
+        Ok(unsafe { this.event.as_mut() })
+    }
+}
+
+impl<const STACK_SIZE: usize> Drop for SseRegistration<STACK_SIZE> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.enabled {
+            let _ = sse_disable(self.event_id());
+        }
+        let _ = sse_unregister(self.event_id());
+    }
+}
+
+impl SseContext<'_> {
+    /// ID of the software event being handled.
+    #[inline]
+    pub fn event_id(&self) -> u32 {
+        self.header.event_id
+    }
+
+    /// ID of the hart handling the software event.
+    #[inline]
+    pub fn hart_id(&self) -> usize {
+        self.hart_id
+    }
+
+    /// General registers of the interrupted context.
+    #[inline]
+    pub fn regs(&self) -> &SseRegs {
+        self.regs
+    }
+
+    /// Mutable general registers of the interrupted context, restored when the handler returns.
+    #[inline]
+    pub fn regs_mut(&mut self) -> &mut SseRegs {
+        self.regs
+    }
+
+    /// Read the interrupted supervisor state from `INTERRUPTED_*` event attributes.
+    pub fn interrupted(&mut self) -> Result<SseInterrupted, Error> {
+        let output = self.attrs_ptr();
+        sse_read_attrs(self.header.event_id, attr_id::INTERRUPTED_SEPC, 4, output).into_result()?;
+        let [sepc, flags, a6, a7] = self.header.attrs;
+        Ok(SseInterrupted {
+            sepc,
+            flags: InterruptedFlags::from_bits_retain(flags),
+            a6,
+            a7,
+        })
+    }
+
+    /// Write the interrupted supervisor state to `INTERRUPTED_*` event attributes, which is
+    /// restored when the event completes.
+    pub fn set_interrupted(&mut self, interrupted: &SseInterrupted) -> Result<(), Error> {
+        self.header.attrs = [
+            interrupted.sepc,
+            interrupted.flags.bits(),
+            interrupted.a6,
+            interrupted.a7,
+        ];
+        let input = self.attrs_ptr();
+        sse_write_attrs(self.header.event_id, attr_id::INTERRUPTED_SEPC, 4, input)
+            .into_result()
+            .map(drop)
+    }
+
+    #[inline]
+    fn attrs_ptr(&mut self) -> SharedPtr<u8> {
+        let phys_addr = (&raw mut self.header.attrs as usize).wrapping_sub(self.header.phys_offset);
+        SharedPtr::new(phys_addr, 0)
+    }
+}
+
+// Called by the entry trampoline on the event stack.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64", feature = "mock"))]
+pub(crate) unsafe extern "C" fn handle_event(
+    header: *mut Header,
+    hart_id: usize,
+    regs: *mut SseRegs,
+) {
+    let (header, regs) = unsafe { (&mut *header, &mut *regs) };
+    if let Some(handler) = header.handler {
+        handler(&mut SseContext {
+            header,
+            hart_id,
+            regs,
+        });
+    }
+}
+
+/// Simulate the entry trampoline handling a software event registered with `entry_arg`.
+#[cfg(feature = "mock")]
+pub(crate) unsafe fn handle_event_mock(
+    entry_arg: usize,
+    hart_id: usize,
+    regs: &mut SseRegs,
+) -> sbi_spec::binary::SbiRet {
+    unsafe { handle_event(entry_arg as *mut Header, hart_id, regs) };
+    crate::sse_complete()
+}
+
+#[cfg(all(target_arch = "riscv64", not(feature = "mock")))]
+macro_rules! sx {
+    () => {
+        "sd"
+    };
+}
+#[cfg(all(target_arch = "riscv64", not(feature = "mock")))]
+macro_rules! lx {
+    () => {
+        "ld"
+    };
+}
+#[cfg(all(target_arch = "riscv32", not(feature = "mock")))]
+macro_rules! sx {
+    () => {
+        "sw"
+    };
+}
+#[cfg(all(target_arch = "riscv32", not(feature = "mock")))]
+macro_rules! lx {
+    () => {
+        "lw"
+    };
+}
+
+/// Entry of all software event handlers registered by this module.
+///
+/// The SBI implementation enters with `a6` holding the hart ID and `a7` holding the event header,
+/// and all other general registers holding values of the interrupted context.
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+))]
+#[unsafe(naked)]
+unsafe extern "C" fn sse_entry() -> ! {
+    core::arch::naked_asm!(
+        // switch to the event stack
+        concat!(sx!(), " sp, {saved_sp}(a7)"),
+        concat!(lx!(), " sp, {stack_top}(a7)"),
+        "addi   sp, sp, -{regs_size}",
+        // save general registers of the interrupted context
+        concat!(sx!(), " ra, 0*{x}(sp)"),
+        concat!(sx!(), " gp, 2*{x}(sp)"),
+        concat!(sx!(), " tp, 3*{x}(sp)"),
+        concat!(sx!(), " t0, 4*{x}(sp)"),
+        concat!(sx!(), " t1, 5*{x}(sp)"),
+        concat!(sx!(), " t2, 6*{x}(sp)"),
+        concat!(sx!(), " s0, 7*{x}(sp)"),
+        concat!(sx!(), " s1, 8*{x}(sp)"),
+        concat!(sx!(), " a0, 9*{x}(sp)"),
+        concat!(sx!(), " a1, 10*{x}(sp)"),
+        concat!(sx!(), " a2, 11*{x}(sp)"),
+        concat!(sx!(), " a3, 12*{x}(sp)"),
+        concat!(sx!(), " a4, 13*{x}(sp)"),
+        concat!(sx!(), " a5, 14*{x}(sp)"),
+        concat!(sx!(), " s2, 15*{x}(sp)"),
+        concat!(sx!(), " s3, 16*{x}(sp)"),
+        concat!(sx!(), " s4, 17*{x}(sp)"),
+        concat!(sx!(), " s5, 18*{x}(sp)"),
+        concat!(sx!(), " s6, 19*{x}(sp)"),
+        concat!(sx!(), " s7, 20*{x}(sp)"),
+        concat!(sx!(), " s8, 21*{x}(sp)"),
+        concat!(sx!(), " s9, 22*{x}(sp)"),
+        concat!(sx!(), " s10, 23*{x}(sp)"),
+        concat!(sx!(), " s11, 24*{x}(sp)"),
+        concat!(sx!(), " t3, 25*{x}(sp)"),
+        concat!(sx!(), " t4, 26*{x}(sp)"),
+        concat!(sx!(), " t5, 27*{x}(sp)"),
+        concat!(sx!(), " t6, 28*{x}(sp)"),
+        concat!(lx!(), " t0, {saved_sp}(a7)"),
+        concat!(sx!(), " t0, 1*{x}(sp)"),
+        // handle_event(header, hart_id, regs)
+        "mv     a0, a7",
+        "mv     a1, a6",
+        "mv     a2, sp",
+        "call   {handle_event}",
+        // restore general registers, stack pointer the last
+        concat!(lx!(), " ra, 0*{x}(sp)"),
+        concat!(lx!(), " gp, 2*{x}(sp)"),
+        concat!(lx!(), " tp, 3*{x}(sp)"),
+        concat!(lx!(), " t0, 4*{x}(sp)"),
+        concat!(lx!(), " t1, 5*{x}(sp)"),
+        concat!(lx!(), " t2, 6*{x}(sp)"),
+        concat!(lx!(), " s0, 7*{x}(sp)"),
+        concat!(lx!(), " s1, 8*{x}(sp)"),
+        concat!(lx!(), " a0, 9*{x}(sp)"),
+        concat!(lx!(), " a1, 10*{x}(sp)"),
+        concat!(lx!(), " a2, 11*{x}(sp)"),
+        concat!(lx!(), " a3, 12*{x}(sp)"),
+        concat!(lx!(), " a4, 13*{x}(sp)"),
+        concat!(lx!(), " a5, 14*{x}(sp)"),
+        concat!(lx!(), " s2, 15*{x}(sp)"),
+        concat!(lx!(), " s3, 16*{x}(sp)"),
+        concat!(lx!(), " s4, 17*{x}(sp)"),
+        concat!(lx!(), " s5, 18*{x}(sp)"),
+        concat!(lx!(), " s6, 19*{x}(sp)"),
+        concat!(lx!(), " s7, 20*{x}(sp)"),
+        concat!(lx!(), " s8, 21*{x}(sp)"),
+        concat!(lx!(), " s9, 22*{x}(sp)"),
+        concat!(lx!(), " s10, 23*{x}(sp)"),
+        concat!(lx!(), " s11, 24*{x}(sp)"),
+        concat!(lx!(), " t3, 25*{x}(sp)"),
+        concat!(lx!(), " t4, 26*{x}(sp)"),
+        concat!(lx!(), " t5, 27*{x}(sp)"),
+        concat!(lx!(), " t6, 28*{x}(sp)"),
+        concat!(lx!(), " sp, 1*{x}(sp)"),
+        // complete the event; the SBI implementation restores `a6`, `a7` and `sepc`,
+        // and resumes the interrupted context without returning from this call
+        "li     a7, {eid_sse}",
+        "li     a6, {complete}",
+        "ecall",
+        "unimp",
+        saved_sp = const core::mem::offset_of!(Header, saved_sp),
+        stack_top = const core::mem::offset_of!(Header, stack_top),
+        regs_size = const size_of::<SseRegs>(),
+        x = const size_of::<usize>(),
+        handle_event = sym handle_event,
+        eid_sse = const sbi_spec::sse::EID_SSE,
+        complete = const sbi_spec::sse::COMPLETE,
+    )
+}
+
+#[cfg(not(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    not(feature = "mock")
+)))]
+unsafe extern "C" fn sse_entry() -> ! {
+    unimplemented!("unsupported architecture")
+}
@@ -0,0 +1,92 @@
+#![cfg(all(feature = "mock", feature = "console"))]
+
+use sbi_rt::{SbiRet, console, mock};
+use sbi_spec::{base, dbcn};
+use std::{cell::RefCell, rc::Rc};
+
+/// Extension ID of the legacy `console_putchar` function.
+const LEGACY_CONSOLE_PUTCHAR: usize = 0x01;
+
+#[derive(Default)]
+struct MockConsole {
+    output: Vec<u8>,
+    writes: usize,
+    write_bytes: usize,
+}
+
+// Console backend is global state, thus all cases are run in one test.
+#[test]
+fn console_output() {
+    // Debug Console extension, accepting at most 8 bytes per call
+    let state = Rc::new(RefCell::new(MockConsole::default()));
+    let mock_state = state.clone();
+    mock::set_handler(move |extension, function, param| {
+        let mut state = mock_state.borrow_mut();
+        match (extension, function) {
+            (base::EID_BASE, base::PROBE_EXTENSION) => SbiRet::success(1),
+            (dbcn::EID_DBCN, dbcn::CONSOLE_WRITE) => {
+                let len = param[0].min(8);
+                let bytes = unsafe { std::slice::from_raw_parts(param[1] as *const u8, len) };
+                state.output.extend_from_slice(bytes);
+                state.writes += 1;
+                SbiRet::success(len)
+            }
+            _ => unreachable!(),
+        }
+    });
+    assert_eq!(console::init(), console::Backend::DebugConsole);
+    sbi_rt::println!("hello, {}!", "world");
+    assert_eq!(state.borrow().output, b"hello, world!\n");
+    assert_eq!(state.borrow().writes, 2);
+
+    // buffered output is flushed when the buffer is full
+    state.borrow_mut().output.clear();
+    sbi_rt::print!("{}", "a".repeat(console::BUFFER_SIZE + 1));
+    assert_eq!(state.borrow().output.len(), console::BUFFER_SIZE + 1);
+
+    // writes by physical address fail, fall back to `console_write_byte`
+    let mock_state = state.clone();
+    mock::set_handler(move |extension, function, _| {
+        let mut state = mock_state.borrow_mut();
+        match (extension, function) {
+            (dbcn::EID_DBCN, dbcn::CONSOLE_WRITE) => SbiRet::invalid_param(),
+            (dbcn::EID_DBCN, dbcn::CONSOLE_WRITE_BYTE) => {
+                state.write_bytes += 1;
+                SbiRet::success(0)
+            }
+            _ => unreachable!(),
+        }
+    });
+    console::set_phys_offset(0x1000);
+    sbi_rt::print!("abc");
+    assert_eq!(state.borrow().write_bytes, 3);
+    console::set_phys_offset(0);
+
+    // legacy console fallback
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let recorded = output.clone();
+    mock::set_handler(
+        move |extension, function, param| match (extension, function) {
+            (base::EID_BASE, base::PROBE_EXTENSION) => SbiRet::success(0),
+            (LEGACY_CONSOLE_PUTCHAR, 0) => {
+                recorded.borrow_mut().push(param[0] as u8);
+                SbiRet::success(0)
+            }
+            _ => unreachable!(),
+        },
+    );
+    assert_eq!(console::init(), console::Backend::Legacy);
+    sbi_rt::println!();
+    sbi_rt::print!("{:x}", 0xbeef);
+    assert_eq!(*output.borrow(), b"\nbeef");
+
+    #[cfg(feature = "log")]
+    {
+        output.borrow_mut().clear();
+        console::Logger::init(log::LevelFilter::Info).unwrap();
+        log::info!("booted");
+        log::debug!("filtered");
+        assert_eq!(*output.borrow(), b"[ INFO] booted\n");
+    }
+    mock::clear_handler();
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "mock")]
+
+use rustsbi::{Console, EnvInfo, Physical, RustSBI, SbiRet};
+use sbi_rt::mock;
+use sbi_spec::{base, pmu, rfnc};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+#[test]
+fn mock_closure_handler() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorded = calls.clone();
+    mock::set_handler(move |extension, function, param| {
+        recorded.borrow_mut().push((extension, function, param));
+        SbiRet::success(function + 1)
+    });
+    assert!(mock::has_handler());
+
+    assert_eq!(sbi_rt::get_spec_version().major(), 0);
+    assert_eq!(
+        sbi_rt::remote_fence_i(sbi_rt::HartMask::from_mask_base(0b11, 4)),
+        SbiRet::success(rfnc::REMOTE_FENCE_I + 1)
+    );
+    assert_eq!(
+        sbi_rt::pmu_counter_start(
+            sbi_rt::CounterMask::from_mask_base(0b1, 3),
+            pmu::flags::StartFlags::INIT_VALUE,
+            0x1234
+        ),
+        SbiRet::success(pmu::COUNTER_START + 1)
+    );
+
+    let calls = calls.borrow();
+    assert_eq!(
+        calls[0],
+        (base::EID_BASE, base::GET_SBI_SPEC_VERSION, [0; 6])
+    );
+    assert_eq!(
+        calls[1],
+        (rfnc::EID_RFNC, rfnc::REMOTE_FENCE_I, [0b11, 4, 0, 0, 0, 0])
+    );
+    assert_eq!(calls[2].0, pmu::EID_PMU);
+    assert_eq!(calls[2].2[..3], [3, 0b1, 1]);
+
+    mock::clear_handler();
+    assert!(!mock::has_handler());
+}
+
+#[test]
+fn mock_scoped_handler() {
+    mock::set_handler(|_, _, _| SbiRet::success(1));
+    let info = mock::with_handler(
+        |_, _, _| SbiRet::success(0),
+        || sbi_rt::probe_extension(sbi_rt::Hsm),
+    );
+    assert!(info.is_unavailable());
+    // the previous handler is restored
+    assert!(sbi_rt::probe_extension(sbi_rt::Hsm).is_available());
+    mock::clear_handler();
+}
+
+#[test]
+#[should_panic = "no mock SBI handler installed"]
+fn mock_without_handler() {
+    sbi_rt::get_spec_version();
+}
+
+#[derive(RustSBI)]
+struct MockSBI {
+    console: BufferConsole,
+    info: MockEnvInfo,
+}
+
+#[test]
+fn mock_rustsbi_handler() {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let sbi = MockSBI {
+        console: BufferConsole(output.clone()),
+        info: MockEnvInfo,
+    };
+    mock::with_handler(
+        move |extension, function, param| sbi.handle_ecall(extension, function, param),
+        || {
+            assert_eq!(sbi_rt::get_spec_version().major(), 2);
+            assert_eq!(sbi_rt::get_mvendorid(), 36);
+            assert!(sbi_rt::probe_extension(sbi_rt::Console).is_available());
+            assert!(sbi_rt::probe_extension(sbi_rt::Timer).is_unavailable());
+            assert_eq!(sbi_rt::console_write_byte(b'A'), SbiRet::success(0));
+            assert_eq!(
+                sbi_rt::set_timer(0x1000),
+                SbiRet::not_supported(),
+                "Timer extension is not implemented"
+            );
+        },
+    );
+    assert_eq!(*output.lock().unwrap(), b"A");
+}
+
+struct BufferConsole(Arc<Mutex<Vec<u8>>>);
+
+impl Console for BufferConsole {
+    fn write(&self, _: Physical<&[u8]>) -> SbiRet {
+        SbiRet::not_supported()
+    }
+    fn read(&self, _: Physical<&mut [u8]>) -> SbiRet {
+        SbiRet::not_supported()
+    }
+    fn write_byte(&self, byte: u8) -> SbiRet {
+        self.0.lock().unwrap().push(byte);
+        SbiRet::success(0)
+    }
+}
+
+struct MockEnvInfo;
+
+impl EnvInfo for MockEnvInfo {
+    fn mvendorid(&self) -> usize {
+        36
+    }
+    fn marchid(&self) -> usize {
+        37
+    }
+    fn mimpid(&self) -> usize {
+        38
+    }
+}
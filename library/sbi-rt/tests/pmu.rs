@@ -0,0 +1,159 @@
+#![cfg(feature = "mock")]
+
+use sbi_rt::{Counter, CounterInfo, PmuEvent, SbiRet, SharedPtr, Snapshot, mock};
+use sbi_spec::{
+    binary::Error,
+    pmu::{self, EventInfo, firmware_event, flags::ConfigFlags},
+};
+use std::{cell::RefCell, rc::Rc};
+
+type Calls = Rc<RefCell<Vec<(usize, [usize; 6])>>>;
+
+const FW_COUNTER_INFO: usize = 1 << (usize::BITS - 1);
+
+fn install_mock(num_counters: usize, firmware_idx: usize) -> Calls {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorded = calls.clone();
+    mock::set_handler(move |extension, function, param| {
+        assert_eq!(extension, pmu::EID_PMU);
+        recorded.borrow_mut().push((function, param));
+        match function {
+            pmu::NUM_COUNTERS => SbiRet::success(num_counters),
+            pmu::COUNTER_GET_INFO if param[0] == firmware_idx => SbiRet::success(FW_COUNTER_INFO),
+            pmu::COUNTER_GET_INFO => SbiRet::success((63 << 12) | (0xC00 + param[0])),
+            // only the counter `firmware_idx` supports firmware events
+            pmu::COUNTER_CONFIG_MATCHING => {
+                let (base, mask) = (param[0], param[1]);
+                if param[3] >> 16 == pmu::event_type::FIRMWARE
+                    && (base..base + usize::BITS as usize).contains(&firmware_idx)
+                    && mask & (1 << (firmware_idx - base)) != 0
+                {
+                    SbiRet::success(firmware_idx)
+                } else {
+                    SbiRet::not_supported()
+                }
+            }
+            pmu::COUNTER_FW_READ => SbiRet::success(0x1234),
+            pmu::COUNTER_FW_READ_HI => SbiRet::success(0x1),
+            _ => SbiRet::success(0),
+        }
+    });
+    calls
+}
+
+#[test]
+fn pmu_counter_config_any() {
+    let calls = install_mock(usize::BITS as usize + 8, usize::BITS as usize + 3);
+    let event = PmuEvent::firmware(firmware_event::SET_TIMER);
+    let counter = Counter::config_any(event, ConfigFlags::CLEAR_VALUE).unwrap();
+    assert_eq!(counter.index(), usize::BITS as usize + 3);
+    assert_eq!(counter.info(), CounterInfo::Firmware);
+
+    let calls = calls.borrow();
+    let functions: Vec<usize> = calls.iter().map(|call| call.0).collect();
+    assert_eq!(
+        functions,
+        [
+            pmu::NUM_COUNTERS,
+            pmu::COUNTER_CONFIG_MATCHING,
+            pmu::COUNTER_CONFIG_MATCHING,
+            pmu::COUNTER_GET_INFO,
+        ]
+    );
+    // counters are searched in sets of XLEN counters
+    assert_eq!(calls[1].1[..4], [0, usize::MAX, 0b10, event.event_idx()]);
+    assert_eq!(calls[2].1[..2], [usize::BITS as usize, 0xFF]);
+    drop(calls);
+
+    let counter = Counter::config_any(PmuEvent::general(1), ConfigFlags::empty());
+    assert_eq!(counter, Err(Error::NotSupported));
+    mock::clear_handler();
+}
+
+#[test]
+fn pmu_counter_start_stop_read() {
+    let calls = install_mock(8, 5);
+    let counter = Counter::from_index(5).unwrap();
+    counter.start(Some(100)).unwrap();
+    counter.start(None).unwrap();
+    counter.stop().unwrap();
+    counter.stop_with_snapshot().unwrap();
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(counter.read(), Ok(0x1234));
+    #[cfg(target_pointer_width = "32")]
+    assert_eq!(counter.read(), Ok(0x1_0000_1234));
+    counter.release().unwrap();
+
+    let calls = calls.borrow();
+    assert_eq!(calls[1], (pmu::COUNTER_START, [5, 1, 0b1, 100, 0, 0]));
+    assert_eq!(calls[2], (pmu::COUNTER_START, [5, 1, 0, 0, 0, 0]));
+    assert_eq!(calls[3], (pmu::COUNTER_STOP, [5, 1, 0, 0, 0, 0]));
+    assert_eq!(calls[4], (pmu::COUNTER_STOP, [5, 1, 0b10, 0, 0, 0]));
+    assert_eq!(calls[5], (pmu::COUNTER_FW_READ, [5, 0, 0, 0, 0, 0]));
+    assert_eq!(
+        calls.last().unwrap(),
+        &(pmu::COUNTER_STOP, [5, 1, 0b1, 0, 0, 0])
+    );
+    drop(calls);
+
+    // hardware counters are read from CSRs, which is not available in mock environment
+    let counter = Counter::from_index(2).unwrap();
+    assert_eq!(
+        counter.info(),
+        CounterInfo::Hardware {
+            csr: 0xC02,
+            width: 64
+        }
+    );
+    assert_eq!(counter.read(), Err(Error::NotSupported));
+    mock::clear_handler();
+}
+
+#[test]
+fn pmu_snapshot_set_shmem() {
+    let calls = install_mock(8, 5);
+    let snapshot: &'static Snapshot = Box::leak(Box::new(Snapshot::new()));
+    unsafe { snapshot.set_shmem(0x1000) }.unwrap();
+    Snapshot::clear_shmem().unwrap();
+
+    let calls = calls.borrow();
+    let addr = snapshot as *const Snapshot as usize;
+    assert_eq!(
+        calls[0],
+        (pmu::SNAPSHOT_SET_SHMEM, [addr - 0x1000, 0, 0, 0, 0, 0])
+    );
+    assert_eq!(
+        calls[1],
+        (
+            pmu::SNAPSHOT_SET_SHMEM,
+            [usize::MAX, usize::MAX, 0, 0, 0, 0]
+        )
+    );
+    mock::clear_handler();
+}
+
+#[test]
+fn pmu_event_get_info() {
+    mock::set_handler(|extension, function, param| {
+        assert_eq!((extension, function), (pmu::EID_PMU, pmu::EVENT_GET_INFO));
+        assert_eq!(param[1..4], [0, 2, 0]);
+        let entries =
+            unsafe { std::slice::from_raw_parts_mut(param[0] as *mut EventInfo, param[2]) };
+        for entry in entries {
+            entry.output = (entry.event_idx >> 16 == pmu::event_type::FIRMWARE as u32) as u32;
+        }
+        SbiRet::success(0)
+    });
+    let mut entries = [
+        EventInfo::new(PmuEvent::general(1).event_idx() as u32, 0),
+        EventInfo::new(
+            PmuEvent::firmware(firmware_event::SET_TIMER).event_idx() as u32,
+            0,
+        ),
+    ];
+    let shmem = SharedPtr::new(entries.as_mut_ptr() as usize, 0);
+    assert!(sbi_rt::pmu_event_get_info(shmem, entries.len(), 0).is_ok());
+    assert!(!entries[0].is_supported());
+    assert!(entries[1].is_supported());
+    mock::clear_handler();
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "mock")]
+
+use sbi_rt::{SbiRet, SseContext, SseEvent, SseRegs, mock};
+use sbi_spec::sse::{self, attr_id, event_id, flags::InterruptedFlags};
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Default)]
+struct MockSse {
+    calls: Vec<(usize, u32)>,
+    entry: Option<(usize, usize)>,
+    interrupted: [usize; 4],
+}
+
+fn install_mock() -> Rc<RefCell<MockSse>> {
+    let state = Rc::new(RefCell::new(MockSse {
+        interrupted: [0x8020_1000, 0b11, 0x66, 0x77],
+        ..Default::default()
+    }));
+    let mock_state = state.clone();
+    mock::set_handler(move |extension, function, param| {
+        assert_eq!(extension, sse::EID_SSE);
+        let mut state = mock_state.borrow_mut();
+        state.calls.push((function, param[0] as u32));
+        match function {
+            sse::REGISTER => state.entry = Some((param[1], param[2])),
+            sse::READ_ATTRS | sse::WRITE_ATTRS => {
+                assert_eq!((param[1] as u32, param[2]), (attr_id::INTERRUPTED_SEPC, 4));
+                let attrs = param[3] as *mut [usize; 4];
+                if function == sse::READ_ATTRS {
+                    unsafe { attrs.write(state.interrupted) };
+                } else {
+                    state.interrupted = unsafe { attrs.read() };
+                }
+            }
+            _ => {}
+        }
+        SbiRet::success(0)
+    });
+    state
+}
+
+fn on_event(ctx: &mut SseContext) {
+    assert_eq!(ctx.event_id(), event_id::LOCAL_SOFTWARE_INJECTED);
+    assert_eq!(ctx.hart_id(), 3);
+    let mut interrupted = ctx.interrupted().unwrap();
+    assert_eq!(interrupted.sepc, 0x8020_1000);
+    assert!(interrupted.flags == InterruptedFlags::SSTATUS_SPP | InterruptedFlags::SSTATUS_SPIE);
+    assert_eq!((interrupted.a6, interrupted.a7), (0x66, 0x77));
+    // skip the interrupted instruction and return a value in `a0`
+    interrupted.sepc += 4;
+    ctx.set_interrupted(&interrupted).unwrap();
+    ctx.regs_mut().a0 = ctx.regs().a1 + 1;
+}
+
+#[test]
+fn sse_register_handle_and_drop() {
+    let state = install_mock();
+    let event = Box::leak(Box::new(SseEvent::<1024>::new()));
+    let mut registration = event
+        .register(event_id::LOCAL_SOFTWARE_INJECTED, on_event)
+        .map_err(|(_, error)| error)
+        .unwrap();
+    registration.enable().unwrap();
+    registration.inject(3).unwrap();
+
+    let (entry_pc, entry_arg) = state.borrow().entry.unwrap();
+    assert_ne!(entry_pc, 0);
+    let mut regs = SseRegs {
+        a1: 41,
+        ..Default::default()
+    };
+    let ret = unsafe { mock::deliver_sse(entry_arg, 3, &mut regs) };
+    assert_eq!(ret, SbiRet::success(0));
+    assert_eq!(regs.a0, 42);
+    assert_eq!(state.borrow().interrupted[0], 0x8020_1004);
+
+    drop(registration);
+    let id = event_id::LOCAL_SOFTWARE_INJECTED;
+    assert_eq!(
+        state.borrow().calls,
+        [
+            (sse::REGISTER, id),
+            (sse::ENABLE, id),
+            (sse::INJECT, id),
+            (sse::READ_ATTRS, id),
+            (sse::WRITE_ATTRS, id),
+            (sse::COMPLETE, 0),
+            (sse::DISABLE, id),
+            (sse::UNREGISTER, id),
+        ]
+    );
+    mock::clear_handler();
+}
+
+#[test]
+fn sse_unregister_returns_storage() {
+    let state = install_mock();
+    let event = Box::leak(Box::new(SseEvent::<1024>::new()));
+    let registration = event
+        .register(event_id::LOCAL_PMU_OVERFLOW, |_| {})
+        .map_err(|(_, error)| error)
+        .unwrap();
+    let event = registration
+        .unregister()
+        .map_err(|(_, error)| error)
+        .unwrap();
+    // event storage is reused for another registration
+    let registration = event
+        .register(event_id::GLOBAL_SOFTWARE_INJECTED, |_| {})
+        .map_err(|(_, error)| error)
+        .unwrap();
+    assert_eq!(registration.event_id(), event_id::GLOBAL_SOFTWARE_INJECTED);
+    core::mem::forget(registration);
+    let calls: Vec<usize> = state.borrow().calls.iter().map(|call| call.0).collect();
+    assert_eq!(calls, [sse::REGISTER, sse::UNREGISTER, sse::REGISTER]);
+    mock::clear_handler();
+}
+
+#[test]
+fn sse_register_failure() {
+    mock::set_handler(|_, _, _| SbiRet::not_supported());
+    let event = Box::leak(Box::new(SseEvent::<1024>::new()));
+    let (_, error) = event
+        .register(event_id::LOCAL_DOUBLE_TRAP, |_| {})
+        .err()
+        .unwrap();
+    assert_eq!(error, sbi_spec::binary::Error::NotSupported);
+    mock::clear_handler();
+}
@@ -3,6 +3,7 @@ use super::{mask_commons::has_bit, sbi_ret::SbiRegister};
 /// Counter index mask structure in SBI function calls for the `PMU` extension §11.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CounterMask<T = usize> {
     counter_idx_mask: T,
     counter_idx_base: T,
@@ -92,4 +93,13 @@ mod tests {
         assert_eq!(CounterMask::<u64>::IGNORE_MASK, u64::MAX);
         assert_eq!(CounterMask::<u128>::IGNORE_MASK, u128::MAX);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn rustsbi_counter_mask_serde() {
+        let mask = CounterMask::from_mask_base(0b11, 3);
+        let json = serde_json::to_string(&mask).unwrap();
+        assert_eq!(json, r#"{"counter_idx_mask":3,"counter_idx_base":3}"#);
+        assert_eq!(serde_json::from_str::<CounterMask>(&json).unwrap(), mask);
+    }
 }
@@ -6,6 +6,7 @@ use super::{
 /// Hart mask structure in SBI function calls.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HartMask<T = usize> {
     hart_mask: T,
     hart_mask_base: T,
@@ -433,7 +434,7 @@ mod tests {
 
         // Special iterator values.
         let nothing = HartMask::from_mask_base(0, 1000);
-        assert!(nothing.iter().eq([]));
+        assert!(nothing.iter().eq([0usize; 0]));
 
         let all_mask_bits_set = HartMask::from_mask_base(usize::MAX, 1000);
         let range = 1000..(1000 + usize::BITS as usize);
@@ -522,4 +523,16 @@ mod tests {
 
         assert_eq!(HartMask::<i32>::all(), HartMask::from_mask_base(0, -1));
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn rustsbi_hart_mask_serde() {
+        let mask = HartMask::from_mask_base(0b101, 4);
+        let json = serde_json::to_string(&mask).unwrap();
+        assert_eq!(json, r#"{"hart_mask":5,"hart_mask_base":4}"#);
+        assert_eq!(serde_json::from_str::<HartMask>(&json).unwrap(), mask);
+        let mask = HartMask::<u32>::from_mask_base(0, HartMask::<u32>::IGNORE_MASK);
+        let json = serde_json::to_string(&mask).unwrap();
+        assert_eq!(serde_json::from_str::<HartMask<u32>>(&json).unwrap(), mask);
+    }
 }
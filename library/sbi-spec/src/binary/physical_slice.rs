@@ -16,10 +16,13 @@ use core::marker::PhantomData;
 /// should provide continuous physical memory, wrapping its reference using this structure
 /// before passing into SBI runtime.
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct Physical<P> {
     num_bytes: usize,
     phys_addr_lo: usize,
     phys_addr_hi: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _marker: PhantomData<P>,
 }
 
@@ -53,3 +56,22 @@ impl<P> Physical<P> {
         self.phys_addr_hi
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustsbi_physical_serde() {
+        let slice = Physical::<&mut [u8]>::new(64, 0x8020_0000, 0x1);
+        let json = serde_json::to_string(&slice).unwrap();
+        assert_eq!(
+            json,
+            r#"{"num_bytes":64,"phys_addr_lo":2149580800,"phys_addr_hi":1}"#
+        );
+        let slice = serde_json::from_str::<Physical<&mut [u8]>>(&json).unwrap();
+        assert_eq!(slice.num_bytes(), 64);
+        assert_eq!(slice.phys_addr_lo(), 0x8020_0000);
+        assert_eq!(slice.phys_addr_hi(), 0x1);
+    }
+}
@@ -9,6 +9,7 @@
 /// extern C. The `repr(C)` is set in case that some users want to use
 /// this structure in FFI code.
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct SbiRet<T = usize> {
     /// Error number.
@@ -206,6 +207,7 @@ impl<T: SbiRegister + core::fmt::LowerHex> core::fmt::Debug for SbiRet<T> {
 
 /// RISC-V SBI error in enumeration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error<T = usize> {
     /// Error for SBI call failed for unknown reasons.
     Failed,
@@ -1143,4 +1145,29 @@ mod tests {
         assert_eq!(SbiRet::io(), SbiRet { value: 0, error: usize::MAX - 13 + 1 });
         assert_eq!(SbiRet::denied_locked(), SbiRet { value: 0, error: usize::MAX - 14 + 1 });
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn rustsbi_sbi_ret_serde() {
+        let ret = SbiRet::success(0x1037);
+        let json = serde_json::to_string(&ret).unwrap();
+        assert_eq!(json, r#"{"error":0,"value":4151}"#);
+        assert_eq!(serde_json::from_str::<SbiRet>(&json).unwrap(), ret);
+        let ret = SbiRet::<u64>::not_supported();
+        let json = serde_json::to_string(&ret).unwrap();
+        assert_eq!(serde_json::from_str::<SbiRet<u64>>(&json).unwrap(), ret);
+
+        for error in [Error::Failed, Error::DeniedLocked, Error::Custom(0x1234)] {
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(serde_json::from_str::<Error>(&json).unwrap(), error);
+        }
+        assert_eq!(
+            serde_json::to_string(&Error::<usize>::InvalidParam).unwrap(),
+            r#""InvalidParam""#
+        );
+        assert_eq!(
+            serde_json::to_string(&Error::Custom(-1_i64)).unwrap(),
+            r#"{"Custom":-1}"#
+        );
+    }
 }
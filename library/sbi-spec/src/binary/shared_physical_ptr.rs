@@ -49,9 +49,12 @@ use core::marker::PhantomData;
 /// which have memory physical addresses wider than `XLEN` bits.
 // FIXME: should constrain with `T: Thin` once ptr_metadata feature is stabled;
 // RISC-V SBI does not provide an approach to pass pointer metadata by SBI calls.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct SharedPtr<T> {
     phys_addr_lo: usize,
     phys_addr_hi: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _marker: PhantomData<*mut T>,
 }
 
@@ -89,3 +92,18 @@ impl<T> Clone for SharedPtr<T> {
 }
 
 impl<T> Copy for SharedPtr<T> {}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustsbi_shared_ptr_serde() {
+        let ptr = SharedPtr::<[u64; 8]>::new(0x8800_0000, 0);
+        let json = serde_json::to_string(&ptr).unwrap();
+        assert_eq!(json, r#"{"phys_addr_lo":2281701376,"phys_addr_hi":0}"#);
+        let ptr = serde_json::from_str::<SharedPtr<[u64; 8]>>(&json).unwrap();
+        assert_eq!(ptr.phys_addr_lo(), 0x8800_0000);
+        assert_eq!(ptr.phys_addr_hi(), 0);
+    }
+}
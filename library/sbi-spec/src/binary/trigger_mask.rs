@@ -3,6 +3,7 @@ use super::sbi_ret::SbiRegister;
 /// Debug trigger mask structure for the `DBTR` extension §19.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TriggerMask<T = usize> {
     trig_idx_base: T,
     trig_idx_mask: T,
@@ -27,3 +28,16 @@ impl<T: SbiRegister> TriggerMask<T> {
         (self.trig_idx_mask, self.trig_idx_base)
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustsbi_trigger_mask_serde() {
+        let mask = TriggerMask::from_mask_base(0b110, 8);
+        let json = serde_json::to_string(&mask).unwrap();
+        assert_eq!(json, r#"{"trig_idx_base":8,"trig_idx_mask":6}"#);
+        assert_eq!(serde_json::from_str::<TriggerMask>(&json).unwrap(), mask);
+    }
+}
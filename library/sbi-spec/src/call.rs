@@ -0,0 +1,2798 @@
+//! Typed SBI calls.
+//!
+//! An SBI call is made of an extension ID in register `a7`, a function ID in register `a6`,
+//! and parameters in registers `a0` to `a5`. This module decodes these registers into
+//! [`SbiCall`], a typed representation of every function defined in this crate, and encodes
+//! them back. Emulators, hypervisors and tracing tools may match on [`SbiCall`] instead of
+//! raw extension and function ID constants.
+//!
+//! # Examples
+//!
+//! ```
+//! use sbi_spec::{
+//!     binary::HartMask,
+//!     call::{Hsm, SbiCall, Spi},
+//! };
+//!
+//! let call = SbiCall::decode(0x48534D, 0, [1, 0x8020_0000, 42, 0, 0, 0]);
+//! assert_eq!(
+//!     call,
+//!     SbiCall::Hsm(Hsm::HartStart { hartid: 1, start_addr: 0x8020_0000, opaque: 42 })
+//! );
+//! assert_eq!(call.to_string(), "sbi_hart_start(hartid: 0x1, start_addr: 0x80200000, opaque: 0x2a)");
+//!
+//! let call = SbiCall::Spi(Spi::SendIpi { hart_mask: HartMask::from_mask_base(0b11, 0) });
+//! assert_eq!(call.encode(), (0x735049, 0, [0b11, 0, 0, 0, 0, 0]));
+//! ```
+
+#[cfg(feature = "legacy")]
+use crate::legacy;
+use crate::{
+    base,
+    binary::{CounterMask, HartMask, TriggerMask},
+    cppc, dbcn, dbtr, fwft, hsm, mpxy, nacl, pmu, rfnc, spi, srst, sse, sta, susp, time,
+};
+use core::fmt;
+
+/// Narrow a 32-bit parameter, returning `None` from the enclosing decoder if the
+/// register holds bits above the lower 32 bits.
+macro_rules! u32_param {
+    ($value: expr) => {{
+        let value: usize = $value;
+        if value as u32 as usize != value {
+            return None;
+        }
+        value as u32
+    }};
+}
+
+/// A typed SBI call.
+///
+/// Calls are decoded from the extension ID, function ID and parameters of an environment
+/// call using [`SbiCall::decode`], and encoded back using [`SbiCall::encode`]. The `Display`
+/// implementation prints the call in the form of `sbi_hart_start(hartid: 0x1, ...)` for logging.
+///
+/// Parameters defined as 32-bit values by the RISC-V SBI specification are truncated when
+/// decoded, and 64-bit values are assembled from two registers on RV32.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SbiCall {
+    /// Call to one of the Legacy extensions.
+    #[cfg(feature = "legacy")]
+    Legacy(Legacy),
+    /// Call to the Base extension.
+    Base(Base),
+    /// Call to the Timer extension.
+    Time(Time),
+    /// Call to the S-mode IPI extension.
+    Spi(Spi),
+    /// Call to the Remote Fence extension.
+    Rfnc(Rfnc),
+    /// Call to the Hart State Management extension.
+    Hsm(Hsm),
+    /// Call to the System Reset extension.
+    Srst(Srst),
+    /// Call to the Performance Monitoring Unit extension.
+    Pmu(Pmu),
+    /// Call to the Debug Console extension.
+    Dbcn(Dbcn),
+    /// Call to the System Suspend extension.
+    Susp(Susp),
+    /// Call to the CPPC extension.
+    Cppc(Cppc),
+    /// Call to the Nested Acceleration extension.
+    Nacl(Nacl),
+    /// Call to the Steal-time Accounting extension.
+    Sta(Sta),
+    /// Call to the Supervisor Software Events extension.
+    Sse(Sse),
+    /// Call to the Firmware Features extension.
+    Fwft(Fwft),
+    /// Call to the Debug Triggers extension.
+    Dbtr(Dbtr),
+    /// Call to the Message Proxy extension.
+    Mpxy(Mpxy),
+    /// Call to an extension or function not defined in this crate.
+    ///
+    /// This includes vendor-specific and firmware-specific extensions.
+    Unknown {
+        /// Extension ID in register `a7`.
+        extension: usize,
+        /// Function ID in register `a6`.
+        function: usize,
+        /// Parameters in registers `a0` to `a5`.
+        param: [usize; 6],
+    },
+}
+
+impl SbiCall {
+    /// Decode an SBI call from its extension ID, function ID and parameters.
+    #[inline]
+    pub const fn decode(extension: usize, function: usize, param: [usize; 6]) -> Self {
+        let unknown = Self::Unknown {
+            extension,
+            function,
+            param,
+        };
+        match extension {
+            #[cfg(feature = "legacy")]
+            0x00..=0x0F => match Legacy::decode(extension, param) {
+                Some(call) => Self::Legacy(call),
+                None => unknown,
+            },
+            base::EID_BASE => match Base::decode(function, param) {
+                Some(call) => Self::Base(call),
+                None => unknown,
+            },
+            time::EID_TIME => match Time::decode(function, param) {
+                Some(call) => Self::Time(call),
+                None => unknown,
+            },
+            spi::EID_SPI => match Spi::decode(function, param) {
+                Some(call) => Self::Spi(call),
+                None => unknown,
+            },
+            rfnc::EID_RFNC => match Rfnc::decode(function, param) {
+                Some(call) => Self::Rfnc(call),
+                None => unknown,
+            },
+            hsm::EID_HSM => match Hsm::decode(function, param) {
+                Some(call) => Self::Hsm(call),
+                None => unknown,
+            },
+            srst::EID_SRST => match Srst::decode(function, param) {
+                Some(call) => Self::Srst(call),
+                None => unknown,
+            },
+            pmu::EID_PMU => match Pmu::decode(function, param) {
+                Some(call) => Self::Pmu(call),
+                None => unknown,
+            },
+            dbcn::EID_DBCN => match Dbcn::decode(function, param) {
+                Some(call) => Self::Dbcn(call),
+                None => unknown,
+            },
+            susp::EID_SUSP => match Susp::decode(function, param) {
+                Some(call) => Self::Susp(call),
+                None => unknown,
+            },
+            cppc::EID_CPPC => match Cppc::decode(function, param) {
+                Some(call) => Self::Cppc(call),
+                None => unknown,
+            },
+            nacl::EID_NACL => match Nacl::decode(function, param) {
+                Some(call) => Self::Nacl(call),
+                None => unknown,
+            },
+            sta::EID_STA => match Sta::decode(function, param) {
+                Some(call) => Self::Sta(call),
+                None => unknown,
+            },
+            sse::EID_SSE => match Sse::decode(function, param) {
+                Some(call) => Self::Sse(call),
+                None => unknown,
+            },
+            fwft::EID_FWFT => match Fwft::decode(function, param) {
+                Some(call) => Self::Fwft(call),
+                None => unknown,
+            },
+            dbtr::EID_DBTR => match Dbtr::decode(function, param) {
+                Some(call) => Self::Dbtr(call),
+                None => unknown,
+            },
+            mpxy::EID_MPXY => match Mpxy::decode(function, param) {
+                Some(call) => Self::Mpxy(call),
+                None => unknown,
+            },
+            _ => unknown,
+        }
+    }
+    /// Encode this SBI call into its extension ID, function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, usize, [usize; 6]) {
+        match *self {
+            #[cfg(feature = "legacy")]
+            Self::Legacy(call) => {
+                let (extension, param) = call.encode();
+                (extension, 0, param)
+            }
+            Self::Base(call) => {
+                let (function, param) = call.encode();
+                (base::EID_BASE, function, param)
+            }
+            Self::Time(call) => {
+                let (function, param) = call.encode();
+                (time::EID_TIME, function, param)
+            }
+            Self::Spi(call) => {
+                let (function, param) = call.encode();
+                (spi::EID_SPI, function, param)
+            }
+            Self::Rfnc(call) => {
+                let (function, param) = call.encode();
+                (rfnc::EID_RFNC, function, param)
+            }
+            Self::Hsm(call) => {
+                let (function, param) = call.encode();
+                (hsm::EID_HSM, function, param)
+            }
+            Self::Srst(call) => {
+                let (function, param) = call.encode();
+                (srst::EID_SRST, function, param)
+            }
+            Self::Pmu(call) => {
+                let (function, param) = call.encode();
+                (pmu::EID_PMU, function, param)
+            }
+            Self::Dbcn(call) => {
+                let (function, param) = call.encode();
+                (dbcn::EID_DBCN, function, param)
+            }
+            Self::Susp(call) => {
+                let (function, param) = call.encode();
+                (susp::EID_SUSP, function, param)
+            }
+            Self::Cppc(call) => {
+                let (function, param) = call.encode();
+                (cppc::EID_CPPC, function, param)
+            }
+            Self::Nacl(call) => {
+                let (function, param) = call.encode();
+                (nacl::EID_NACL, function, param)
+            }
+            Self::Sta(call) => {
+                let (function, param) = call.encode();
+                (sta::EID_STA, function, param)
+            }
+            Self::Sse(call) => {
+                let (function, param) = call.encode();
+                (sse::EID_SSE, function, param)
+            }
+            Self::Fwft(call) => {
+                let (function, param) = call.encode();
+                (fwft::EID_FWFT, function, param)
+            }
+            Self::Dbtr(call) => {
+                let (function, param) = call.encode();
+                (dbtr::EID_DBTR, function, param)
+            }
+            Self::Mpxy(call) => {
+                let (function, param) = call.encode();
+                (mpxy::EID_MPXY, function, param)
+            }
+            Self::Unknown {
+                extension,
+                function,
+                param,
+            } => (extension, function, param),
+        }
+    }
+}
+
+impl fmt::Display for SbiCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "legacy")]
+            Self::Legacy(call) => call.fmt(f),
+            Self::Base(call) => call.fmt(f),
+            Self::Time(call) => call.fmt(f),
+            Self::Spi(call) => call.fmt(f),
+            Self::Rfnc(call) => call.fmt(f),
+            Self::Hsm(call) => call.fmt(f),
+            Self::Srst(call) => call.fmt(f),
+            Self::Pmu(call) => call.fmt(f),
+            Self::Dbcn(call) => call.fmt(f),
+            Self::Susp(call) => call.fmt(f),
+            Self::Cppc(call) => call.fmt(f),
+            Self::Nacl(call) => call.fmt(f),
+            Self::Sta(call) => call.fmt(f),
+            Self::Sse(call) => call.fmt(f),
+            Self::Fwft(call) => call.fmt(f),
+            Self::Dbtr(call) => call.fmt(f),
+            Self::Mpxy(call) => call.fmt(f),
+            Self::Unknown {
+                extension,
+                function,
+                param,
+            } => {
+                let [a0, a1, a2, a3, a4, a5] = param;
+                write!(
+                    f,
+                    "sbi_call(extension: {extension:#x}, function: {function:#x}, \
+                    param: [{a0:#x}, {a1:#x}, {a2:#x}, {a3:#x}, {a4:#x}, {a5:#x}])"
+                )
+            }
+        }
+    }
+}
+
+/// Calls to the Base extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base {
+    /// `sbi_get_spec_version`: get the SBI specification version.
+    GetSpecVersion,
+    /// `sbi_get_impl_id`: get the SBI implementation ID.
+    GetImplId,
+    /// `sbi_get_impl_version`: get the SBI implementation version.
+    GetImplVersion,
+    /// `sbi_probe_extension`: probe availability of an SBI extension.
+    ProbeExtension {
+        /// Extension ID to probe.
+        extension_id: usize,
+    },
+    /// `sbi_get_mvendorid`: get the machine vendor ID.
+    GetMvendorid,
+    /// `sbi_get_marchid`: get the machine architecture ID.
+    GetMarchid,
+    /// `sbi_get_mimpid`: get the machine implementation ID.
+    GetMimpid,
+}
+
+impl Base {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            base::GET_SBI_SPEC_VERSION => Self::GetSpecVersion,
+            base::GET_SBI_IMPL_ID => Self::GetImplId,
+            base::GET_SBI_IMPL_VERSION => Self::GetImplVersion,
+            base::PROBE_EXTENSION => Self::ProbeExtension {
+                extension_id: param[0],
+            },
+            base::GET_MVENDORID => Self::GetMvendorid,
+            base::GET_MARCHID => Self::GetMarchid,
+            base::GET_MIMPID => Self::GetMimpid,
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::GetSpecVersion => (base::GET_SBI_SPEC_VERSION, [0, 0, 0, 0, 0, 0]),
+            Self::GetImplId => (base::GET_SBI_IMPL_ID, [0, 0, 0, 0, 0, 0]),
+            Self::GetImplVersion => (base::GET_SBI_IMPL_VERSION, [0, 0, 0, 0, 0, 0]),
+            Self::ProbeExtension { extension_id } => {
+                (base::PROBE_EXTENSION, [extension_id, 0, 0, 0, 0, 0])
+            }
+            Self::GetMvendorid => (base::GET_MVENDORID, [0, 0, 0, 0, 0, 0]),
+            Self::GetMarchid => (base::GET_MARCHID, [0, 0, 0, 0, 0, 0]),
+            Self::GetMimpid => (base::GET_MIMPID, [0, 0, 0, 0, 0, 0]),
+        }
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::GetSpecVersion => write!(f, "sbi_get_spec_version()"),
+            Self::GetImplId => write!(f, "sbi_get_impl_id()"),
+            Self::GetImplVersion => write!(f, "sbi_get_impl_version()"),
+            Self::ProbeExtension { extension_id } => {
+                write!(f, "sbi_probe_extension(extension_id: {extension_id:#x})")
+            }
+            Self::GetMvendorid => write!(f, "sbi_get_mvendorid()"),
+            Self::GetMarchid => write!(f, "sbi_get_marchid()"),
+            Self::GetMimpid => write!(f, "sbi_get_mimpid()"),
+        }
+    }
+}
+
+/// Calls to the Timer extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Time {
+    /// `sbi_set_timer`: program the clock for the next event.
+    SetTimer {
+        /// Absolute time of the next timer event.
+        stime_value: u64,
+    },
+}
+
+impl Time {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            time::SET_TIMER => Self::SetTimer {
+                stime_value: join_u64(param[0], param[1]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::SetTimer { stime_value } => {
+                let (stime_value_lo, stime_value_hi) = split_u64(stime_value);
+                (
+                    time::SET_TIMER,
+                    [stime_value_lo, stime_value_hi, 0, 0, 0, 0],
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SetTimer { stime_value } => {
+                write!(f, "sbi_set_timer(stime_value: {stime_value:#x})")
+            }
+        }
+    }
+}
+
+/// Calls to the S-mode IPI extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Spi {
+    /// `sbi_send_ipi`: send an inter-processor interrupt to harts.
+    SendIpi {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+    },
+}
+
+impl Spi {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            spi::SEND_IPI => Self::SendIpi {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::SendIpi { hart_mask } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (spi::SEND_IPI, [hart_mask_mask, hart_mask_base, 0, 0, 0, 0])
+            }
+        }
+    }
+}
+
+impl fmt::Display for Spi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SendIpi { hart_mask } => write!(
+                f,
+                "sbi_send_ipi(hart_mask: {:#x}, hart_mask_base: {:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+        }
+    }
+}
+
+/// Calls to the Remote Fence extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rfnc {
+    /// `sbi_remote_fence_i`: execute `FENCE.I` on remote harts.
+    RemoteFenceI {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+    },
+    /// `sbi_remote_sfence_vma`: execute `SFENCE.VMA` on remote harts.
+    RemoteSfenceVma {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+    },
+    /// `sbi_remote_sfence_vma_asid`: execute `SFENCE.VMA` with an ASID on remote harts.
+    RemoteSfenceVmaAsid {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+        /// Address space identifier.
+        asid: usize,
+    },
+    /// `sbi_remote_hfence_gvma_vmid`: execute `HFENCE.GVMA` with a VMID on remote harts.
+    RemoteHfenceGvmaVmid {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+        /// Virtual machine identifier.
+        vmid: usize,
+    },
+    /// `sbi_remote_hfence_gvma`: execute `HFENCE.GVMA` for all guests on remote harts.
+    RemoteHfenceGvma {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+    },
+    /// `sbi_remote_hfence_vvma_asid`: execute `HFENCE.VVMA` with an ASID on remote harts.
+    RemoteHfenceVvmaAsid {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+        /// Address space identifier.
+        asid: usize,
+    },
+    /// `sbi_remote_hfence_vvma`: execute `HFENCE.VVMA` on remote harts.
+    RemoteHfenceVvma {
+        /// Harts the request applies to.
+        hart_mask: HartMask,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+    },
+}
+
+impl Rfnc {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            rfnc::REMOTE_FENCE_I => Self::RemoteFenceI {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+            },
+            rfnc::REMOTE_SFENCE_VMA => Self::RemoteSfenceVma {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+                start_addr: param[2],
+                size: param[3],
+            },
+            rfnc::REMOTE_SFENCE_VMA_ASID => Self::RemoteSfenceVmaAsid {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+                start_addr: param[2],
+                size: param[3],
+                asid: param[4],
+            },
+            rfnc::REMOTE_HFENCE_GVMA_VMID => Self::RemoteHfenceGvmaVmid {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+                start_addr: param[2],
+                size: param[3],
+                vmid: param[4],
+            },
+            rfnc::REMOTE_HFENCE_GVMA => Self::RemoteHfenceGvma {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+                start_addr: param[2],
+                size: param[3],
+            },
+            rfnc::REMOTE_HFENCE_VVMA_ASID => Self::RemoteHfenceVvmaAsid {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+                start_addr: param[2],
+                size: param[3],
+                asid: param[4],
+            },
+            rfnc::REMOTE_HFENCE_VVMA => Self::RemoteHfenceVvma {
+                hart_mask: HartMask::from_mask_base(param[0], param[1]),
+                start_addr: param[2],
+                size: param[3],
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::RemoteFenceI { hart_mask } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_FENCE_I,
+                    [hart_mask_mask, hart_mask_base, 0, 0, 0, 0],
+                )
+            }
+            Self::RemoteSfenceVma {
+                hart_mask,
+                start_addr,
+                size,
+            } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_SFENCE_VMA,
+                    [hart_mask_mask, hart_mask_base, start_addr, size, 0, 0],
+                )
+            }
+            Self::RemoteSfenceVmaAsid {
+                hart_mask,
+                start_addr,
+                size,
+                asid,
+            } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_SFENCE_VMA_ASID,
+                    [hart_mask_mask, hart_mask_base, start_addr, size, asid, 0],
+                )
+            }
+            Self::RemoteHfenceGvmaVmid {
+                hart_mask,
+                start_addr,
+                size,
+                vmid,
+            } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_HFENCE_GVMA_VMID,
+                    [hart_mask_mask, hart_mask_base, start_addr, size, vmid, 0],
+                )
+            }
+            Self::RemoteHfenceGvma {
+                hart_mask,
+                start_addr,
+                size,
+            } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_HFENCE_GVMA,
+                    [hart_mask_mask, hart_mask_base, start_addr, size, 0, 0],
+                )
+            }
+            Self::RemoteHfenceVvmaAsid {
+                hart_mask,
+                start_addr,
+                size,
+                asid,
+            } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_HFENCE_VVMA_ASID,
+                    [hart_mask_mask, hart_mask_base, start_addr, size, asid, 0],
+                )
+            }
+            Self::RemoteHfenceVvma {
+                hart_mask,
+                start_addr,
+                size,
+            } => {
+                let (hart_mask_mask, hart_mask_base) = hart_mask.into_inner();
+                (
+                    rfnc::REMOTE_HFENCE_VVMA,
+                    [hart_mask_mask, hart_mask_base, start_addr, size, 0, 0],
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rfnc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::RemoteFenceI { hart_mask } => write!(
+                f,
+                "sbi_remote_fence_i(hart_mask: {:#x}, hart_mask_base: {:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+            Self::RemoteSfenceVma {
+                hart_mask,
+                start_addr,
+                size,
+            } => write!(
+                f,
+                "sbi_remote_sfence_vma(hart_mask: {:#x}, hart_mask_base: {:#x}, start_addr: {start_addr:#x}, size: {size:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+            Self::RemoteSfenceVmaAsid {
+                hart_mask,
+                start_addr,
+                size,
+                asid,
+            } => write!(
+                f,
+                "sbi_remote_sfence_vma_asid(hart_mask: {:#x}, hart_mask_base: {:#x}, start_addr: {start_addr:#x}, size: {size:#x}, asid: {asid:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+            Self::RemoteHfenceGvmaVmid {
+                hart_mask,
+                start_addr,
+                size,
+                vmid,
+            } => write!(
+                f,
+                "sbi_remote_hfence_gvma_vmid(hart_mask: {:#x}, hart_mask_base: {:#x}, start_addr: {start_addr:#x}, size: {size:#x}, vmid: {vmid:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+            Self::RemoteHfenceGvma {
+                hart_mask,
+                start_addr,
+                size,
+            } => write!(
+                f,
+                "sbi_remote_hfence_gvma(hart_mask: {:#x}, hart_mask_base: {:#x}, start_addr: {start_addr:#x}, size: {size:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+            Self::RemoteHfenceVvmaAsid {
+                hart_mask,
+                start_addr,
+                size,
+                asid,
+            } => write!(
+                f,
+                "sbi_remote_hfence_vvma_asid(hart_mask: {:#x}, hart_mask_base: {:#x}, start_addr: {start_addr:#x}, size: {size:#x}, asid: {asid:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+            Self::RemoteHfenceVvma {
+                hart_mask,
+                start_addr,
+                size,
+            } => write!(
+                f,
+                "sbi_remote_hfence_vvma(hart_mask: {:#x}, hart_mask_base: {:#x}, start_addr: {start_addr:#x}, size: {size:#x})",
+                hart_mask.into_inner().0,
+                hart_mask.into_inner().1
+            ),
+        }
+    }
+}
+
+/// Calls to the Hart State Management extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hsm {
+    /// `sbi_hart_start`: start a hart in supervisor mode.
+    HartStart {
+        /// Target hart ID.
+        hartid: usize,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Opaque value passed to the supervisor software in register `a1`.
+        opaque: usize,
+    },
+    /// `sbi_hart_stop`: stop the calling hart.
+    HartStop,
+    /// `sbi_hart_get_status`: get the state of a hart.
+    HartGetStatus {
+        /// Target hart ID.
+        hartid: usize,
+    },
+    /// `sbi_hart_suspend`: suspend the calling hart.
+    HartSuspend {
+        /// Suspend type.
+        suspend_type: u32,
+        /// Physical address the hart resumes at, for non-retentive suspend.
+        resume_addr: usize,
+        /// Opaque value passed to the supervisor software in register `a1`.
+        opaque: usize,
+    },
+}
+
+impl Hsm {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            hsm::HART_START => Self::HartStart {
+                hartid: param[0],
+                start_addr: param[1],
+                opaque: param[2],
+            },
+            hsm::HART_STOP => Self::HartStop,
+            hsm::HART_GET_STATUS => Self::HartGetStatus { hartid: param[0] },
+            hsm::HART_SUSPEND => Self::HartSuspend {
+                suspend_type: u32_param!(param[0]),
+                resume_addr: param[1],
+                opaque: param[2],
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::HartStart {
+                hartid,
+                start_addr,
+                opaque,
+            } => (hsm::HART_START, [hartid, start_addr, opaque, 0, 0, 0]),
+            Self::HartStop => (hsm::HART_STOP, [0, 0, 0, 0, 0, 0]),
+            Self::HartGetStatus { hartid } => (hsm::HART_GET_STATUS, [hartid, 0, 0, 0, 0, 0]),
+            Self::HartSuspend {
+                suspend_type,
+                resume_addr,
+                opaque,
+            } => (
+                hsm::HART_SUSPEND,
+                [suspend_type as usize, resume_addr, opaque, 0, 0, 0],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Hsm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::HartStart {
+                hartid,
+                start_addr,
+                opaque,
+            } => write!(
+                f,
+                "sbi_hart_start(hartid: {hartid:#x}, start_addr: {start_addr:#x}, opaque: {opaque:#x})"
+            ),
+            Self::HartStop => write!(f, "sbi_hart_stop()"),
+            Self::HartGetStatus { hartid } => write!(f, "sbi_hart_get_status(hartid: {hartid:#x})"),
+            Self::HartSuspend {
+                suspend_type,
+                resume_addr,
+                opaque,
+            } => write!(
+                f,
+                "sbi_hart_suspend(suspend_type: {suspend_type:#x}, resume_addr: {resume_addr:#x}, opaque: {opaque:#x})"
+            ),
+        }
+    }
+}
+
+/// Calls to the System Reset extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Srst {
+    /// `sbi_system_reset`: reset the system.
+    SystemReset {
+        /// Type of system reset.
+        reset_type: u32,
+        /// Reason of system reset.
+        reset_reason: u32,
+    },
+}
+
+impl Srst {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            srst::SYSTEM_RESET => Self::SystemReset {
+                reset_type: u32_param!(param[0]),
+                reset_reason: u32_param!(param[1]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::SystemReset {
+                reset_type,
+                reset_reason,
+            } => (
+                srst::SYSTEM_RESET,
+                [reset_type as usize, reset_reason as usize, 0, 0, 0, 0],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Srst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SystemReset {
+                reset_type,
+                reset_reason,
+            } => write!(
+                f,
+                "sbi_system_reset(reset_type: {reset_type:#x}, reset_reason: {reset_reason:#x})"
+            ),
+        }
+    }
+}
+
+/// Calls to the Performance Monitoring Unit extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pmu {
+    /// `sbi_pmu_num_counters`: get the number of counters.
+    NumCounters,
+    /// `sbi_pmu_counter_get_info`: get details of a counter.
+    CounterGetInfo {
+        /// Index of the counter.
+        counter_idx: usize,
+    },
+    /// `sbi_pmu_counter_config_matching`: find and configure a counter matching the given event.
+    CounterConfigMatching {
+        /// Counters the request applies to.
+        counters: CounterMask,
+        /// Filter and configuration flags.
+        config_flags: usize,
+        /// Index of the event to monitor.
+        event_idx: usize,
+        /// Additional data of the event.
+        event_data: u64,
+    },
+    /// `sbi_pmu_counter_start`: start counters.
+    CounterStart {
+        /// Counters the request applies to.
+        counters: CounterMask,
+        /// Counter start flags.
+        start_flags: usize,
+        /// Initial value of the counters.
+        initial_value: u64,
+    },
+    /// `sbi_pmu_counter_stop`: stop counters.
+    CounterStop {
+        /// Counters the request applies to.
+        counters: CounterMask,
+        /// Counter stop flags.
+        stop_flags: usize,
+    },
+    /// `sbi_pmu_counter_fw_read`: read a firmware counter.
+    CounterFwRead {
+        /// Index of the counter.
+        counter_idx: usize,
+    },
+    /// `sbi_pmu_counter_fw_read_hi`: read upper 32 bits of a firmware counter.
+    CounterFwReadHi {
+        /// Index of the counter.
+        counter_idx: usize,
+    },
+    /// `sbi_pmu_snapshot_set_shmem`: set the counter snapshot shared memory.
+    SnapshotSetShmem {
+        /// Lower XLEN bits of the shared memory physical base address.
+        shmem_phys_lo: usize,
+        /// Upper XLEN bits of the shared memory physical base address.
+        shmem_phys_hi: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+    /// `sbi_pmu_event_get_info`: get information of events.
+    EventGetInfo {
+        /// Lower XLEN bits of the shared memory physical base address.
+        shmem_phys_lo: usize,
+        /// Upper XLEN bits of the shared memory physical base address.
+        shmem_phys_hi: usize,
+        /// Number of entries in the shared memory.
+        num_entries: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+}
+
+impl Pmu {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            pmu::NUM_COUNTERS => Self::NumCounters,
+            pmu::COUNTER_GET_INFO => Self::CounterGetInfo {
+                counter_idx: param[0],
+            },
+            pmu::COUNTER_CONFIG_MATCHING => Self::CounterConfigMatching {
+                counters: CounterMask::from_mask_base(param[1], param[0]),
+                config_flags: param[2],
+                event_idx: param[3],
+                event_data: join_u64(param[4], param[5]),
+            },
+            pmu::COUNTER_START => Self::CounterStart {
+                counters: CounterMask::from_mask_base(param[1], param[0]),
+                start_flags: param[2],
+                initial_value: join_u64(param[3], param[4]),
+            },
+            pmu::COUNTER_STOP => Self::CounterStop {
+                counters: CounterMask::from_mask_base(param[1], param[0]),
+                stop_flags: param[2],
+            },
+            pmu::COUNTER_FW_READ => Self::CounterFwRead {
+                counter_idx: param[0],
+            },
+            pmu::COUNTER_FW_READ_HI => Self::CounterFwReadHi {
+                counter_idx: param[0],
+            },
+            pmu::SNAPSHOT_SET_SHMEM => Self::SnapshotSetShmem {
+                shmem_phys_lo: param[0],
+                shmem_phys_hi: param[1],
+                flags: param[2],
+            },
+            pmu::EVENT_GET_INFO => Self::EventGetInfo {
+                shmem_phys_lo: param[0],
+                shmem_phys_hi: param[1],
+                num_entries: param[2],
+                flags: param[3],
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::NumCounters => (pmu::NUM_COUNTERS, [0, 0, 0, 0, 0, 0]),
+            Self::CounterGetInfo { counter_idx } => {
+                (pmu::COUNTER_GET_INFO, [counter_idx, 0, 0, 0, 0, 0])
+            }
+            Self::CounterConfigMatching {
+                counters,
+                config_flags,
+                event_idx,
+                event_data,
+            } => {
+                let (counters_mask, counters_base) = counters.into_inner();
+                let (event_data_lo, event_data_hi) = split_u64(event_data);
+                (
+                    pmu::COUNTER_CONFIG_MATCHING,
+                    [
+                        counters_base,
+                        counters_mask,
+                        config_flags,
+                        event_idx,
+                        event_data_lo,
+                        event_data_hi,
+                    ],
+                )
+            }
+            Self::CounterStart {
+                counters,
+                start_flags,
+                initial_value,
+            } => {
+                let (counters_mask, counters_base) = counters.into_inner();
+                let (initial_value_lo, initial_value_hi) = split_u64(initial_value);
+                (
+                    pmu::COUNTER_START,
+                    [
+                        counters_base,
+                        counters_mask,
+                        start_flags,
+                        initial_value_lo,
+                        initial_value_hi,
+                        0,
+                    ],
+                )
+            }
+            Self::CounterStop {
+                counters,
+                stop_flags,
+            } => {
+                let (counters_mask, counters_base) = counters.into_inner();
+                (
+                    pmu::COUNTER_STOP,
+                    [counters_base, counters_mask, stop_flags, 0, 0, 0],
+                )
+            }
+            Self::CounterFwRead { counter_idx } => {
+                (pmu::COUNTER_FW_READ, [counter_idx, 0, 0, 0, 0, 0])
+            }
+            Self::CounterFwReadHi { counter_idx } => {
+                (pmu::COUNTER_FW_READ_HI, [counter_idx, 0, 0, 0, 0, 0])
+            }
+            Self::SnapshotSetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => (
+                pmu::SNAPSHOT_SET_SHMEM,
+                [shmem_phys_lo, shmem_phys_hi, flags, 0, 0, 0],
+            ),
+            Self::EventGetInfo {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                num_entries,
+                flags,
+            } => (
+                pmu::EVENT_GET_INFO,
+                [shmem_phys_lo, shmem_phys_hi, num_entries, flags, 0, 0],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Pmu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NumCounters => write!(f, "sbi_pmu_num_counters()"),
+            Self::CounterGetInfo { counter_idx } => {
+                write!(f, "sbi_pmu_counter_get_info(counter_idx: {counter_idx:#x})")
+            }
+            Self::CounterConfigMatching {
+                counters,
+                config_flags,
+                event_idx,
+                event_data,
+            } => write!(
+                f,
+                "sbi_pmu_counter_config_matching(counter_idx_mask: {:#x}, counter_idx_base: {:#x}, config_flags: {config_flags:#x}, event_idx: {event_idx:#x}, event_data: {event_data:#x})",
+                counters.into_inner().0,
+                counters.into_inner().1
+            ),
+            Self::CounterStart {
+                counters,
+                start_flags,
+                initial_value,
+            } => write!(
+                f,
+                "sbi_pmu_counter_start(counter_idx_mask: {:#x}, counter_idx_base: {:#x}, start_flags: {start_flags:#x}, initial_value: {initial_value:#x})",
+                counters.into_inner().0,
+                counters.into_inner().1
+            ),
+            Self::CounterStop {
+                counters,
+                stop_flags,
+            } => write!(
+                f,
+                "sbi_pmu_counter_stop(counter_idx_mask: {:#x}, counter_idx_base: {:#x}, stop_flags: {stop_flags:#x})",
+                counters.into_inner().0,
+                counters.into_inner().1
+            ),
+            Self::CounterFwRead { counter_idx } => {
+                write!(f, "sbi_pmu_counter_fw_read(counter_idx: {counter_idx:#x})")
+            }
+            Self::CounterFwReadHi { counter_idx } => write!(
+                f,
+                "sbi_pmu_counter_fw_read_hi(counter_idx: {counter_idx:#x})"
+            ),
+            Self::SnapshotSetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => write!(
+                f,
+                "sbi_pmu_snapshot_set_shmem(shmem_phys_lo: {shmem_phys_lo:#x}, shmem_phys_hi: {shmem_phys_hi:#x}, flags: {flags:#x})"
+            ),
+            Self::EventGetInfo {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                num_entries,
+                flags,
+            } => write!(
+                f,
+                "sbi_pmu_event_get_info(shmem_phys_lo: {shmem_phys_lo:#x}, shmem_phys_hi: {shmem_phys_hi:#x}, num_entries: {num_entries:#x}, flags: {flags:#x})"
+            ),
+        }
+    }
+}
+
+/// Calls to the Debug Console extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dbcn {
+    /// `sbi_debug_console_write`: write bytes to the debug console.
+    ConsoleWrite {
+        /// Number of bytes in the buffer.
+        num_bytes: usize,
+        /// Lower XLEN bits of the buffer physical base address.
+        base_addr_lo: usize,
+        /// Upper XLEN bits of the buffer physical base address.
+        base_addr_hi: usize,
+    },
+    /// `sbi_debug_console_read`: read bytes from the debug console.
+    ConsoleRead {
+        /// Number of bytes in the buffer.
+        num_bytes: usize,
+        /// Lower XLEN bits of the buffer physical base address.
+        base_addr_lo: usize,
+        /// Upper XLEN bits of the buffer physical base address.
+        base_addr_hi: usize,
+    },
+    /// `sbi_debug_console_write_byte`: write a byte to the debug console.
+    ConsoleWriteByte {
+        /// Byte to write.
+        byte: u8,
+    },
+}
+
+impl Dbcn {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            dbcn::CONSOLE_WRITE => Self::ConsoleWrite {
+                num_bytes: param[0],
+                base_addr_lo: param[1],
+                base_addr_hi: param[2],
+            },
+            dbcn::CONSOLE_READ => Self::ConsoleRead {
+                num_bytes: param[0],
+                base_addr_lo: param[1],
+                base_addr_hi: param[2],
+            },
+            dbcn::CONSOLE_WRITE_BYTE => Self::ConsoleWriteByte {
+                byte: param[0] as u8,
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::ConsoleWrite {
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+            } => (
+                dbcn::CONSOLE_WRITE,
+                [num_bytes, base_addr_lo, base_addr_hi, 0, 0, 0],
+            ),
+            Self::ConsoleRead {
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+            } => (
+                dbcn::CONSOLE_READ,
+                [num_bytes, base_addr_lo, base_addr_hi, 0, 0, 0],
+            ),
+            Self::ConsoleWriteByte { byte } => {
+                (dbcn::CONSOLE_WRITE_BYTE, [byte as usize, 0, 0, 0, 0, 0])
+            }
+        }
+    }
+}
+
+impl fmt::Display for Dbcn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ConsoleWrite {
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+            } => write!(
+                f,
+                "sbi_debug_console_write(num_bytes: {num_bytes:#x}, base_addr_lo: {base_addr_lo:#x}, base_addr_hi: {base_addr_hi:#x})"
+            ),
+            Self::ConsoleRead {
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+            } => write!(
+                f,
+                "sbi_debug_console_read(num_bytes: {num_bytes:#x}, base_addr_lo: {base_addr_lo:#x}, base_addr_hi: {base_addr_hi:#x})"
+            ),
+            Self::ConsoleWriteByte { byte } => {
+                write!(f, "sbi_debug_console_write_byte(byte: {byte:#x})")
+            }
+        }
+    }
+}
+
+/// Calls to the System Suspend extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Susp {
+    /// `sbi_system_suspend`: suspend the system.
+    Suspend {
+        /// Sleep type.
+        sleep_type: u32,
+        /// Physical address the hart resumes at, for non-retentive suspend.
+        resume_addr: usize,
+        /// Opaque value passed to the supervisor software in register `a1`.
+        opaque: usize,
+    },
+}
+
+impl Susp {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            susp::SUSPEND => Self::Suspend {
+                sleep_type: u32_param!(param[0]),
+                resume_addr: param[1],
+                opaque: param[2],
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::Suspend {
+                sleep_type,
+                resume_addr,
+                opaque,
+            } => (
+                susp::SUSPEND,
+                [sleep_type as usize, resume_addr, opaque, 0, 0, 0],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Susp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Suspend {
+                sleep_type,
+                resume_addr,
+                opaque,
+            } => write!(
+                f,
+                "sbi_system_suspend(sleep_type: {sleep_type:#x}, resume_addr: {resume_addr:#x}, opaque: {opaque:#x})"
+            ),
+        }
+    }
+}
+
+/// Calls to the CPPC extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cppc {
+    /// `sbi_cppc_probe`: probe a CPPC register.
+    Probe {
+        /// CPPC register ID.
+        reg_id: u32,
+    },
+    /// `sbi_cppc_read`: read a CPPC register.
+    Read {
+        /// CPPC register ID.
+        reg_id: u32,
+    },
+    /// `sbi_cppc_read_hi`: read upper 32 bits of a CPPC register.
+    ReadHi {
+        /// CPPC register ID.
+        reg_id: u32,
+    },
+    /// `sbi_cppc_write`: write a CPPC register.
+    Write {
+        /// CPPC register ID.
+        reg_id: u32,
+        /// Value to write.
+        value: u64,
+    },
+}
+
+impl Cppc {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            cppc::PROBE => Self::Probe {
+                reg_id: u32_param!(param[0]),
+            },
+            cppc::READ => Self::Read {
+                reg_id: u32_param!(param[0]),
+            },
+            cppc::READ_HI => Self::ReadHi {
+                reg_id: u32_param!(param[0]),
+            },
+            cppc::WRITE => Self::Write {
+                reg_id: u32_param!(param[0]),
+                value: join_u64(param[1], param[2]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::Probe { reg_id } => (cppc::PROBE, [reg_id as usize, 0, 0, 0, 0, 0]),
+            Self::Read { reg_id } => (cppc::READ, [reg_id as usize, 0, 0, 0, 0, 0]),
+            Self::ReadHi { reg_id } => (cppc::READ_HI, [reg_id as usize, 0, 0, 0, 0, 0]),
+            Self::Write { reg_id, value } => {
+                let (value_lo, value_hi) = split_u64(value);
+                (cppc::WRITE, [reg_id as usize, value_lo, value_hi, 0, 0, 0])
+            }
+        }
+    }
+}
+
+impl fmt::Display for Cppc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Probe { reg_id } => write!(f, "sbi_cppc_probe(reg_id: {reg_id:#x})"),
+            Self::Read { reg_id } => write!(f, "sbi_cppc_read(reg_id: {reg_id:#x})"),
+            Self::ReadHi { reg_id } => write!(f, "sbi_cppc_read_hi(reg_id: {reg_id:#x})"),
+            Self::Write { reg_id, value } => {
+                write!(f, "sbi_cppc_write(reg_id: {reg_id:#x}, value: {value:#x})")
+            }
+        }
+    }
+}
+
+/// Calls to the Nested Acceleration extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nacl {
+    /// `sbi_nacl_probe_feature`: probe a nested acceleration feature.
+    ProbeFeature {
+        /// Nested acceleration feature ID.
+        feature_id: u32,
+    },
+    /// `sbi_nacl_set_shmem`: set the nested acceleration shared memory.
+    SetShmem {
+        /// Lower XLEN bits of the shared memory physical base address.
+        shmem_phys_lo: usize,
+        /// Upper XLEN bits of the shared memory physical base address.
+        shmem_phys_hi: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+    /// `sbi_nacl_sync_csr`: synchronize CSRs in the shared memory.
+    SyncCsr {
+        /// CSR number to synchronize, or all-ones for all CSRs.
+        csr_num: usize,
+    },
+    /// `sbi_nacl_sync_hfence`: synchronize hfence entries in the shared memory.
+    SyncHfence {
+        /// Index of the hfence entry to synchronize, or all-ones for all entries.
+        entry_index: usize,
+    },
+    /// `sbi_nacl_sync_sret`: synchronize shared memory and emulate `SRET`.
+    SyncSret,
+}
+
+impl Nacl {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            nacl::PROBE_FEATURE => Self::ProbeFeature {
+                feature_id: u32_param!(param[0]),
+            },
+            nacl::SET_SHMEM => Self::SetShmem {
+                shmem_phys_lo: param[0],
+                shmem_phys_hi: param[1],
+                flags: param[2],
+            },
+            nacl::SYNC_CSR => Self::SyncCsr { csr_num: param[0] },
+            nacl::SYNC_HFENCE => Self::SyncHfence {
+                entry_index: param[0],
+            },
+            nacl::SYNC_SRET => Self::SyncSret,
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::ProbeFeature { feature_id } => {
+                (nacl::PROBE_FEATURE, [feature_id as usize, 0, 0, 0, 0, 0])
+            }
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => (
+                nacl::SET_SHMEM,
+                [shmem_phys_lo, shmem_phys_hi, flags, 0, 0, 0],
+            ),
+            Self::SyncCsr { csr_num } => (nacl::SYNC_CSR, [csr_num, 0, 0, 0, 0, 0]),
+            Self::SyncHfence { entry_index } => (nacl::SYNC_HFENCE, [entry_index, 0, 0, 0, 0, 0]),
+            Self::SyncSret => (nacl::SYNC_SRET, [0, 0, 0, 0, 0, 0]),
+        }
+    }
+}
+
+impl fmt::Display for Nacl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ProbeFeature { feature_id } => {
+                write!(f, "sbi_nacl_probe_feature(feature_id: {feature_id:#x})")
+            }
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => write!(
+                f,
+                "sbi_nacl_set_shmem(shmem_phys_lo: {shmem_phys_lo:#x}, shmem_phys_hi: {shmem_phys_hi:#x}, flags: {flags:#x})"
+            ),
+            Self::SyncCsr { csr_num } => write!(f, "sbi_nacl_sync_csr(csr_num: {csr_num:#x})"),
+            Self::SyncHfence { entry_index } => {
+                write!(f, "sbi_nacl_sync_hfence(entry_index: {entry_index:#x})")
+            }
+            Self::SyncSret => write!(f, "sbi_nacl_sync_sret()"),
+        }
+    }
+}
+
+/// Calls to the Steal-time Accounting extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sta {
+    /// `sbi_steal_time_set_shmem`: set the steal-time shared memory.
+    SetShmem {
+        /// Lower XLEN bits of the shared memory physical base address.
+        shmem_phys_lo: usize,
+        /// Upper XLEN bits of the shared memory physical base address.
+        shmem_phys_hi: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+}
+
+impl Sta {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            sta::SET_SHMEM => Self::SetShmem {
+                shmem_phys_lo: param[0],
+                shmem_phys_hi: param[1],
+                flags: param[2],
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => (
+                sta::SET_SHMEM,
+                [shmem_phys_lo, shmem_phys_hi, flags, 0, 0, 0],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Sta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => write!(
+                f,
+                "sbi_steal_time_set_shmem(shmem_phys_lo: {shmem_phys_lo:#x}, shmem_phys_hi: {shmem_phys_hi:#x}, flags: {flags:#x})"
+            ),
+        }
+    }
+}
+
+/// Calls to the Supervisor Software Events extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sse {
+    /// `sbi_sse_read_attrs`: read attributes of a software event.
+    ReadAttrs {
+        /// Software event ID.
+        event_id: u32,
+        /// ID of the first attribute.
+        base_attr_id: u32,
+        /// Number of attributes.
+        attr_count: usize,
+        /// Lower XLEN bits of the output buffer physical address.
+        output_phys_lo: usize,
+        /// Upper XLEN bits of the output buffer physical address.
+        output_phys_hi: usize,
+    },
+    /// `sbi_sse_write_attrs`: write attributes of a software event.
+    WriteAttrs {
+        /// Software event ID.
+        event_id: u32,
+        /// ID of the first attribute.
+        base_attr_id: u32,
+        /// Number of attributes.
+        attr_count: usize,
+        /// Lower XLEN bits of the input buffer physical address.
+        input_phys_lo: usize,
+        /// Upper XLEN bits of the input buffer physical address.
+        input_phys_hi: usize,
+    },
+    /// `sbi_sse_register`: register a software event handler.
+    Register {
+        /// Software event ID.
+        event_id: u32,
+        /// Entry address of the event handler.
+        handler_entry_pc: usize,
+        /// Argument of the event handler.
+        handler_entry_arg: usize,
+    },
+    /// `sbi_sse_unregister`: unregister a software event handler.
+    Unregister {
+        /// Software event ID.
+        event_id: u32,
+    },
+    /// `sbi_sse_enable`: enable a software event.
+    Enable {
+        /// Software event ID.
+        event_id: u32,
+    },
+    /// `sbi_sse_disable`: disable a software event.
+    Disable {
+        /// Software event ID.
+        event_id: u32,
+    },
+    /// `sbi_sse_complete`: complete handling of the current software event.
+    Complete,
+    /// `sbi_sse_inject`: inject a software event.
+    Inject {
+        /// Software event ID.
+        event_id: u32,
+        /// Target hart ID.
+        hartid: usize,
+    },
+    /// `sbi_sse_hart_unmask`: start receiving software events on the calling hart.
+    HartUnmask,
+    /// `sbi_sse_hart_mask`: stop receiving software events on the calling hart.
+    HartMask,
+}
+
+impl Sse {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            sse::READ_ATTRS => Self::ReadAttrs {
+                event_id: u32_param!(param[0]),
+                base_attr_id: u32_param!(param[1]),
+                attr_count: param[2],
+                output_phys_lo: param[3],
+                output_phys_hi: param[4],
+            },
+            sse::WRITE_ATTRS => Self::WriteAttrs {
+                event_id: u32_param!(param[0]),
+                base_attr_id: u32_param!(param[1]),
+                attr_count: param[2],
+                input_phys_lo: param[3],
+                input_phys_hi: param[4],
+            },
+            sse::REGISTER => Self::Register {
+                event_id: u32_param!(param[0]),
+                handler_entry_pc: param[1],
+                handler_entry_arg: param[2],
+            },
+            sse::UNREGISTER => Self::Unregister {
+                event_id: u32_param!(param[0]),
+            },
+            sse::ENABLE => Self::Enable {
+                event_id: u32_param!(param[0]),
+            },
+            sse::DISABLE => Self::Disable {
+                event_id: u32_param!(param[0]),
+            },
+            sse::COMPLETE => Self::Complete,
+            sse::INJECT => Self::Inject {
+                event_id: u32_param!(param[0]),
+                hartid: param[1],
+            },
+            sse::HART_UNMASK => Self::HartUnmask,
+            sse::HART_MASK => Self::HartMask,
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::ReadAttrs {
+                event_id,
+                base_attr_id,
+                attr_count,
+                output_phys_lo,
+                output_phys_hi,
+            } => (
+                sse::READ_ATTRS,
+                [
+                    event_id as usize,
+                    base_attr_id as usize,
+                    attr_count,
+                    output_phys_lo,
+                    output_phys_hi,
+                    0,
+                ],
+            ),
+            Self::WriteAttrs {
+                event_id,
+                base_attr_id,
+                attr_count,
+                input_phys_lo,
+                input_phys_hi,
+            } => (
+                sse::WRITE_ATTRS,
+                [
+                    event_id as usize,
+                    base_attr_id as usize,
+                    attr_count,
+                    input_phys_lo,
+                    input_phys_hi,
+                    0,
+                ],
+            ),
+            Self::Register {
+                event_id,
+                handler_entry_pc,
+                handler_entry_arg,
+            } => (
+                sse::REGISTER,
+                [
+                    event_id as usize,
+                    handler_entry_pc,
+                    handler_entry_arg,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Self::Unregister { event_id } => (sse::UNREGISTER, [event_id as usize, 0, 0, 0, 0, 0]),
+            Self::Enable { event_id } => (sse::ENABLE, [event_id as usize, 0, 0, 0, 0, 0]),
+            Self::Disable { event_id } => (sse::DISABLE, [event_id as usize, 0, 0, 0, 0, 0]),
+            Self::Complete => (sse::COMPLETE, [0, 0, 0, 0, 0, 0]),
+            Self::Inject { event_id, hartid } => {
+                (sse::INJECT, [event_id as usize, hartid, 0, 0, 0, 0])
+            }
+            Self::HartUnmask => (sse::HART_UNMASK, [0, 0, 0, 0, 0, 0]),
+            Self::HartMask => (sse::HART_MASK, [0, 0, 0, 0, 0, 0]),
+        }
+    }
+}
+
+impl fmt::Display for Sse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ReadAttrs {
+                event_id,
+                base_attr_id,
+                attr_count,
+                output_phys_lo,
+                output_phys_hi,
+            } => write!(
+                f,
+                "sbi_sse_read_attrs(event_id: {event_id:#x}, base_attr_id: {base_attr_id:#x}, attr_count: {attr_count:#x}, output_phys_lo: {output_phys_lo:#x}, output_phys_hi: {output_phys_hi:#x})"
+            ),
+            Self::WriteAttrs {
+                event_id,
+                base_attr_id,
+                attr_count,
+                input_phys_lo,
+                input_phys_hi,
+            } => write!(
+                f,
+                "sbi_sse_write_attrs(event_id: {event_id:#x}, base_attr_id: {base_attr_id:#x}, attr_count: {attr_count:#x}, input_phys_lo: {input_phys_lo:#x}, input_phys_hi: {input_phys_hi:#x})"
+            ),
+            Self::Register {
+                event_id,
+                handler_entry_pc,
+                handler_entry_arg,
+            } => write!(
+                f,
+                "sbi_sse_register(event_id: {event_id:#x}, handler_entry_pc: {handler_entry_pc:#x}, handler_entry_arg: {handler_entry_arg:#x})"
+            ),
+            Self::Unregister { event_id } => {
+                write!(f, "sbi_sse_unregister(event_id: {event_id:#x})")
+            }
+            Self::Enable { event_id } => write!(f, "sbi_sse_enable(event_id: {event_id:#x})"),
+            Self::Disable { event_id } => write!(f, "sbi_sse_disable(event_id: {event_id:#x})"),
+            Self::Complete => write!(f, "sbi_sse_complete()"),
+            Self::Inject { event_id, hartid } => write!(
+                f,
+                "sbi_sse_inject(event_id: {event_id:#x}, hartid: {hartid:#x})"
+            ),
+            Self::HartUnmask => write!(f, "sbi_sse_hart_unmask()"),
+            Self::HartMask => write!(f, "sbi_sse_hart_mask()"),
+        }
+    }
+}
+
+/// Calls to the Firmware Features extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fwft {
+    /// `sbi_fwft_set`: set the value of a firmware feature.
+    Set {
+        /// Firmware feature type.
+        feature: u32,
+        /// Value to write.
+        value: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+    /// `sbi_fwft_get`: get the value of a firmware feature.
+    Get {
+        /// Firmware feature type.
+        feature: u32,
+    },
+}
+
+impl Fwft {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            fwft::SET => Self::Set {
+                feature: u32_param!(param[0]),
+                value: param[1],
+                flags: param[2],
+            },
+            fwft::GET => Self::Get {
+                feature: u32_param!(param[0]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::Set {
+                feature,
+                value,
+                flags,
+            } => (fwft::SET, [feature as usize, value, flags, 0, 0, 0]),
+            Self::Get { feature } => (fwft::GET, [feature as usize, 0, 0, 0, 0, 0]),
+        }
+    }
+}
+
+impl fmt::Display for Fwft {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Set {
+                feature,
+                value,
+                flags,
+            } => write!(
+                f,
+                "sbi_fwft_set(feature: {feature:#x}, value: {value:#x}, flags: {flags:#x})"
+            ),
+            Self::Get { feature } => write!(f, "sbi_fwft_get(feature: {feature:#x})"),
+        }
+    }
+}
+
+/// Calls to the Debug Triggers extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dbtr {
+    /// `sbi_debug_num_triggers`: get the number of debug triggers.
+    NumTriggers {
+        /// Trigger configuration to match, or zero for all triggers.
+        trig_tdata1: usize,
+    },
+    /// `sbi_debug_set_shmem`: set the debug trigger shared memory.
+    SetShmem {
+        /// Lower XLEN bits of the shared memory physical base address.
+        shmem_phys_lo: usize,
+        /// Upper XLEN bits of the shared memory physical base address.
+        shmem_phys_hi: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+    /// `sbi_debug_read_triggers`: read debug triggers into the shared memory.
+    ReadTriggers {
+        /// Index of the first trigger.
+        trig_idx_base: usize,
+        /// Number of triggers.
+        trig_count: usize,
+    },
+    /// `sbi_debug_install_triggers`: install debug triggers from the shared memory.
+    InstallTriggers {
+        /// Number of triggers.
+        trig_count: usize,
+    },
+    /// `sbi_debug_update_triggers`: update debug triggers from the shared memory.
+    UpdateTriggers {
+        /// Number of triggers.
+        trig_count: usize,
+    },
+    /// `sbi_debug_uninstall_triggers`: uninstall debug triggers.
+    UninstallTriggers {
+        /// Triggers the request applies to.
+        triggers: TriggerMask,
+    },
+    /// `sbi_debug_enable_triggers`: enable debug triggers.
+    EnableTriggers {
+        /// Triggers the request applies to.
+        triggers: TriggerMask,
+    },
+    /// `sbi_debug_disable_triggers`: disable debug triggers.
+    DisableTriggers {
+        /// Triggers the request applies to.
+        triggers: TriggerMask,
+    },
+}
+
+impl Dbtr {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            dbtr::NUM_TRIGGERS => Self::NumTriggers {
+                trig_tdata1: param[0],
+            },
+            dbtr::SET_SHMEM => Self::SetShmem {
+                shmem_phys_lo: param[0],
+                shmem_phys_hi: param[1],
+                flags: param[2],
+            },
+            dbtr::READ_TRIGGERS => Self::ReadTriggers {
+                trig_idx_base: param[0],
+                trig_count: param[1],
+            },
+            dbtr::INSTALL_TRIGGERS => Self::InstallTriggers {
+                trig_count: param[0],
+            },
+            dbtr::UPDATE_TRIGGERS => Self::UpdateTriggers {
+                trig_count: param[0],
+            },
+            dbtr::UNINSTALL_TRIGGERS => Self::UninstallTriggers {
+                triggers: TriggerMask::from_mask_base(param[1], param[0]),
+            },
+            dbtr::ENABLE_TRIGGERS => Self::EnableTriggers {
+                triggers: TriggerMask::from_mask_base(param[1], param[0]),
+            },
+            dbtr::DISABLE_TRIGGERS => Self::DisableTriggers {
+                triggers: TriggerMask::from_mask_base(param[1], param[0]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::NumTriggers { trig_tdata1 } => (dbtr::NUM_TRIGGERS, [trig_tdata1, 0, 0, 0, 0, 0]),
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => (
+                dbtr::SET_SHMEM,
+                [shmem_phys_lo, shmem_phys_hi, flags, 0, 0, 0],
+            ),
+            Self::ReadTriggers {
+                trig_idx_base,
+                trig_count,
+            } => (dbtr::READ_TRIGGERS, [trig_idx_base, trig_count, 0, 0, 0, 0]),
+            Self::InstallTriggers { trig_count } => {
+                (dbtr::INSTALL_TRIGGERS, [trig_count, 0, 0, 0, 0, 0])
+            }
+            Self::UpdateTriggers { trig_count } => {
+                (dbtr::UPDATE_TRIGGERS, [trig_count, 0, 0, 0, 0, 0])
+            }
+            Self::UninstallTriggers { triggers } => {
+                let (triggers_mask, triggers_base) = triggers.into_inner();
+                (
+                    dbtr::UNINSTALL_TRIGGERS,
+                    [triggers_base, triggers_mask, 0, 0, 0, 0],
+                )
+            }
+            Self::EnableTriggers { triggers } => {
+                let (triggers_mask, triggers_base) = triggers.into_inner();
+                (
+                    dbtr::ENABLE_TRIGGERS,
+                    [triggers_base, triggers_mask, 0, 0, 0, 0],
+                )
+            }
+            Self::DisableTriggers { triggers } => {
+                let (triggers_mask, triggers_base) = triggers.into_inner();
+                (
+                    dbtr::DISABLE_TRIGGERS,
+                    [triggers_base, triggers_mask, 0, 0, 0, 0],
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for Dbtr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NumTriggers { trig_tdata1 } => {
+                write!(f, "sbi_debug_num_triggers(trig_tdata1: {trig_tdata1:#x})")
+            }
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => write!(
+                f,
+                "sbi_debug_set_shmem(shmem_phys_lo: {shmem_phys_lo:#x}, shmem_phys_hi: {shmem_phys_hi:#x}, flags: {flags:#x})"
+            ),
+            Self::ReadTriggers {
+                trig_idx_base,
+                trig_count,
+            } => write!(
+                f,
+                "sbi_debug_read_triggers(trig_idx_base: {trig_idx_base:#x}, trig_count: {trig_count:#x})"
+            ),
+            Self::InstallTriggers { trig_count } => {
+                write!(f, "sbi_debug_install_triggers(trig_count: {trig_count:#x})")
+            }
+            Self::UpdateTriggers { trig_count } => {
+                write!(f, "sbi_debug_update_triggers(trig_count: {trig_count:#x})")
+            }
+            Self::UninstallTriggers { triggers } => write!(
+                f,
+                "sbi_debug_uninstall_triggers(trig_idx_mask: {:#x}, trig_idx_base: {:#x})",
+                triggers.into_inner().0,
+                triggers.into_inner().1
+            ),
+            Self::EnableTriggers { triggers } => write!(
+                f,
+                "sbi_debug_enable_triggers(trig_idx_mask: {:#x}, trig_idx_base: {:#x})",
+                triggers.into_inner().0,
+                triggers.into_inner().1
+            ),
+            Self::DisableTriggers { triggers } => write!(
+                f,
+                "sbi_debug_disable_triggers(trig_idx_mask: {:#x}, trig_idx_base: {:#x})",
+                triggers.into_inner().0,
+                triggers.into_inner().1
+            ),
+        }
+    }
+}
+
+/// Calls to the Message Proxy extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mpxy {
+    /// `sbi_mpxy_get_shmem_size`: get the shared memory size.
+    GetShmemSize,
+    /// `sbi_mpxy_set_shmem`: set the message proxy shared memory.
+    SetShmem {
+        /// Lower XLEN bits of the shared memory physical base address.
+        shmem_phys_lo: usize,
+        /// Upper XLEN bits of the shared memory physical base address.
+        shmem_phys_hi: usize,
+        /// Flags of the request.
+        flags: usize,
+    },
+    /// `sbi_mpxy_get_channel_ids`: get channel IDs into the shared memory.
+    GetChannelIds {
+        /// Index of the first channel ID to read.
+        start_index: u32,
+    },
+    /// `sbi_mpxy_read_attributes`: read channel attributes into the shared memory.
+    ReadAttributes {
+        /// Message proxy channel ID.
+        channel_id: u32,
+        /// ID of the first attribute.
+        base_attribute_id: u32,
+        /// Number of attributes.
+        attribute_count: u32,
+    },
+    /// `sbi_mpxy_write_attributes`: write channel attributes from the shared memory.
+    WriteAttributes {
+        /// Message proxy channel ID.
+        channel_id: u32,
+        /// ID of the first attribute.
+        base_attribute_id: u32,
+        /// Number of attributes.
+        attribute_count: u32,
+    },
+    /// `sbi_mpxy_send_message_with_response`: send a message and wait for its response.
+    SendMessageWithResponse {
+        /// Message proxy channel ID.
+        channel_id: u32,
+        /// Message ID.
+        message_id: u32,
+        /// Length of message data in the shared memory.
+        message_data_len: usize,
+    },
+    /// `sbi_mpxy_send_message_without_response`: send a message without waiting for response.
+    SendMessageWithoutResponse {
+        /// Message proxy channel ID.
+        channel_id: u32,
+        /// Message ID.
+        message_id: u32,
+        /// Length of message data in the shared memory.
+        message_data_len: usize,
+    },
+    /// `sbi_mpxy_get_notification_events`: get notification events into the shared memory.
+    GetNotificationEvents {
+        /// Message proxy channel ID.
+        channel_id: u32,
+    },
+}
+
+impl Mpxy {
+    /// Decode a call from its function ID and parameters.
+    ///
+    /// Returns `None` if the function ID is not defined in this extension, or if a 32-bit
+    /// parameter does not fit in 32 bits.
+    #[inline]
+    pub const fn decode(function: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match function {
+            mpxy::GET_SHMEM_SIZE => Self::GetShmemSize,
+            mpxy::SET_SHMEM => Self::SetShmem {
+                shmem_phys_lo: param[0],
+                shmem_phys_hi: param[1],
+                flags: param[2],
+            },
+            mpxy::GET_CHANNEL_IDS => Self::GetChannelIds {
+                start_index: u32_param!(param[0]),
+            },
+            mpxy::READ_ATTRIBUTE => Self::ReadAttributes {
+                channel_id: u32_param!(param[0]),
+                base_attribute_id: u32_param!(param[1]),
+                attribute_count: u32_param!(param[2]),
+            },
+            mpxy::WRITE_ATTRIBUTE => Self::WriteAttributes {
+                channel_id: u32_param!(param[0]),
+                base_attribute_id: u32_param!(param[1]),
+                attribute_count: u32_param!(param[2]),
+            },
+            mpxy::SEND_MESSAGE_WITH_RESPONSE => Self::SendMessageWithResponse {
+                channel_id: u32_param!(param[0]),
+                message_id: u32_param!(param[1]),
+                message_data_len: param[2],
+            },
+            mpxy::SEND_MESSAGE_WITHOUT_RESPONSE => Self::SendMessageWithoutResponse {
+                channel_id: u32_param!(param[0]),
+                message_id: u32_param!(param[1]),
+                message_data_len: param[2],
+            },
+            mpxy::GET_NOTIFICATION_EVENTS => Self::GetNotificationEvents {
+                channel_id: u32_param!(param[0]),
+            },
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this call into its function ID and parameters.
+    ///
+    /// Parameters not used by the function are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::GetShmemSize => (mpxy::GET_SHMEM_SIZE, [0, 0, 0, 0, 0, 0]),
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => (
+                mpxy::SET_SHMEM,
+                [shmem_phys_lo, shmem_phys_hi, flags, 0, 0, 0],
+            ),
+            Self::GetChannelIds { start_index } => {
+                (mpxy::GET_CHANNEL_IDS, [start_index as usize, 0, 0, 0, 0, 0])
+            }
+            Self::ReadAttributes {
+                channel_id,
+                base_attribute_id,
+                attribute_count,
+            } => (
+                mpxy::READ_ATTRIBUTE,
+                [
+                    channel_id as usize,
+                    base_attribute_id as usize,
+                    attribute_count as usize,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Self::WriteAttributes {
+                channel_id,
+                base_attribute_id,
+                attribute_count,
+            } => (
+                mpxy::WRITE_ATTRIBUTE,
+                [
+                    channel_id as usize,
+                    base_attribute_id as usize,
+                    attribute_count as usize,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Self::SendMessageWithResponse {
+                channel_id,
+                message_id,
+                message_data_len,
+            } => (
+                mpxy::SEND_MESSAGE_WITH_RESPONSE,
+                [
+                    channel_id as usize,
+                    message_id as usize,
+                    message_data_len,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Self::SendMessageWithoutResponse {
+                channel_id,
+                message_id,
+                message_data_len,
+            } => (
+                mpxy::SEND_MESSAGE_WITHOUT_RESPONSE,
+                [
+                    channel_id as usize,
+                    message_id as usize,
+                    message_data_len,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Self::GetNotificationEvents { channel_id } => (
+                mpxy::GET_NOTIFICATION_EVENTS,
+                [channel_id as usize, 0, 0, 0, 0, 0],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Mpxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::GetShmemSize => write!(f, "sbi_mpxy_get_shmem_size()"),
+            Self::SetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => write!(
+                f,
+                "sbi_mpxy_set_shmem(shmem_phys_lo: {shmem_phys_lo:#x}, shmem_phys_hi: {shmem_phys_hi:#x}, flags: {flags:#x})"
+            ),
+            Self::GetChannelIds { start_index } => {
+                write!(f, "sbi_mpxy_get_channel_ids(start_index: {start_index:#x})")
+            }
+            Self::ReadAttributes {
+                channel_id,
+                base_attribute_id,
+                attribute_count,
+            } => write!(
+                f,
+                "sbi_mpxy_read_attributes(channel_id: {channel_id:#x}, base_attribute_id: {base_attribute_id:#x}, attribute_count: {attribute_count:#x})"
+            ),
+            Self::WriteAttributes {
+                channel_id,
+                base_attribute_id,
+                attribute_count,
+            } => write!(
+                f,
+                "sbi_mpxy_write_attributes(channel_id: {channel_id:#x}, base_attribute_id: {base_attribute_id:#x}, attribute_count: {attribute_count:#x})"
+            ),
+            Self::SendMessageWithResponse {
+                channel_id,
+                message_id,
+                message_data_len,
+            } => write!(
+                f,
+                "sbi_mpxy_send_message_with_response(channel_id: {channel_id:#x}, message_id: {message_id:#x}, message_data_len: {message_data_len:#x})"
+            ),
+            Self::SendMessageWithoutResponse {
+                channel_id,
+                message_id,
+                message_data_len,
+            } => write!(
+                f,
+                "sbi_mpxy_send_message_without_response(channel_id: {channel_id:#x}, message_id: {message_id:#x}, message_data_len: {message_data_len:#x})"
+            ),
+            Self::GetNotificationEvents { channel_id } => write!(
+                f,
+                "sbi_mpxy_get_notification_events(channel_id: {channel_id:#x})"
+            ),
+        }
+    }
+}
+
+/// Calls to the Legacy extensions.
+///
+/// Each legacy extension ID stands for a single function; function IDs in register `a6` are ignored.
+#[cfg(feature = "legacy")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Legacy {
+    /// `sbi_set_timer`: program the clock for the next event.
+    SetTimer {
+        /// Absolute time of the next timer event.
+        stime_value: u64,
+    },
+    /// `sbi_console_putchar`: write a character to the debug console.
+    ConsolePutchar {
+        /// Character to write.
+        ch: usize,
+    },
+    /// `sbi_console_getchar`: read a character from the debug console.
+    ConsoleGetchar,
+    /// `sbi_clear_ipi`: clear pending inter-processor interrupts.
+    ClearIpi,
+    /// `sbi_send_ipi`: send an inter-processor interrupt to harts.
+    SendIpi {
+        /// Virtual address of the hart mask.
+        hart_mask_ptr: usize,
+    },
+    /// `sbi_remote_fence_i`: execute `FENCE.I` on remote harts.
+    RemoteFenceI {
+        /// Virtual address of the hart mask.
+        hart_mask_ptr: usize,
+    },
+    /// `sbi_remote_sfence_vma`: execute `SFENCE.VMA` on remote harts.
+    RemoteSfenceVma {
+        /// Virtual address of the hart mask.
+        hart_mask_ptr: usize,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+    },
+    /// `sbi_remote_sfence_vma_asid`: execute `SFENCE.VMA` with an ASID on remote harts.
+    RemoteSfenceVmaAsid {
+        /// Virtual address of the hart mask.
+        hart_mask_ptr: usize,
+        /// Start of the address range.
+        start_addr: usize,
+        /// Size of the address range.
+        size: usize,
+        /// Address space identifier.
+        asid: usize,
+    },
+    /// `sbi_shutdown`: shut down all harts.
+    Shutdown,
+}
+
+#[cfg(feature = "legacy")]
+impl Legacy {
+    /// Decode a legacy call from its extension ID and parameters.
+    ///
+    /// Returns `None` if the extension ID is not a legacy extension.
+    #[inline]
+    pub const fn decode(extension: usize, param: [usize; 6]) -> Option<Self> {
+        let call = match extension {
+            legacy::LEGACY_SET_TIMER => Self::SetTimer {
+                stime_value: join_u64(param[0], param[1]),
+            },
+            legacy::LEGACY_CONSOLE_PUTCHAR => Self::ConsolePutchar { ch: param[0] },
+            legacy::LEGACY_CONSOLE_GETCHAR => Self::ConsoleGetchar,
+            legacy::LEGACY_CLEAR_IPI => Self::ClearIpi,
+            legacy::LEGACY_SEND_IPI => Self::SendIpi {
+                hart_mask_ptr: param[0],
+            },
+            legacy::LEGACY_REMOTE_FENCE_I => Self::RemoteFenceI {
+                hart_mask_ptr: param[0],
+            },
+            legacy::LEGACY_REMOTE_SFENCE_VMA => Self::RemoteSfenceVma {
+                hart_mask_ptr: param[0],
+                start_addr: param[1],
+                size: param[2],
+            },
+            legacy::LEGACY_REMOTE_SFENCE_VMA_ASID => Self::RemoteSfenceVmaAsid {
+                hart_mask_ptr: param[0],
+                start_addr: param[1],
+                size: param[2],
+                asid: param[3],
+            },
+            legacy::LEGACY_SHUTDOWN => Self::Shutdown,
+            _ => return None,
+        };
+        Some(call)
+    }
+    /// Encode this legacy call into its extension ID and parameters.
+    ///
+    /// Parameters not used by the extension are encoded as zero.
+    #[inline]
+    pub const fn encode(&self) -> (usize, [usize; 6]) {
+        match *self {
+            Self::SetTimer { stime_value } => {
+                let (stime_value_lo, stime_value_hi) = split_u64(stime_value);
+                (
+                    legacy::LEGACY_SET_TIMER,
+                    [stime_value_lo, stime_value_hi, 0, 0, 0, 0],
+                )
+            }
+            Self::ConsolePutchar { ch } => (legacy::LEGACY_CONSOLE_PUTCHAR, [ch, 0, 0, 0, 0, 0]),
+            Self::ConsoleGetchar => (legacy::LEGACY_CONSOLE_GETCHAR, [0, 0, 0, 0, 0, 0]),
+            Self::ClearIpi => (legacy::LEGACY_CLEAR_IPI, [0, 0, 0, 0, 0, 0]),
+            Self::SendIpi { hart_mask_ptr } => {
+                (legacy::LEGACY_SEND_IPI, [hart_mask_ptr, 0, 0, 0, 0, 0])
+            }
+            Self::RemoteFenceI { hart_mask_ptr } => (
+                legacy::LEGACY_REMOTE_FENCE_I,
+                [hart_mask_ptr, 0, 0, 0, 0, 0],
+            ),
+            Self::RemoteSfenceVma {
+                hart_mask_ptr,
+                start_addr,
+                size,
+            } => (
+                legacy::LEGACY_REMOTE_SFENCE_VMA,
+                [hart_mask_ptr, start_addr, size, 0, 0, 0],
+            ),
+            Self::RemoteSfenceVmaAsid {
+                hart_mask_ptr,
+                start_addr,
+                size,
+                asid,
+            } => (
+                legacy::LEGACY_REMOTE_SFENCE_VMA_ASID,
+                [hart_mask_ptr, start_addr, size, asid, 0, 0],
+            ),
+            Self::Shutdown => (legacy::LEGACY_SHUTDOWN, [0, 0, 0, 0, 0, 0]),
+        }
+    }
+}
+
+#[cfg(feature = "legacy")]
+impl fmt::Display for Legacy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SetTimer { stime_value } => {
+                write!(f, "sbi_set_timer(stime_value: {stime_value:#x})")
+            }
+            Self::ConsolePutchar { ch } => write!(f, "sbi_console_putchar(ch: {ch:#x})"),
+            Self::ConsoleGetchar => write!(f, "sbi_console_getchar()"),
+            Self::ClearIpi => write!(f, "sbi_clear_ipi()"),
+            Self::SendIpi { hart_mask_ptr } => {
+                write!(f, "sbi_send_ipi(hart_mask_ptr: {hart_mask_ptr:#x})")
+            }
+            Self::RemoteFenceI { hart_mask_ptr } => {
+                write!(f, "sbi_remote_fence_i(hart_mask_ptr: {hart_mask_ptr:#x})")
+            }
+            Self::RemoteSfenceVma {
+                hart_mask_ptr,
+                start_addr,
+                size,
+            } => write!(
+                f,
+                "sbi_remote_sfence_vma(hart_mask_ptr: {hart_mask_ptr:#x}, start_addr: {start_addr:#x}, size: {size:#x})"
+            ),
+            Self::RemoteSfenceVmaAsid {
+                hart_mask_ptr,
+                start_addr,
+                size,
+                asid,
+            } => write!(
+                f,
+                "sbi_remote_sfence_vma_asid(hart_mask_ptr: {hart_mask_ptr:#x}, start_addr: {start_addr:#x}, size: {size:#x}, asid: {asid:#x})"
+            ),
+            Self::Shutdown => write!(f, "sbi_shutdown()"),
+        }
+    }
+}
+
+/// Assemble a 64-bit parameter from its lower and upper halves.
+///
+/// On RV64 the whole value is held in the register of the lower half.
+#[inline]
+const fn join_u64(lo: usize, hi: usize) -> u64 {
+    match () {
+        #[cfg(target_pointer_width = "64")]
+        () => {
+            let _ = hi;
+            lo as u64
+        }
+        #[cfg(target_pointer_width = "32")]
+        () => ((hi as u64) << 32) | (lo as u64),
+    }
+}
+
+/// Split a 64-bit parameter into its lower and upper halves.
+#[inline]
+const fn split_u64(value: u64) -> (usize, usize) {
+    match () {
+        #[cfg(target_pointer_width = "64")]
+        () => (value as usize, 0),
+        #[cfg(target_pointer_width = "32")]
+        () => (value as usize, (value >> 32) as usize),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_encode_all_functions() {
+        // Every function ID defined in each extension decodes into a typed call,
+        // and encodes back into the same registers.
+        let param = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66];
+        for (extension, functions) in [
+            (base::EID_BASE, 0..7),
+            (time::EID_TIME, 0..1),
+            (spi::EID_SPI, 0..1),
+            (rfnc::EID_RFNC, 0..7),
+            (hsm::EID_HSM, 0..4),
+            (srst::EID_SRST, 0..1),
+            (pmu::EID_PMU, 0..9),
+            (dbcn::EID_DBCN, 0..3),
+            (susp::EID_SUSP, 0..1),
+            (cppc::EID_CPPC, 0..4),
+            (nacl::EID_NACL, 0..5),
+            (sta::EID_STA, 0..1),
+            (sse::EID_SSE, 0..10),
+            (fwft::EID_FWFT, 0..2),
+            (dbtr::EID_DBTR, 0..8),
+            (mpxy::EID_MPXY, 0..8),
+        ] {
+            for function in functions.clone() {
+                let call = SbiCall::decode(extension, function, param);
+                assert!(
+                    !matches!(call, SbiCall::Unknown { .. }),
+                    "{extension:#x}, {function:#x}"
+                );
+                let (e, f, p) = call.encode();
+                assert_eq!((e, f), (extension, function));
+                assert_eq!(SbiCall::decode(e, f, p), call);
+                for (encoded, decoded) in p.iter().zip(param) {
+                    assert!(*encoded == 0 || *encoded == decoded);
+                }
+            }
+            let call = SbiCall::decode(extension, functions.end, param);
+            assert_eq!(
+                call,
+                SbiCall::Unknown {
+                    extension,
+                    function: functions.end,
+                    param
+                }
+            );
+            assert_eq!(call.encode(), (extension, functions.end, param));
+        }
+    }
+
+    #[test]
+    fn decode_typed_parameters() {
+        assert_eq!(
+            SbiCall::decode(
+                rfnc::EID_RFNC,
+                rfnc::REMOTE_SFENCE_VMA_ASID,
+                [0b101, 2, 0x1000, 0x2000, 7, 0]
+            ),
+            SbiCall::Rfnc(Rfnc::RemoteSfenceVmaAsid {
+                hart_mask: HartMask::from_mask_base(0b101, 2),
+                start_addr: 0x1000,
+                size: 0x2000,
+                asid: 7
+            })
+        );
+        // PMU counter masks are passed as `counter_idx_base` in `a0` and `counter_idx_mask` in `a1`
+        assert_eq!(
+            SbiCall::decode(pmu::EID_PMU, pmu::COUNTER_STOP, [3, 0b11, 1, 0, 0, 0]),
+            SbiCall::Pmu(Pmu::CounterStop {
+                counters: CounterMask::from_mask_base(0b11, 3),
+                stop_flags: 1
+            })
+        );
+        assert_eq!(
+            SbiCall::decode(dbtr::EID_DBTR, dbtr::ENABLE_TRIGGERS, [4, 0b1, 0, 0, 0, 0]),
+            SbiCall::Dbtr(Dbtr::EnableTriggers {
+                triggers: TriggerMask::from_mask_base(0b1, 4)
+            })
+        );
+        assert_eq!(
+            SbiCall::decode(
+                dbcn::EID_DBCN,
+                dbcn::CONSOLE_WRITE_BYTE,
+                [0x141, 0, 0, 0, 0, 0]
+            ),
+            SbiCall::Dbcn(Dbcn::ConsoleWriteByte { byte: b'A' })
+        );
+        assert_eq!(
+            SbiCall::Mpxy(Mpxy::SendMessageWithResponse {
+                channel_id: 1,
+                message_id: 2,
+                message_data_len: 3
+            })
+            .encode(),
+            (
+                mpxy::EID_MPXY,
+                mpxy::SEND_MESSAGE_WITH_RESPONSE,
+                [1, 2, 3, 0, 0, 0]
+            )
+        );
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            SbiCall::decode(
+                time::EID_TIME,
+                time::SET_TIMER,
+                [0x1_0000_0000, 0, 0, 0, 0, 0]
+            ),
+            SbiCall::Time(Time::SetTimer {
+                stime_value: 0x1_0000_0000
+            })
+        );
+        // Vendor-specific extensions are not decoded
+        assert!(matches!(
+            SbiCall::decode(0x0900_0000, 0, [0; 6]),
+            SbiCall::Unknown { .. }
+        ));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn decode_u32_out_of_range() {
+        // 32-bit parameters with upper bits set are not decoded, and encode back unchanged
+        for (extension, function, param) in [
+            (
+                hsm::EID_HSM,
+                hsm::HART_SUSPEND,
+                [0x1_0000_0000, 0, 0, 0, 0, 0],
+            ),
+            (
+                srst::EID_SRST,
+                srst::SYSTEM_RESET,
+                [0, 0x1_0000_0001, 0, 0, 0, 0],
+            ),
+            (
+                susp::EID_SUSP,
+                susp::SUSPEND,
+                [0x1_0000_0000, 0, 0, 0, 0, 0],
+            ),
+            (cppc::EID_CPPC, cppc::READ, [0x1_0000_0000, 0, 0, 0, 0, 0]),
+            (fwft::EID_FWFT, fwft::GET, [0x1_0000_0000, 0, 0, 0, 0, 0]),
+            (sse::EID_SSE, sse::ENABLE, [0x1_0000_0000, 0, 0, 0, 0, 0]),
+            (
+                nacl::EID_NACL,
+                nacl::PROBE_FEATURE,
+                [0x1_0000_0000, 0, 0, 0, 0, 0],
+            ),
+            (
+                mpxy::EID_MPXY,
+                mpxy::SEND_MESSAGE_WITH_RESPONSE,
+                [1, 0x1_0000_0002, 3, 0, 0, 0],
+            ),
+        ] {
+            let call = SbiCall::decode(extension, function, param);
+            assert_eq!(
+                call,
+                SbiCall::Unknown {
+                    extension,
+                    function,
+                    param
+                },
+                "{extension:#x}, {function:#x}"
+            );
+            assert_eq!(call.encode(), (extension, function, param));
+        }
+        assert_eq!(
+            SbiCall::decode(
+                hsm::EID_HSM,
+                hsm::HART_SUSPEND,
+                [0xffff_ffff, 0, 0, 0, 0, 0]
+            ),
+            SbiCall::Hsm(Hsm::HartSuspend {
+                suspend_type: 0xffff_ffff,
+                resume_addr: 0,
+                opaque: 0
+            })
+        );
+    }
+
+    #[test]
+    fn display_calls() {
+        extern crate alloc;
+        use alloc::string::ToString;
+
+        assert_eq!(
+            SbiCall::Base(Base::GetSpecVersion).to_string(),
+            "sbi_get_spec_version()"
+        );
+        assert_eq!(
+            SbiCall::Base(Base::ProbeExtension {
+                extension_id: hsm::EID_HSM
+            })
+            .to_string(),
+            "sbi_probe_extension(extension_id: 0x48534d)"
+        );
+        assert_eq!(
+            SbiCall::Spi(Spi::SendIpi {
+                hart_mask: HartMask::from_mask_base(0b11, 0)
+            })
+            .to_string(),
+            "sbi_send_ipi(hart_mask: 0x3, hart_mask_base: 0x0)"
+        );
+        assert_eq!(
+            SbiCall::Fwft(Fwft::Get { feature: 1 }).to_string(),
+            "sbi_fwft_get(feature: 0x1)"
+        );
+        assert_eq!(
+            SbiCall::decode(0x0900_0000, 1, [2, 0, 0, 0, 0, 0]).to_string(),
+            "sbi_call(extension: 0x9000000, function: 0x1, param: [0x2, 0x0, 0x0, 0x0, 0x0, 0x0])"
+        );
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn decode_legacy() {
+        let call = SbiCall::decode(
+            legacy::LEGACY_CONSOLE_PUTCHAR,
+            0x12,
+            [b'a' as usize, 0, 0, 0, 0, 0],
+        );
+        assert_eq!(
+            call,
+            SbiCall::Legacy(Legacy::ConsolePutchar { ch: b'a' as usize })
+        );
+        assert_eq!(
+            call.encode(),
+            (
+                legacy::LEGACY_CONSOLE_PUTCHAR,
+                0,
+                [b'a' as usize, 0, 0, 0, 0, 0]
+            )
+        );
+        for extension in 0..=8 {
+            assert!(matches!(
+                SbiCall::decode(extension, 0, [0; 6]),
+                SbiCall::Legacy(_)
+            ));
+        }
+        assert!(matches!(
+            SbiCall::decode(0x09, 0, [0; 6]),
+            SbiCall::Unknown { .. }
+        ));
+    }
+}
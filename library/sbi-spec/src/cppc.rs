@@ -28,3 +28,53 @@ mod fid {
     #[doc(alias = "SBI_EXT_CPPC_WRITE")]
     pub const WRITE: usize = 3;
 }
+
+/// CPPC register identifiers.
+///
+/// Declared in §14.
+pub mod reg_id {
+    /// Highest performance the processor can reach (32-bit, read-only).
+    pub const HIGHEST_PERFORMANCE: u32 = 0x0000_0000;
+    /// Highest sustained performance of the processor (32-bit, read-only).
+    pub const NOMINAL_PERFORMANCE: u32 = 0x0000_0001;
+    /// Lowest performance with non-linear power savings (32-bit, read-only).
+    pub const LOWEST_NONLINEAR_PERFORMANCE: u32 = 0x0000_0002;
+    /// Lowest performance the processor can reach (32-bit, read-only).
+    pub const LOWEST_PERFORMANCE: u32 = 0x0000_0003;
+    /// Currently guaranteed performance (32-bit, read-only).
+    pub const GUARANTEED_PERFORMANCE: u32 = 0x0000_0004;
+    /// Desired performance requested by the supervisor (32-bit, read-write).
+    pub const DESIRED_PERFORMANCE: u32 = 0x0000_0005;
+    /// Minimum performance allowed by the supervisor (32-bit, read-write).
+    pub const MINIMUM_PERFORMANCE: u32 = 0x0000_0006;
+    /// Maximum performance allowed by the supervisor (32-bit, read-write).
+    pub const MAXIMUM_PERFORMANCE: u32 = 0x0000_0007;
+    /// Tolerated deviation below the desired performance (32-bit, read-write).
+    pub const PERFORMANCE_REDUCTION_TOLERANCE: u32 = 0x0000_0008;
+    /// Time window for the delivered performance, in milliseconds (32-bit, read-write).
+    pub const TIME_WINDOW: u32 = 0x0000_0009;
+    /// Minimum time for the performance counters to wrap around, in seconds (32 or 64-bit, read-only).
+    pub const COUNTER_WRAPAROUND_TIME: u32 = 0x0000_000A;
+    /// Counter incrementing at the reference performance (32 or 64-bit, read-only).
+    pub const REFERENCE_PERFORMANCE_COUNTER: u32 = 0x0000_000B;
+    /// Counter incrementing at the delivered performance (32 or 64-bit, read-only).
+    pub const DELIVERED_PERFORMANCE_COUNTER: u32 = 0x0000_000C;
+    /// Whether the delivered performance was limited by the platform (32-bit, read-write).
+    pub const PERFORMANCE_LIMITED: u32 = 0x0000_000D;
+    /// Whether CPPC is enabled (32-bit, read-write).
+    pub const CPPC_ENABLE: u32 = 0x0000_000E;
+    /// Whether the platform selects performance autonomously (32-bit, read-write).
+    pub const AUTONOMOUS_SELECTION_ENABLE: u32 = 0x0000_000F;
+    /// Activity window of autonomous selection (32-bit, read-write).
+    pub const AUTONOMOUS_ACTIVITY_WINDOW: u32 = 0x0000_0010;
+    /// Energy performance preference of autonomous selection (32-bit, read-write).
+    pub const ENERGY_PERFORMANCE_PREFERENCE: u32 = 0x0000_0011;
+    /// Performance at which the reference performance counter increments (32-bit, read-only).
+    pub const REFERENCE_PERFORMANCE: u32 = 0x0000_0012;
+    /// Lowest frequency of the processor, in MHz (32-bit, read-only).
+    pub const LOWEST_FREQUENCY: u32 = 0x0000_0013;
+    /// Nominal frequency of the processor, in MHz (32-bit, read-only).
+    pub const NOMINAL_FREQUENCY: u32 = 0x0000_0014;
+    /// Maximum performance state transition latency, in nanoseconds (32-bit, read-only).
+    pub const TRANSITION_LATENCY: u32 = 0x8000_0000;
+}
@@ -55,6 +55,9 @@ pub mod dbtr;
 // §20
 pub mod mpxy;
 
+// Typed SBI calls over all extensions above
+pub mod call;
+
 /// Converts SBI EID from str.
 const fn eid_from_str(name: &str) -> i32 {
     match *name.as_bytes() {
@@ -208,6 +211,7 @@ mod tests {
         const_assert_eq!(5, COUNTER_FW_READ);
         const_assert_eq!(6, COUNTER_FW_READ_HI);
         const_assert_eq!(7, SNAPSHOT_SET_SHMEM);
+        const_assert_eq!(8, EVENT_GET_INFO);
 
         const_assert_eq!(0, event_type::HARDWARE_GENERAL);
         const_assert_eq!(1, event_type::HARDWARE_CACHE);
@@ -266,6 +270,10 @@ mod tests {
         const_assert_eq!(65535, firmware_event::PLATFORM);
 
         const_assert_eq!(4096, shmem_size::SIZE);
+        const_assert_eq!(16, size_of::<EventInfo>());
+        const_assert_eq!(16, align_of::<EventInfo>());
+        const_assert_eq!(4, core::mem::offset_of!(EventInfo, output));
+        const_assert_eq!(8, core::mem::offset_of!(EventInfo, event_data));
         const_assert_eq!(1, flags::ConfigFlags::SKIP_MATCH.bits());
         const_assert_eq!(2, flags::ConfigFlags::CLEAR_VALUE.bits());
         const_assert_eq!(4, flags::ConfigFlags::AUTO_START.bits());
@@ -304,6 +312,29 @@ mod tests {
         const_assert_eq!(1, READ);
         const_assert_eq!(2, READ_HI);
         const_assert_eq!(3, WRITE);
+
+        const_assert_eq!(0x00, reg_id::HIGHEST_PERFORMANCE);
+        const_assert_eq!(0x01, reg_id::NOMINAL_PERFORMANCE);
+        const_assert_eq!(0x02, reg_id::LOWEST_NONLINEAR_PERFORMANCE);
+        const_assert_eq!(0x03, reg_id::LOWEST_PERFORMANCE);
+        const_assert_eq!(0x04, reg_id::GUARANTEED_PERFORMANCE);
+        const_assert_eq!(0x05, reg_id::DESIRED_PERFORMANCE);
+        const_assert_eq!(0x06, reg_id::MINIMUM_PERFORMANCE);
+        const_assert_eq!(0x07, reg_id::MAXIMUM_PERFORMANCE);
+        const_assert_eq!(0x08, reg_id::PERFORMANCE_REDUCTION_TOLERANCE);
+        const_assert_eq!(0x09, reg_id::TIME_WINDOW);
+        const_assert_eq!(0x0A, reg_id::COUNTER_WRAPAROUND_TIME);
+        const_assert_eq!(0x0B, reg_id::REFERENCE_PERFORMANCE_COUNTER);
+        const_assert_eq!(0x0C, reg_id::DELIVERED_PERFORMANCE_COUNTER);
+        const_assert_eq!(0x0D, reg_id::PERFORMANCE_LIMITED);
+        const_assert_eq!(0x0E, reg_id::CPPC_ENABLE);
+        const_assert_eq!(0x0F, reg_id::AUTONOMOUS_SELECTION_ENABLE);
+        const_assert_eq!(0x10, reg_id::AUTONOMOUS_ACTIVITY_WINDOW);
+        const_assert_eq!(0x11, reg_id::ENERGY_PERFORMANCE_PREFERENCE);
+        const_assert_eq!(0x12, reg_id::REFERENCE_PERFORMANCE);
+        const_assert_eq!(0x13, reg_id::LOWEST_FREQUENCY);
+        const_assert_eq!(0x14, reg_id::NOMINAL_FREQUENCY);
+        const_assert_eq!(0x8000_0000, reg_id::TRANSITION_LATENCY);
     }
     // §15
     #[test]
@@ -363,6 +394,31 @@ mod tests {
         const_assert_eq!(7, INJECT);
         const_assert_eq!(8, HART_UNMASK);
         const_assert_eq!(9, HART_MASK);
+
+        const_assert_eq!(0x0000_0000, event_id::LOCAL_HIGH_PRIO_RAS);
+        const_assert_eq!(0x0000_0001, event_id::LOCAL_DOUBLE_TRAP);
+        const_assert_eq!(0x0000_8000, event_id::GLOBAL_HIGH_PRIO_RAS);
+        const_assert_eq!(0x0001_0000, event_id::LOCAL_PMU_OVERFLOW);
+        const_assert_eq!(0x0010_0000, event_id::LOCAL_LOW_PRIO_RAS);
+        const_assert_eq!(0x0010_8000, event_id::GLOBAL_LOW_PRIO_RAS);
+        const_assert_eq!(0xffff_0000, event_id::LOCAL_SOFTWARE_INJECTED);
+        const_assert_eq!(0xffff_8000, event_id::GLOBAL_SOFTWARE_INJECTED);
+
+        const_assert_eq!(0, attr_id::STATUS);
+        const_assert_eq!(1, attr_id::PRIORITY);
+        const_assert_eq!(2, attr_id::CONFIG);
+        const_assert_eq!(3, attr_id::PREFERRED_HART);
+        const_assert_eq!(4, attr_id::ENTRY_PC);
+        const_assert_eq!(5, attr_id::ENTRY_ARG);
+        const_assert_eq!(6, attr_id::INTERRUPTED_SEPC);
+        const_assert_eq!(7, attr_id::INTERRUPTED_FLAGS);
+        const_assert_eq!(8, attr_id::INTERRUPTED_A6);
+        const_assert_eq!(9, attr_id::INTERRUPTED_A7);
+
+        const_assert_eq!(0, event_state::UNUSED);
+        const_assert_eq!(1, event_state::REGISTERED);
+        const_assert_eq!(2, event_state::ENABLED);
+        const_assert_eq!(3, event_state::RUNNING);
     }
     // §18
     #[test]
@@ -399,5 +455,19 @@ mod tests {
         const_assert_eq!(5, SEND_MESSAGE_WITH_RESPONSE);
         const_assert_eq!(6, SEND_MESSAGE_WITHOUT_RESPONSE);
         const_assert_eq!(7, GET_NOTIFICATION_EVENTS);
+
+        const_assert_eq!(0, attr_id::MSG_PROT_ID);
+        const_assert_eq!(1, attr_id::MSG_PROT_VERSION);
+        const_assert_eq!(2, attr_id::MSG_MAX_LEN);
+        const_assert_eq!(3, attr_id::MSG_SEND_TIMEOUT);
+        const_assert_eq!(4, attr_id::MSG_COMPLETION_TIMEOUT);
+        const_assert_eq!(5, attr_id::CHANNEL_CAPABILITY);
+        const_assert_eq!(6, attr_id::SSE_EVENT_ID);
+        const_assert_eq!(7, attr_id::MSI_CONTROL);
+        const_assert_eq!(8, attr_id::MSI_ADDR_LO);
+        const_assert_eq!(9, attr_id::MSI_ADDR_HI);
+        const_assert_eq!(10, attr_id::MSI_DATA);
+        const_assert_eq!(11, attr_id::EVENTS_STATE_CONTROL);
+        const_assert_eq!(0x8000_0000, attr_id::MSG_PROT_ATTR_START);
     }
 }
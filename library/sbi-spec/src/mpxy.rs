@@ -48,3 +48,63 @@ mod fid {
     #[doc(alias = "SBI_EXT_MPXY_GET_NOTIFICATION_EVENTS")]
     pub const GET_NOTIFICATION_EVENTS: usize = 7;
 }
+
+/// Standard message channel attribute identifiers.
+///
+/// Attribute IDs `0x8000_0000` and above are message protocol specific.
+///
+/// Declared in §20.3.
+pub mod attr_id {
+    /// Message protocol identifier of the channel.
+    pub const MSG_PROT_ID: u32 = 0x0000_0000;
+    /// Message protocol version of the channel.
+    pub const MSG_PROT_VERSION: u32 = 0x0000_0001;
+    /// Maximum message data length in bytes supported by the channel.
+    pub const MSG_MAX_LEN: u32 = 0x0000_0002;
+    /// Timeout in microseconds for sending a message.
+    pub const MSG_SEND_TIMEOUT: u32 = 0x0000_0003;
+    /// Timeout in microseconds for completing a message with response.
+    pub const MSG_COMPLETION_TIMEOUT: u32 = 0x0000_0004;
+    /// Capabilities of the channel, see [`ChannelCapability`](super::flags::ChannelCapability).
+    pub const CHANNEL_CAPABILITY: u32 = 0x0000_0005;
+    /// SSE event ID used to signal notification events of the channel.
+    pub const SSE_EVENT_ID: u32 = 0x0000_0006;
+    /// Enable or disable MSI based notification of the channel.
+    pub const MSI_CONTROL: u32 = 0x0000_0007;
+    /// Lower 32 bits of the MSI address.
+    pub const MSI_ADDR_LO: u32 = 0x0000_0008;
+    /// Upper 32 bits of the MSI address.
+    pub const MSI_ADDR_HI: u32 = 0x0000_0009;
+    /// MSI data value.
+    pub const MSI_DATA: u32 = 0x0000_000A;
+    /// Enable or disable events state reporting of the channel.
+    pub const EVENTS_STATE_CONTROL: u32 = 0x0000_000B;
+    /// First message protocol specific attribute identifier.
+    pub const MSG_PROT_ATTR_START: u32 = 0x8000_0000;
+}
+
+/// Message proxy flags.
+pub mod flags {
+    use bitflags::bitflags;
+
+    bitflags! {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        /// Bits of the `CHANNEL_CAPABILITY` attribute.
+        ///
+        /// Declared in §20.3.
+        pub struct ChannelCapability: u32 {
+            /// Channel supports MSI based notification.
+            const MSI = 1 << 0;
+            /// Channel supports SSE based notification.
+            const SSE = 1 << 1;
+            /// Channel supports events state reporting.
+            const EVENTS_STATE = 1 << 2;
+            /// Channel supports `SEND_MESSAGE_WITH_RESPONSE`.
+            const SEND_WITH_RESPONSE = 1 << 3;
+            /// Channel supports `SEND_MESSAGE_WITHOUT_RESPONSE`.
+            const SEND_WITHOUT_RESPONSE = 1 << 4;
+            /// Channel supports `GET_NOTIFICATION_EVENTS`.
+            const GET_NOTIFICATION_EVENTS = 1 << 5;
+        }
+    }
+}
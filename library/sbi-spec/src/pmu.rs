@@ -212,6 +212,41 @@ pub mod shmem_size {
     pub const SIZE: usize = 4096;
 }
 
+/// Entry of the shared memory array to get details about PMU events.
+///
+/// The supervisor fills in `event_idx` and `event_data` of each entry before calling
+/// `EVENT_GET_INFO`, and the SBI implementation writes `output` of each entry.
+///
+/// Declared in §11.14.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EventInfo {
+    /// Event index of the event to query.
+    pub event_idx: u32,
+    /// Output of the query; bit 0 is set if the event is supported.
+    pub output: u32,
+    /// Event data of the event to query.
+    pub event_data: u64,
+}
+
+impl EventInfo {
+    /// Create an entry to query the event `event_idx` with `event_data`.
+    #[inline]
+    pub const fn new(event_idx: u32, event_data: u64) -> Self {
+        Self {
+            event_idx,
+            output: 0,
+            event_data,
+        }
+    }
+
+    /// Whether the SBI implementation reported this event as supported.
+    #[inline]
+    pub const fn is_supported(&self) -> bool {
+        self.output & 1 != 0
+    }
+}
+
 /// Find and configure a matching counter.
 /// Start a set of counters.
 /// Stop a set of counters.
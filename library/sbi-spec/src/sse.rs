@@ -58,3 +58,122 @@ mod fid {
     #[doc(alias = "SBI_EXT_SSE_HART_MASK")]
     pub const HART_MASK: usize = 9;
 }
+
+/// Software event identifiers.
+///
+/// Bit 15 of an event ID is set for global events and cleared for local events.
+///
+/// Declared in §17.
+pub mod event_id {
+    /// Local high priority RAS event.
+    pub const LOCAL_HIGH_PRIO_RAS: u32 = 0x0000_0000;
+    /// Local double trap event.
+    pub const LOCAL_DOUBLE_TRAP: u32 = 0x0000_0001;
+    /// Global high priority RAS event.
+    pub const GLOBAL_HIGH_PRIO_RAS: u32 = 0x0000_8000;
+    /// Local PMU overflow event.
+    pub const LOCAL_PMU_OVERFLOW: u32 = 0x0001_0000;
+    /// Local low priority RAS event.
+    pub const LOCAL_LOW_PRIO_RAS: u32 = 0x0010_0000;
+    /// Global low priority RAS event.
+    pub const GLOBAL_LOW_PRIO_RAS: u32 = 0x0010_8000;
+    /// Software injected local event.
+    pub const LOCAL_SOFTWARE_INJECTED: u32 = 0xffff_0000;
+    /// Software injected global event.
+    pub const GLOBAL_SOFTWARE_INJECTED: u32 = 0xffff_8000;
+    /// Bit in event ID that marks the event as a global event.
+    pub const GLOBAL_BIT: u32 = 1 << 15;
+    /// Bit in event ID that marks the event as platform specific.
+    pub const PLATFORM_BIT: u32 = 1 << 14;
+}
+
+/// Software event attribute identifiers.
+///
+/// Declared in §17.
+pub mod attr_id {
+    /// Status of the software event (read-only).
+    ///
+    /// Bits `[1:0]` hold the event state, bit 2 is the pending status and
+    /// bit 3 indicates whether the event can be injected using `sse_inject`.
+    pub const STATUS: u32 = 0x0000_0000;
+    /// Software event priority (read-write when the event is in `UNUSED` or `REGISTERED` state).
+    pub const PRIORITY: u32 = 0x0000_0001;
+    /// Additional configuration of the software event; bit 0 is the `ONESHOT` flag.
+    pub const CONFIG: u32 = 0x0000_0002;
+    /// Hart ID of the preferred hart that should handle a global software event.
+    pub const PREFERRED_HART: u32 = 0x0000_0003;
+    /// Entry program counter value for handling the software event (read-only).
+    pub const ENTRY_PC: u32 = 0x0000_0004;
+    /// Entry argument (or parameter) value for handling the software event (read-only).
+    pub const ENTRY_ARG: u32 = 0x0000_0005;
+    /// Interrupted `sepc` CSR value, saved when the event was delivered.
+    pub const INTERRUPTED_SEPC: u32 = 0x0000_0006;
+    /// Interrupted flags, saved from `sstatus` and `hstatus` when the event was delivered.
+    pub const INTERRUPTED_FLAGS: u32 = 0x0000_0007;
+    /// Interrupted `a6` general purpose register value.
+    pub const INTERRUPTED_A6: u32 = 0x0000_0008;
+    /// Interrupted `a7` general purpose register value.
+    pub const INTERRUPTED_A7: u32 = 0x0000_0009;
+    /// Number of standard attributes; attribute IDs from this value and above are reserved.
+    pub const COUNT: u32 = 0x0000_000A;
+}
+
+/// Software event states, as reported in bits `[1:0]` of the `STATUS` attribute.
+///
+/// Declared in §17.
+pub mod event_state {
+    /// The event is not registered.
+    pub const UNUSED: usize = 0;
+    /// The event is registered but not enabled.
+    pub const REGISTERED: usize = 1;
+    /// The event is registered and enabled.
+    pub const ENABLED: usize = 2;
+    /// The event handler is running.
+    pub const RUNNING: usize = 3;
+}
+
+/// Bit fields of software event attributes.
+///
+/// Declared in §17.
+pub mod flags {
+    use bitflags::bitflags;
+
+    bitflags! {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        /// Bit fields of the `STATUS` attribute besides the event state.
+        pub struct StatusFlags: usize {
+            /// Event is pending.
+            const PENDING = 1 << 2;
+            /// Event can be injected using `sse_inject`.
+            const INJECT = 1 << 3;
+        }
+    }
+
+    bitflags! {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        /// Bit fields of the `CONFIG` attribute.
+        pub struct ConfigFlags: usize {
+            /// The event is automatically moved to `REGISTERED` state after completion.
+            const ONESHOT = 1 << 0;
+        }
+    }
+
+    bitflags! {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        /// Bit fields of the `INTERRUPTED_FLAGS` attribute.
+        pub struct InterruptedFlags: usize {
+            /// Interrupted `sstatus.SPP` value.
+            const SSTATUS_SPP = 1 << 0;
+            /// Interrupted `sstatus.SPIE` value.
+            const SSTATUS_SPIE = 1 << 1;
+            /// Interrupted `hstatus.SPV` value.
+            const HSTATUS_SPV = 1 << 2;
+            /// Interrupted `hstatus.SPVP` value.
+            const HSTATUS_SPVP = 1 << 3;
+            /// Interrupted `sstatus.SPELP` value.
+            const SSTATUS_SPELP = 1 << 4;
+            /// Interrupted `sstatus.SDT` value.
+            const SSTATUS_SDT = 1 << 5;
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Firmware features extension test suite.
+
+use crate::thread::Thread;
+use riscv::{
+    interrupt::supervisor::{Exception, Interrupt},
+    register::{
+        scause::{self, Trap},
+        sie::{self, Sie},
+    },
+};
+use sbi::{Counter, PmuEvent, SbiRet};
+use sbi_spec::{
+    fwft::{feature_type, flags::SetFlags},
+    pmu::{firmware_event, flags::ConfigFlags},
+};
+
+/// Firmware features extension test cases.
+#[derive(Clone, Debug)]
+pub enum Case {
+    /// Can't proceed test for firmware features extension does not exist.
+    NotExist,
+    /// Test begin.
+    Begin,
+    /// Test process for a reserved feature rejected with denied.
+    ReservedDenied,
+    /// Test process for the value of a feature read.
+    Get {
+        /// Feature ID.
+        feature: u32,
+        /// Current value of the feature.
+        value: usize,
+    },
+    /// Test process for a feature skipped for it is not supported.
+    NotSupported(u32),
+    /// Test process for a feature skipped for setting it is denied by the SBI implementation.
+    SetDenied(u32),
+    /// Test process for a misaligned load trapped to supervisor after delegation enabled.
+    MisalignedDelegated,
+    /// Test skipped for a misaligned load completed without trap, thus delegation is not exercised.
+    MisalignedNotTrapped,
+    /// Test process for a feature locked, and later set calls rejected with denied locked.
+    Locked {
+        /// Feature ID.
+        feature: u32,
+        /// Locked value of the feature.
+        value: usize,
+    },
+    /// Test failed for a misaligned load emulated by the SBI implementation after delegation enabled.
+    MisalignedEmulated,
+    /// Test failed for unexpected trap occurred upon tests.
+    UnexpectedTrap(Trap<usize, usize>),
+    /// Test failed for an FWFT call on a feature returned an unexpected value.
+    Failed(u32, &'static str, SbiRet),
+    /// All test cases on firmware features extension has passed.
+    Pass,
+}
+
+const FEATURES: [u32; 6] = [
+    feature_type::MISALIGNED_EXC_DELEG as _,
+    feature_type::LANDING_PAD as _,
+    feature_type::SHADOW_STACK as _,
+    feature_type::DOUBLE_TRAP as _,
+    feature_type::PTE_AD_HW_UPDATING as _,
+    feature_type::POINTER_MASKING_PMLEN as _,
+];
+
+/// Last feature ID of the reserved range `0x6..=0x3fff_ffff`.
+const RESERVED_FEATURE: u32 = 0x3fff_ffff;
+
+/// Test firmware features extension.
+///
+/// Every supported feature is locked with its current value, so that it can no longer be
+/// modified until the system resets.
+pub fn test(mut f: impl FnMut(Case)) {
+    if sbi::probe_extension(sbi::Fwft).is_unavailable() {
+        f(Case::NotExist);
+        return;
+    }
+    f(Case::Begin);
+
+    let mut pass = true;
+
+    // 保留的特性必须被拒绝
+    let ret = sbi::fwft_get(RESERVED_FEATURE);
+    let set_ret = sbi::fwft_set(RESERVED_FEATURE, 0, 0);
+    if ret != SbiRet::denied() {
+        f(Case::Failed(RESERVED_FEATURE, "get", ret));
+        pass = false;
+    } else if set_ret != SbiRet::denied() {
+        f(Case::Failed(RESERVED_FEATURE, "set", set_ret));
+        pass = false;
+    } else {
+        f(Case::ReservedDenied);
+    }
+
+    for feature in FEATURES {
+        pass &= test_feature(feature, &mut f);
+    }
+
+    if pass {
+        f(Case::Pass);
+    }
+}
+
+fn test_feature(feature: u32, mut f: impl FnMut(Case)) -> bool {
+    let ret = sbi::fwft_get(feature);
+    if ret == SbiRet::not_supported() {
+        f(Case::NotSupported(feature));
+        return true;
+    } else if !ret.is_ok() {
+        f(Case::Failed(feature, "get", ret));
+        return false;
+    }
+    let value = ret.value;
+    f(Case::Get { feature, value });
+
+    // 以当前值设置，SBI 实现可以拒绝
+    let ret = sbi::fwft_set(feature, value, 0);
+    if ret == SbiRet::denied() {
+        f(Case::SetDenied(feature));
+        return true;
+    } else if !ret.is_ok() {
+        f(Case::Failed(feature, "set", ret));
+        return false;
+    }
+    // 未定义的标志位必须被拒绝
+    let ret = sbi::fwft_set(feature, value, !SetFlags::all().bits());
+    if ret != SbiRet::invalid_param() {
+        f(Case::Failed(feature, "set with invalid flags", ret));
+        return false;
+    }
+    if feature == feature_type::MISALIGNED_EXC_DELEG as u32 && !test_misaligned(value, &mut f) {
+        return false;
+    }
+
+    // 以当前值锁定，此后设置必须失败，且值不变
+    let ret = sbi::fwft_set(feature, value, SetFlags::LOCK.bits());
+    if !ret.is_ok() {
+        f(Case::Failed(feature, "set with lock", ret));
+        return false;
+    }
+    let ret = sbi::fwft_set(feature, value, 0);
+    if ret != SbiRet::denied_locked() {
+        f(Case::Failed(feature, "set after lock", ret));
+        return false;
+    }
+    let ret = sbi::fwft_get(feature);
+    if ret != SbiRet::success(value) {
+        f(Case::Failed(feature, "get after lock", ret));
+        return false;
+    }
+    f(Case::Locked { feature, value });
+    true
+}
+
+/// 开启非对齐异常委托后，在线程中执行非对齐访存，再恢复原值
+///
+/// 访存未产生异常时，以 `MISALIGNED_LOAD` 固件计数器区分硬件完成的访存与 SBI 实现的模拟。
+fn test_misaligned(value: usize, mut f: impl FnMut(Case)) -> bool {
+    const FEATURE: u32 = feature_type::MISALIGNED_EXC_DELEG as _;
+
+    fn load(addr: usize) -> ! {
+        unsafe {
+            core::arch::asm!(
+                "lw {0}, 0({0})",
+                "unimp",
+                in(reg) addr,
+                options(noreturn, readonly, nostack),
+            )
+        };
+    }
+
+    let ret = sbi::fwft_set(FEATURE, 1, 0);
+    if !ret.is_ok() {
+        f(Case::Failed(FEATURE, "enable delegation", ret));
+        return false;
+    }
+    // 固件计数器不可用时无法区分，只能跳过
+    let counter = if sbi::probe_extension(sbi::Pmu).is_available() {
+        let event = PmuEvent::firmware(firmware_event::MISALIGNED_LOAD);
+        Counter::config_any(event, ConfigFlags::CLEAR_VALUE | ConfigFlags::AUTO_START).ok()
+    } else {
+        None
+    };
+    let data = [0usize; 2];
+    let mut stack = [0usize; 32];
+    let mut thread = Thread::new(load as *const () as _, stack.as_mut_ptr_range().end as _);
+    *thread.a_mut(0) = data.as_ptr() as usize + 1;
+    unsafe {
+        // 屏蔽所有中断，只捕获访存异常
+        let sie = sie::read();
+        sie::write(Sie::from_bits(0));
+        thread.execute();
+        sie::write(sie);
+    }
+    let trap = scause::read().cause();
+    let pass = match trap.try_into::<Interrupt, Exception>() {
+        Ok(Trap::Exception(Exception::LoadMisaligned)) => {
+            f(Case::MisalignedDelegated);
+            true
+        }
+        // 访存已完成，线程执行到 `unimp`
+        Ok(Trap::Exception(Exception::IllegalInstruction)) => {
+            match counter.as_ref().map(Counter::read) {
+                Some(Ok(count)) if count != 0 => {
+                    f(Case::MisalignedEmulated);
+                    false
+                }
+                _ => {
+                    f(Case::MisalignedNotTrapped);
+                    true
+                }
+            }
+        }
+        _ => {
+            f(Case::UnexpectedTrap(trap));
+            false
+        }
+    };
+    if let Some(counter) = counter {
+        let _ = counter.release();
+    }
+
+    let ret = sbi::fwft_set(FEATURE, value, 0);
+    if !ret.is_ok() {
+        f(Case::Failed(FEATURE, "restore delegation", ret));
+        return false;
+    }
+    pass
+}
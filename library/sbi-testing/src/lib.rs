@@ -3,15 +3,22 @@
 #![no_std]
 #![deny(warnings, missing_docs)]
 
+mod scratch;
 mod thread;
 
+pub use scratch::Scratch;
+
 pub extern crate sbi_rt as sbi;
 
 #[cfg(feature = "log")]
 mod log_test;
+#[cfg(feature = "log")]
+mod report;
 
 #[cfg(feature = "log")]
 pub use log_test::Testing;
+#[cfg(feature = "log")]
+pub use report::Format as ReportFormat;
 
 // §4
 mod base;
@@ -23,14 +30,26 @@ pub use time::{Case as TimerCase, test as test_timer};
 mod spi;
 pub use spi::{Case as IpiCase, test as test_ipi};
 // §8
-// pub mod rfnc;
+mod rfnc;
+pub use rfnc::{Case as RfncCase, Function as RfncFunction, test as test_rfnc};
 // §9
 mod hsm;
 pub use hsm::{Case as HsmCase, test as test_hsm};
 // §10
-// pub mod srst;
+mod srst;
+pub use srst::{Case as SrstCase, test as test_srst};
 // §11
-// pub mod pmu;
+mod pmu;
+pub use pmu::{Case as PmuCase, test as test_pmu};
 // §12
 mod dbcn;
 pub use dbcn::{Case as DbcnCase, test as test_dbcn};
+// §13
+mod susp;
+pub use susp::{Case as SuspCase, test as test_susp};
+// §17
+mod sse;
+pub use sse::{Case as SseCase, test as test_sse};
+// §18
+mod fwft;
+pub use fwft::{Case as FwftCase, test as test_fwft};
@@ -1,5 +1,9 @@
-﻿use crate::{base, dbcn, hsm, spi, time};
-use log::*;
+use crate::{
+    Scratch, base, dbcn, fwft, hsm, pmu,
+    report::{Format, Reporter},
+    rfnc, spi, srst, sse, susp, time,
+};
+use core::fmt::Write;
 
 /// Automatic SBI testing with logging enabled.
 pub struct Testing {
@@ -9,186 +13,339 @@ pub struct Testing {
     pub hart_mask: usize,
     /// Base of hart list to test Hart State Monitor extension.
     pub hart_mask_base: usize,
-    /// Delay value to test Timer programmer extension.
+    /// Delay value to test Timer programmer extension, also the wake-up delay of suspend to RAM.
     pub delay: u64,
+    /// Persistent scratch area to test warm reboot and suspend to RAM; `None` skips them.
+    ///
+    /// With a scratch area, the system warm reboots once during tests, and this testing
+    /// process should be started again in the next boot to finish the tests.
+    pub scratch: Option<&'static mut Scratch>,
 }
 
-const TARGET: &str = "sbi-testing";
-
 impl Testing {
     /// Start testing process of RISC-V SBI implementation.
+    ///
+    /// Test case events are printed by the `log` crate.
+    #[inline]
     pub fn test(self) -> bool {
-        let mut result = true;
+        self.run(Reporter::log())
+    }
+
+    /// Start testing process of RISC-V SBI implementation, writing test case events to
+    /// `output` as machine-readable records of `format`.
+    #[inline]
+    pub fn report(self, format: Format, output: &mut dyn Write) -> bool {
+        self.run(Reporter::new(format, output))
+    }
+
+    fn run(mut self, mut reporter: Reporter) -> bool {
         base::test(|case| {
+            let r = reporter.case("Base", &case);
             use base::Case::*;
             match case {
                 NotExist => panic!("Sbi `Base` not exist"),
-                Begin => info!(target: TARGET, "Testing `Base`"),
-                Pass => info!(target: TARGET, "Sbi `Base` test pass"),
-                GetSbiSpecVersion(version) => {
-                    info!(target: TARGET, "sbi spec version = {version}");
-                }
-                GetSbiImplId(Ok(name)) => {
-                    info!(target: TARGET, "sbi impl = {name}");
-                }
+                Begin => r.info(format_args!("Testing `Base`")),
+                Pass => r.info(format_args!("Sbi `Base` test pass")),
+                GetSbiSpecVersion(version) => r.info(format_args!("sbi spec version = {version}")),
+                GetSbiImplId(Ok(name)) => r.info(format_args!("sbi impl = {name}")),
                 GetSbiImplId(Err(unknown)) => {
-                    warn!(target: TARGET, "unknown sbi impl = {unknown:#x}");
+                    r.warn(format_args!("unknown sbi impl = {unknown:#x}"))
                 }
                 GetSbiImplVersion(version) => {
-                    info!(target: TARGET, "sbi impl version = {version:#x}");
-                }
-                ProbeExtensions(exts) => {
-                    info!(target: TARGET, "sbi extensions = {exts}");
-                }
-                GetMvendorId(id) => {
-                    info!(target: TARGET, "mvendor id = {id:#x}");
+                    r.info(format_args!("sbi impl version = {version:#x}"))
                 }
-                GetMarchId(id) => {
-                    info!(target: TARGET, "march id = {id:#x}");
-                }
-                GetMimpId(id) => {
-                    info!(target: TARGET, "mimp id = {id:#x}");
+                ProbeExtensions(exts) => r.info(format_args!("sbi extensions = {exts}")),
+                GetMvendorId(id) => r.info(format_args!("mvendor id = {id:#x}")),
+                GetMarchId(id) => r.info(format_args!("march id = {id:#x}")),
+                GetMimpId(id) => r.info(format_args!("mimp id = {id:#x}")),
+            }
+        });
+        // SRST 在 Base 之后立即测试，热重启前只重复执行 Base 测试
+        srst::test(self.scratch.as_deref_mut(), |case| {
+            let r = reporter.case("SRST", &case);
+            use srst::Case::*;
+            match case {
+                NotExist => r.error(format_args!("Sbi `SRST` not exist")),
+                Begin => r.info(format_args!("Testing `SRST`")),
+                Pass => r.info(format_args!("Sbi `SRST` test pass")),
+                ReservedTypeRejected => r.debug(format_args!("reserved reset types rejected")),
+                ReservedReasonRejected => r.debug(format_args!("reserved reset reason rejected")),
+                NoScratch => r.warn(format_args!("no scratch area for warm reboot")),
+                WarmRebootRequested => r.info(format_args!("warm reboot requested")),
+                WarmRebooted => r.info(format_args!("system warm rebooted")),
+                ResetWithReservedReason => {
+                    r.error(format_args!("system reset with a reserved reason"))
                 }
+                Failed(what, ret) => r.ret(ret).error(format_args!("{what} failed: {ret:?}")),
             }
         });
         time::test(self.delay, |case| {
+            let r = reporter.case("TIME", &case);
             use time::Case::*;
             match case {
-                NotExist => {
-                    error!(target: TARGET, "Sbi `TIME` not exist");
-                    result = false;
-                }
-                Begin => info!(target: TARGET, "Testing `TIME`"),
-                Pass => info!(target: TARGET, "Sbi `TIME` test pass"),
-                Interval { begin: _, end: _ } => {
-                    info!(
-                        target: TARGET,
-                        "read time register successfully, set timer +1s"
-                    );
-                }
-                ReadFailed => {
-                    error!(target: TARGET, "csrr time failed");
-                    result = false;
-                }
-                TimeDecreased { a, b } => {
-                    error!(target: TARGET, "time decreased: {a} -> {b}");
-                    result = false;
-                }
-                SetTimer => {
-                    info!(target: TARGET, "timer interrupt delegate successfully");
-                }
-                UnexpectedTrap(trap) => {
-                    error!(
-                        target: TARGET,
-                        "expect trap at supervisor timer, but {trap:?} was caught"
-                    );
-                    result = false;
-                }
+                NotExist => r.error(format_args!("Sbi `TIME` not exist")),
+                Begin => r.info(format_args!("Testing `TIME`")),
+                Pass => r.info(format_args!("Sbi `TIME` test pass")),
+                Interval { begin: _, end: _ } => r.info(format_args!(
+                    "read time register successfully, set timer +1s"
+                )),
+                ReadFailed => r.error(format_args!("csrr time failed")),
+                TimeDecreased { a, b } => r.error(format_args!("time decreased: {a} -> {b}")),
+                SetTimer => r.info(format_args!("timer interrupt delegate successfully")),
+                UnexpectedTrap(trap) => r.error(format_args!(
+                    "expect trap at supervisor timer, but {trap:?} was caught"
+                )),
             }
         });
         spi::test(self.hartid, |case| {
+            let r = reporter.case("sPI", &case);
             use spi::Case::*;
             match case {
-                NotExist => {
-                    error!(target: TARGET, "Sbi `sPI` not exist");
-                    result = false;
-                }
-                Begin => info!(target: TARGET, "Testing `sPI`"),
-                Pass => info!(target: TARGET, "Sbi `sPI` test pass"),
-                SendIpi => info!(target: TARGET, "send ipi successfully"),
-                UnexpectedTrap(trap) => {
-                    error!(
-                        target: TARGET,
-                        "expect trap at supervisor soft, but {trap:?} was caught"
-                    );
-                    result = false;
+                NotExist => r.error(format_args!("Sbi `sPI` not exist")),
+                Begin => r.info(format_args!("Testing `sPI`")),
+                Pass => r.info(format_args!("Sbi `sPI` test pass")),
+                SendIpi => r.info(format_args!("send ipi successfully")),
+                UnexpectedTrap(trap) => r.error(format_args!(
+                    "expect trap at supervisor soft, but {trap:?} was caught"
+                )),
+            }
+        });
+        rfnc::test(self.hartid, self.hart_mask, self.hart_mask_base, |case| {
+            let r = reporter.case("RFNC", &case);
+            use rfnc::Case::*;
+            match case {
+                NotExist => r.error(format_args!("Sbi `RFNC` not exist")),
+                Begin => r.info(format_args!("Testing `RFNC`")),
+                Pass => r.info(format_args!("Sbi `RFNC` test pass")),
+                LocalFence(function) => r.debug(format_args!("{function:?} on current hart pass")),
+                AllHartsFence(function) => r.debug(format_args!("{function:?} on all harts pass")),
+                FullFlush(function) => r.debug(format_args!("{function:?} full flush pass")),
+                HfenceNotSupported(function) => r.debug(format_args!(
+                    "{function:?} not supported without hypervisor extension"
+                )),
+                UnalignedAddress(function, ret) => r
+                    .ret(ret)
+                    .debug(format_args!("{function:?} on unaligned address: {ret:?}")),
+                InvalidHartRejected(function) => {
+                    r.debug(format_args!("{function:?} rejected invalid hart"))
                 }
+                InvalidHartNotRejected(function, ret) => r.ret(ret).error(format_args!(
+                    "{function:?} to invalid hart returned {ret:?}"
+                )),
+                StoppedHart(id) => r.info(format_args!("remote fence to stopped hart {id} pass")),
+                NoStoppedHart => r.warn(format_args!("no stopped hart for remote fence")),
+                Failed(function, ret) => r
+                    .ret(ret)
+                    .error(format_args!("{function:?} failed: {ret:?}")),
             }
         });
         hsm::test(self.hartid, self.hart_mask, self.hart_mask_base, |case| {
+            let r = reporter.case("HSM", &case);
             use hsm::Case::*;
             match case {
-                NotExist => {
-                    error!(target: TARGET, "Sbi `HSM` not exist");
-                    result = false;
-                }
-                Begin => info!(target: TARGET, "Testing `HSM`"),
-                Pass => info!(target: TARGET, "Sbi `HSM` test pass"),
-                HartStartedBeforeTest(id) => warn!(target: TARGET, "hart {id} already started"),
-                NoStoppedHart => warn!(target: TARGET, "no stopped hart"),
-                BatchBegin(batch) => info!(target: TARGET, "Testing harts: {batch:?}"),
-                HartStarted(id) => debug!(target: TARGET, "hart {id} started"),
-                HartStartFailed { hartid, ret } => {
-                    error!(target: TARGET, "hart {hartid} start failed: {ret:?}");
-                    result = false;
-                }
+                NotExist => r.error(format_args!("Sbi `HSM` not exist")),
+                Begin => r.info(format_args!("Testing `HSM`")),
+                Pass => r.info(format_args!("Sbi `HSM` test pass")),
+                HartStartedBeforeTest(id) => r.warn(format_args!("hart {id} already started")),
+                NoStoppedHart => r.warn(format_args!("no stopped hart")),
+                BatchBegin(batch) => r.info(format_args!("Testing harts: {batch:?}")),
+                HartStarted(id) => r.debug(format_args!("hart {id} started")),
+                HartStartFailed { hartid, ret } => r
+                    .ret(ret)
+                    .error(format_args!("hart {hartid} start failed: {ret:?}")),
                 HartSuspendedNonretentive(id) => {
-                    debug!(target: TARGET, "hart {id} suspended nonretentive")
+                    r.debug(format_args!("hart {id} suspended nonretentive"))
                 }
-                HartResumed(id) => debug!(target: TARGET, "hart {id} resumed"),
+                HartResumed(id) => r.debug(format_args!("hart {id} resumed")),
                 HartSuspendedRetentive(id) => {
-                    debug!(target: TARGET, "hart {id} suspended retentive")
+                    r.debug(format_args!("hart {id} suspended retentive"))
                 }
-                HartStopped(id) => debug!(target: TARGET, "hart {id} stopped"),
+                HartStopped(id) => r.debug(format_args!("hart {id} stopped")),
                 RemoteRFencePass(id) => {
-                    info!(target: TARGET, "remote RFence to started hart {id} pass")
+                    r.info(format_args!("remote RFence to started hart {id} pass"))
+                }
+                RemoteRFenceFailed(id, ret) => r.ret(ret).error(format_args!(
+                    "remote RFence to started hart {id} failed: {ret:?}"
+                )),
+                BatchPass(batch) => r.info(format_args!("Testing Pass: {batch:?}")),
+            }
+        });
+        pmu::test(|case| {
+            let r = reporter.case("PMU", &case);
+            use pmu::Case::*;
+            match case {
+                NotExist => r.error(format_args!("Sbi `PMU` not exist")),
+                Begin => r.info(format_args!("Testing `PMU`")),
+                Pass => r.info(format_args!("Sbi `PMU` test pass")),
+                NumCounters(num) => r.info(format_args!("number of counters = {num}")),
+                NoCounters => r.warn(format_args!("no PMU counter available")),
+                CounterInfo(idx, info) => r.debug(format_args!("counter {idx}: {info:?}")),
+                CounterInfoFailed(idx, ret) => r
+                    .ret(ret)
+                    .error(format_args!("get info of counter {idx} failed: {ret:?}")),
+                ConfigMatching(idx) => {
+                    r.debug(format_args!("CPU cycles configured on counter {idx}"))
                 }
-                RemoteRFenceFailed(id, ret) => {
-                    error!(target: TARGET, "remote RFence to started hart {id} failed: {ret:?}");
-                    result = false;
+                HardwareEventNotSupported => r.warn(format_args!("CPU cycles event not supported")),
+                StartStopStates(idx) => {
+                    r.debug(format_args!("counter {idx} start and stop states pass"))
                 }
-                BatchPass(batch) => info!(target: TARGET, "Testing Pass: {batch:?}"),
+                SkipMatch(idx) => r.debug(format_args!("counter {idx} SKIP_MATCH pass")),
+                AutoStart(idx) => r.debug(format_args!("counter {idx} AUTO_START pass")),
+                FirmwareEvent { event, counter_idx } => r.debug(format_args!(
+                    "firmware event {event} on counter {counter_idx} pass"
+                )),
+                FirmwareEventNotSupported(event) => {
+                    r.debug(format_args!("firmware event {event} not supported"))
+                }
+                FirmwareEventOnHardwareCounter { event, counter_idx } => r.error(format_args!(
+                    "firmware event {event} configured on hardware counter {counter_idx}"
+                )),
+                FirmwareReadHi(idx) => r.debug(format_args!("counter {idx} fw_read_hi pass")),
+                Snapshot(idx) => r.info(format_args!("snapshot of counter {idx} pass")),
+                SnapshotNotSupported => r.warn(format_args!("PMU snapshot not supported")),
+                SnapshotMismatch {
+                    counter_idx,
+                    snapshot,
+                    read,
+                } => r.error(format_args!(
+                    "snapshot of counter {counter_idx} is {snapshot}, but {read} was read"
+                )),
+                EventInfo {
+                    event_idx,
+                    supported,
+                } => r.debug(format_args!("event {event_idx:#x} supported: {supported}")),
+                EventInfoNotSupported => r.warn(format_args!("PMU event info not supported")),
+                EventInfoMismatch {
+                    event_idx,
+                    supported,
+                } => r.error(format_args!(
+                    "event {event_idx:#x} reported supported: {supported}, \
+                     mismatched with counter configuration"
+                )),
+                Failed(what, ret) => r.ret(ret).error(format_args!("{what} failed: {ret:?}")),
             }
         });
         dbcn::test(|case| {
+            let r = reporter.case("DBCN", &case);
             use dbcn::Case::*;
             match case {
-                NotExist => {
-                    error!(target: TARGET, "Sbi `DBCN` not exist");
-                    result = false;
-                }
-                Begin => info!(target: TARGET, "Testing `DBCN`"),
-                Pass => info!(target: TARGET, "Sbi `DBCN` test pass"),
+                NotExist => r.error(format_args!("Sbi `DBCN` not exist")),
+                Begin => r.info(format_args!("Testing `DBCN`")),
+                Pass => r.info(format_args!("Sbi `DBCN` test pass")),
                 WriteByte => {}
-                WritingByteFailed(ret) => {
-                    error!(target: TARGET, "writing byte failed: {ret:?}");
-                    result = false;
-                }
-                WriteSlice => info!(target: TARGET, "writing slice successfully"),
+                WritingByteFailed(ret) => r
+                    .ret(ret)
+                    .error(format_args!("writing byte failed: {ret:?}")),
+                WriteSlice => r.info(format_args!("writing slice successfully")),
                 WritingPartialSlice(len) => {
-                    warn!(target: TARGET, "writing partial slice: {len} bytes written");
+                    r.warn(format_args!("writing partial slice: {len} bytes written"))
                 }
-                WritingSliceFailed(ret) => {
-                    error!(target: TARGET, "writing slice failed: {ret:?}");
-                    result = false;
+                WritingSliceFailed(ret) => r
+                    .ret(ret)
+                    .error(format_args!("writing slice failed: {ret:?}")),
+                Read(len) => r.info(format_args!("reading {len} bytes from console")),
+                ReadingFailed(ret) => r.ret(ret).error(format_args!("reading failed: {ret:?}")),
+                NonzeroUpperWriteRejected(ret) => r.ret(ret).info(format_args!(
+                    "DBCN rejected non-zero upper-half write: {ret:?}"
+                )),
+                NonzeroUpperWriteAccepted(len) => r.error(format_args!(
+                    "DBCN accepted non-zero upper-half write: {len} bytes written"
+                )),
+                NonzeroUpperReadRejected(ret) => r.ret(ret).info(format_args!(
+                    "DBCN rejected non-zero upper-half read: {ret:?}"
+                )),
+                NonzeroUpperReadAccepted(len) => r.error(format_args!(
+                    "DBCN accepted non-zero upper-half read: {len} bytes read"
+                )),
+            }
+        });
+        susp::test(self.hartid, self.delay, self.scratch, |case| {
+            let r = reporter.case("SUSP", &case);
+            use susp::Case::*;
+            match case {
+                NotExist => r.error(format_args!("Sbi `SUSP` not exist")),
+                Begin => r.info(format_args!("Testing `SUSP`")),
+                Pass => r.info(format_args!("Sbi `SUSP` test pass")),
+                ReservedTypeRejected => r.debug(format_args!("reserved sleep types rejected")),
+                NoScratch => r.warn(format_args!("no scratch area for suspend to RAM")),
+                NotSupported => r.warn(format_args!("suspend to RAM not supported")),
+                Denied => r.warn(format_args!("suspend to RAM denied")),
+                Resumed => r.info(format_args!("system suspended to RAM and resumed")),
+                ResetOnSuspend => r.error(format_args!("system reset on suspend to RAM")),
+                WrongResumeArgs { hart_id, opaque } => r.error(format_args!(
+                    "system resumed on hart {hart_id} with opaque {opaque:#x}"
+                )),
+                UnexpectedTrap(trap) => r.error(format_args!(
+                    "expect trap at illegal instruction, but {trap:?} was caught"
+                )),
+                Failed(what, ret) => r.ret(ret).error(format_args!("{what} failed: {ret:?}")),
+            }
+        });
+        sse::test(self.hartid, |case| {
+            let r = reporter.case("SSE", &case);
+            use sse::Case::*;
+            match case {
+                NotExist => r.error(format_args!("Sbi `SSE` not exist")),
+                Begin => r.info(format_args!("Testing `SSE`")),
+                Pass => r.info(format_args!("Sbi `SSE` test pass")),
+                CompleteOutsideHandler => {
+                    r.debug(format_args!("complete outside of event handler pass"))
                 }
-                Read(len) => info!(target: TARGET, "reading {len} bytes from console"),
-                ReadingFailed(ret) => {
-                    error!(target: TARGET, "reading failed: {ret:?}");
-                    result = false;
+                Delivered => r.info(format_args!("local software injected event delivered")),
+                Completed => r.debug(format_args!("event enabled again after completion")),
+                Preempted => r.info(format_args!("higher priority event preempted handler")),
+                Deferred => r.info(format_args!("lower priority event deferred after handler")),
+                GlobalEventNotSupported => {
+                    r.warn(format_args!("global software injected event not supported"))
                 }
-                NonzeroUpperWriteRejected(ret) => {
-                    info!(target: TARGET, "DBCN rejected non-zero upper-half write: {ret:?}");
+                NotDelivered => r.error(format_args!("injected event not delivered")),
+                WrongOrder(what) => {
+                    r.error(format_args!("events handled in wrong order upon {what}"))
                 }
-                NonzeroUpperWriteAccepted(len) => {
-                    error!(
-                        target: TARGET,
-                        "DBCN accepted non-zero upper-half write: {len} bytes written"
-                    );
-                    result = false;
+                WrongHart(id) => r.error(format_args!("local event handled on hart {id}")),
+                UnexpectedState { expected, found } => r.error(format_args!(
+                    "expect event state {expected}, but {found} was read"
+                )),
+                UnexpectedTrap(trap) => r.error(format_args!(
+                    "expect trap at illegal instruction, but {trap:?} was caught"
+                )),
+                Failed(what, ret) => r.ret(ret).error(format_args!("{what} failed: {ret:?}")),
+            }
+        });
+        fwft::test(|case| {
+            let r = reporter.case("FWFT", &case);
+            use fwft::Case::*;
+            match case {
+                NotExist => r.error(format_args!("Sbi `FWFT` not exist")),
+                Begin => r.info(format_args!("Testing `FWFT`")),
+                Pass => r.info(format_args!("Sbi `FWFT` test pass")),
+                ReservedDenied => r.debug(format_args!("reserved feature denied")),
+                Get { feature, value } => {
+                    r.debug(format_args!("feature {feature} value = {value:#x}"))
                 }
-                NonzeroUpperReadRejected(ret) => {
-                    info!(target: TARGET, "DBCN rejected non-zero upper-half read: {ret:?}");
+                NotSupported(feature) => r.warn(format_args!("feature {feature} not supported")),
+                SetDenied(feature) => r.warn(format_args!("setting feature {feature} denied")),
+                MisalignedDelegated => {
+                    r.info(format_args!("misaligned load delegated to supervisor"))
                 }
-                NonzeroUpperReadAccepted(len) => {
-                    error!(
-                        target: TARGET,
-                        "DBCN accepted non-zero upper-half read: {len} bytes read"
-                    );
-                    result = false;
+                MisalignedNotTrapped => r.warn(format_args!(
+                    "misaligned load completed without trap, delegation not exercised"
+                )),
+                Locked { feature, value } => {
+                    r.info(format_args!("feature {feature} locked at {value:#x}"))
                 }
+                MisalignedEmulated => r.error(format_args!(
+                    "misaligned load emulated by SBI implementation with delegation enabled"
+                )),
+                UnexpectedTrap(trap) => r.error(format_args!(
+                    "expect trap at load misaligned, but {trap:?} was caught"
+                )),
+                Failed(feature, what, ret) => r
+                    .ret(ret)
+                    .error(format_args!("feature {feature} {what} failed: {ret:?}")),
             }
         });
-        result
+        reporter.finish()
     }
 }
@@ -0,0 +1,387 @@
+//! Performance monitoring unit extension test suite.
+
+use core::cell::Cell;
+use sbi::{Counter, CounterInfo, CounterMask, PmuEvent, SbiRet, SharedPtr, Snapshot};
+use sbi_spec::binary::Error;
+use sbi_spec::pmu::{
+    EventInfo, firmware_event,
+    flags::{ConfigFlags, StartFlags, StopFlags},
+    hardware_event,
+};
+
+/// Performance monitoring unit extension test cases.
+#[derive(Clone, Debug)]
+pub enum Case {
+    /// Can't proceed test for performance monitoring unit extension does not exist.
+    NotExist,
+    /// Test begin.
+    Begin,
+    /// Test process for the number of counters.
+    NumCounters(usize),
+    /// Test failed for no counter is available.
+    NoCounters,
+    /// Test process for information of a counter decoded.
+    CounterInfo(usize, CounterInfo),
+    /// Test failed for can't get information of a counter.
+    CounterInfoFailed(usize, SbiRet),
+    /// Test process for the CPU cycles event configured on a counter with `CLEAR_VALUE`.
+    ConfigMatching(usize),
+    /// Test process for hardware counter tests skipped for the CPU cycles event is not supported.
+    HardwareEventNotSupported,
+    /// Test process for start and stop calls on a counter returned expected state errors.
+    StartStopStates(usize),
+    /// Test process for a configured counter reused with `SKIP_MATCH`.
+    SkipMatch(usize),
+    /// Test process for a counter started on configuration with `AUTO_START`.
+    AutoStart(usize),
+    /// Test process for a firmware event counted on a firmware counter.
+    FirmwareEvent {
+        /// The firmware event code.
+        event: usize,
+        /// The counter configured for this event.
+        counter_idx: usize,
+    },
+    /// Test process for firmware event skipped for it is not supported.
+    FirmwareEventNotSupported(usize),
+    /// Test failed for a firmware event was configured on a hardware counter.
+    FirmwareEventOnHardwareCounter {
+        /// The firmware event code.
+        event: usize,
+        /// The counter configured for this event.
+        counter_idx: usize,
+    },
+    /// Test process for the upper 32 bits of a firmware counter read using `fw_read_hi`.
+    FirmwareReadHi(usize),
+    /// Test process for counter value saved in the snapshot shared memory.
+    Snapshot(usize),
+    /// Test process for snapshot skipped for snapshot shared memory is not supported.
+    SnapshotNotSupported,
+    /// Test failed for counter value in the snapshot shared memory mismatched.
+    SnapshotMismatch {
+        /// The counter stopped with `TAKE_SNAPSHOT`.
+        counter_idx: usize,
+        /// Value saved in the snapshot shared memory.
+        snapshot: u64,
+        /// Value read using `fw_read`.
+        read: u64,
+    },
+    /// Test process for event information of an event.
+    EventInfo {
+        /// The event index queried.
+        event_idx: usize,
+        /// Whether the event is reported as supported.
+        supported: bool,
+    },
+    /// Test process for event information skipped for `EVENT_GET_INFO` is not supported.
+    EventInfoNotSupported,
+    /// Test failed for event information is inconsistent with counter configuration.
+    EventInfoMismatch {
+        /// The event index queried.
+        event_idx: usize,
+        /// Whether the event is reported as supported.
+        supported: bool,
+    },
+    /// Test failed for an SBI call described returned an unexpected value.
+    Failed(&'static str, SbiRet),
+    /// All test cases on performance monitoring unit extension has passed.
+    Pass,
+}
+
+/// Test performance monitoring unit extension on the current hart.
+///
+/// Counters configured by this test are released before the test returns.
+pub fn test(mut f: impl FnMut(Case)) {
+    if sbi::probe_extension(sbi::Pmu).is_unavailable() {
+        f(Case::NotExist);
+        return;
+    }
+    f(Case::Begin);
+
+    let num_counters = sbi::pmu_num_counters();
+    if num_counters == 0 {
+        f(Case::NoCounters);
+        return;
+    }
+    f(Case::NumCounters(num_counters));
+    let pass = Cell::new(true);
+    let expect = |what: &'static str, ret: SbiRet, expected: SbiRet, f: &mut dyn FnMut(Case)| {
+        if ret != expected {
+            f(Case::Failed(what, ret));
+            pass.set(false);
+        }
+        ret == expected
+    };
+
+    // 计数器信息
+    for idx in 0..num_counters {
+        let ret = sbi::pmu_counter_get_info(idx);
+        match ret.ok() {
+            Some(raw) => f(Case::CounterInfo(idx, CounterInfo::from_raw(raw))),
+            None => {
+                f(Case::CounterInfoFailed(idx, ret));
+                pass.set(false);
+            }
+        }
+    }
+    expect(
+        "get info of a counter beyond the number of counters",
+        sbi::pmu_counter_get_info(num_counters),
+        SbiRet::invalid_param(),
+        &mut f,
+    );
+
+    // 硬件事件：配置、启停状态、SKIP_MATCH 和 AUTO_START
+    let cycles = PmuEvent::general(hardware_event::CPU_CYCLES);
+    let ret = Counter::config_any(cycles, ConfigFlags::CLEAR_VALUE);
+    let cycles_supported = ret.is_ok();
+    match ret {
+        Err(Error::NotSupported) => f(Case::HardwareEventNotSupported),
+        Err(err) => {
+            f(Case::Failed("configure the CPU cycles event", err.into()));
+            pass.set(false);
+        }
+        Ok(counter) => {
+            let idx = counter.index();
+            f(Case::ConfigMatching(idx));
+            let mask = CounterMask::from_mask_base(1, idx);
+            let start = || sbi::pmu_counter_start(mask, StartFlags::empty(), 0);
+            let stop = |flags| sbi::pmu_counter_stop(mask, flags);
+            let states = [
+                expect(
+                    "start a configured counter",
+                    start(),
+                    SbiRet::success(0),
+                    &mut f,
+                ),
+                expect(
+                    "start a started counter",
+                    start(),
+                    SbiRet::already_started(),
+                    &mut f,
+                ),
+                expect(
+                    "stop a started counter",
+                    stop(StopFlags::empty()),
+                    SbiRet::success(0),
+                    &mut f,
+                ),
+                expect(
+                    "stop a stopped counter",
+                    stop(StopFlags::empty()),
+                    SbiRet::already_stopped(),
+                    &mut f,
+                ),
+            ];
+            if states.iter().all(|ok| *ok) {
+                f(Case::StartStopStates(idx));
+            }
+            let ret = sbi::pmu_counter_config_matching(
+                mask,
+                ConfigFlags::SKIP_MATCH,
+                cycles.event_idx(),
+                cycles.event_data(),
+            );
+            if expect(
+                "configure with SKIP_MATCH",
+                ret,
+                SbiRet::success(idx),
+                &mut f,
+            ) {
+                f(Case::SkipMatch(idx));
+            }
+            let ret = sbi::pmu_counter_config_matching(
+                mask,
+                ConfigFlags::SKIP_MATCH | ConfigFlags::AUTO_START,
+                cycles.event_idx(),
+                cycles.event_data(),
+            );
+            if expect(
+                "configure with AUTO_START",
+                ret,
+                SbiRet::success(idx),
+                &mut f,
+            ) && expect(
+                "start a counter configured with AUTO_START",
+                start(),
+                SbiRet::already_started(),
+                &mut f,
+            ) {
+                f(Case::AutoStart(idx));
+            }
+            expect(
+                "stop and reset a counter",
+                stop(StopFlags::RESET),
+                SbiRet::success(0),
+                &mut f,
+            );
+        }
+    }
+
+    // 固件事件
+    const INITIAL_VALUE: u64 = 0x1_0000_0019;
+    let mut firmware_counter = None;
+    for event in firmware_event::MISALIGNED_LOAD..=firmware_event::HFENCE_VVMA_ASID_RECEIVED {
+        let idx = match Counter::config_any(PmuEvent::firmware(event), ConfigFlags::CLEAR_VALUE) {
+            Ok(counter) => counter.index(),
+            Err(Error::NotSupported) => {
+                f(Case::FirmwareEventNotSupported(event));
+                continue;
+            }
+            Err(err) => {
+                f(Case::Failed("configure a firmware event", err.into()));
+                pass.set(false);
+                continue;
+            }
+        };
+        let mask = CounterMask::from_mask_base(1, idx);
+        let info = sbi::pmu_counter_get_info(idx);
+        if info.ok().map(CounterInfo::from_raw) != Some(CounterInfo::Firmware) {
+            f(Case::FirmwareEventOnHardwareCounter {
+                event,
+                counter_idx: idx,
+            });
+            pass.set(false);
+        } else if expect(
+            "read a cleared firmware counter",
+            sbi::pmu_counter_fw_read(idx),
+            SbiRet::success(0),
+            &mut f,
+        ) && expect(
+            "start a firmware counter with initial value",
+            sbi::pmu_counter_start(mask, StartFlags::INIT_VALUE, INITIAL_VALUE),
+            SbiRet::success(0),
+            &mut f,
+        ) {
+            let ret = sbi::pmu_counter_fw_read(idx);
+            if ret.is_err() || (ret.value as u64) < (INITIAL_VALUE as usize as u64) {
+                f(Case::Failed("read a started firmware counter", ret));
+                pass.set(false);
+            } else {
+                f(Case::FirmwareEvent {
+                    event,
+                    counter_idx: idx,
+                });
+            }
+            #[cfg(target_pointer_width = "32")]
+            let expected_hi = SbiRet::success((INITIAL_VALUE >> 32) as usize);
+            #[cfg(target_pointer_width = "64")]
+            let expected_hi = SbiRet::success(0);
+            if expect(
+                "read upper bits of a firmware counter",
+                sbi::pmu_counter_fw_read_hi(idx),
+                expected_hi,
+                &mut f,
+            ) {
+                f(Case::FirmwareReadHi(idx));
+            }
+            firmware_counter.get_or_insert(event);
+        }
+        let _ = sbi::pmu_counter_stop(mask, StopFlags::RESET);
+    }
+
+    // 快照共享内存
+    let snapshot = &SNAPSHOT;
+    // SAFETY: 测试在单个 hart 上运行，且结束前会关闭快照共享内存
+    match unsafe { snapshot.set_shmem(0) }.map_err(SbiRet::from) {
+        Err(ret) if ret == SbiRet::not_supported() => f(Case::SnapshotNotSupported),
+        Err(ret) => {
+            f(Case::Failed("set snapshot shared memory", ret));
+            pass.set(false);
+        }
+        Ok(()) => {
+            if let Some(event) = firmware_counter
+                && !test_snapshot(snapshot, event, &mut f)
+            {
+                pass.set(false);
+            }
+            expect(
+                "disable snapshot shared memory",
+                sbi::pmu_snapshot_set_shmem(SharedPtr::new(usize::MAX, usize::MAX), 0),
+                SbiRet::success(0),
+                &mut f,
+            );
+        }
+    }
+
+    // 事件信息
+    let mut entries = [
+        EventInfo::new(cycles.event_idx() as u32, 0),
+        EventInfo::new(
+            PmuEvent::firmware(firmware_event::SET_TIMER).event_idx() as u32,
+            0,
+        ),
+        EventInfo::new(PmuEvent::firmware(0xFFFE).event_idx() as u32, 0),
+    ];
+    let shmem = SharedPtr::new(entries.as_mut_ptr() as usize, 0);
+    let ret = sbi::pmu_event_get_info(shmem, entries.len(), 0);
+    if ret == SbiRet::not_supported() {
+        f(Case::EventInfoNotSupported);
+    } else if ret.is_ok() {
+        for entry in entries {
+            let (event_idx, supported) = (entry.event_idx as usize, entry.is_supported());
+            if event_idx == cycles.event_idx() && supported != cycles_supported {
+                f(Case::EventInfoMismatch {
+                    event_idx,
+                    supported,
+                });
+                pass.set(false);
+            } else {
+                f(Case::EventInfo {
+                    event_idx,
+                    supported,
+                });
+            }
+        }
+        expect(
+            "get event info with non-zero flags",
+            sbi::pmu_event_get_info(shmem, entries.len(), 1),
+            SbiRet::invalid_param(),
+            &mut f,
+        );
+    } else {
+        f(Case::Failed("get event info", ret));
+        pass.set(false);
+    }
+
+    if pass.get() {
+        f(Case::Pass);
+    }
+}
+
+static SNAPSHOT: Snapshot = Snapshot::new();
+
+/// 停止固件计数器时保存快照，并与 fw_read 读到的值比较
+fn test_snapshot(snapshot: &Snapshot, event: usize, f: &mut dyn FnMut(Case)) -> bool {
+    let flags = ConfigFlags::CLEAR_VALUE | ConfigFlags::AUTO_START;
+    let idx = match Counter::config_any(PmuEvent::firmware(event), flags) {
+        Ok(counter) => counter.index(),
+        Err(err) => {
+            f(Case::Failed("configure a firmware event", err.into()));
+            return false;
+        }
+    };
+    let mask = CounterMask::from_mask_base(1, idx);
+    let ret = sbi::pmu_counter_stop(mask, StopFlags::TAKE_SNAPSHOT);
+    let pass = if ret.is_err() {
+        f(Case::Failed("stop a counter with TAKE_SNAPSHOT", ret));
+        false
+    } else {
+        let read = sbi::pmu_counter_fw_read(idx).value as u64;
+        // 快照中的值以 counter_idx_base 为起点
+        let saved = snapshot.counter_value(0);
+        if saved as usize as u64 == read {
+            f(Case::Snapshot(idx));
+            true
+        } else {
+            f(Case::SnapshotMismatch {
+                counter_idx: idx,
+                snapshot: saved,
+                read,
+            });
+            false
+        }
+    };
+    let _ = sbi::pmu_counter_start(mask, StartFlags::empty(), 0);
+    let _ = sbi::pmu_counter_stop(mask, StopFlags::RESET);
+    pass
+}
@@ -0,0 +1,299 @@
+//! Human-readable and machine-readable reports of test cases.
+
+use core::fmt::{self, Write};
+use log::{Level, log};
+use sbi::SbiRet;
+
+pub(crate) const TARGET: &str = "sbi-testing";
+
+/// Format of machine-readable test reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Test Anything Protocol version 13.
+    ///
+    /// Each case event is a test line `ok N - Suite/Case: message`; skipped cases are marked
+    /// with a `# SKIP` directive, and `SbiRet` values are reported in a YAML diagnostic block.
+    Tap,
+    /// One JSON object per line.
+    ///
+    /// Each object has fields `suite`, `case`, `verdict` (`pass`, `skip` or `fail`) and `message`,
+    /// and fields `error` and `value` if the case carries an `SbiRet` value.
+    JsonLines,
+}
+
+/// Verdict of a case event, derived from its log level.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Skip,
+    Fail,
+}
+
+impl Verdict {
+    #[inline]
+    fn from_level(level: Level) -> Self {
+        match level {
+            Level::Error => Self::Fail,
+            Level::Warn => Self::Skip,
+            _ => Self::Pass,
+        }
+    }
+
+    #[inline]
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Skip => "skip",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+/// Reporter of case events from all test suites.
+///
+/// Without an output, case events are printed by the `log` crate; otherwise they are written
+/// to the output as records of the given format.
+pub(crate) struct Reporter<'a> {
+    output: Option<(Format, &'a mut dyn Write)>,
+    count: usize,
+    pass: bool,
+}
+
+impl<'a> Reporter<'a> {
+    /// Create a reporter printing case events by the `log` crate.
+    #[inline]
+    pub fn log() -> Self {
+        Self {
+            output: None,
+            count: 0,
+            pass: true,
+        }
+    }
+
+    /// Create a reporter writing records of `format` to `output`.
+    #[inline]
+    pub fn new(format: Format, output: &'a mut dyn Write) -> Self {
+        if format == Format::Tap {
+            let _ = writeln!(output, "TAP version 13");
+        }
+        Self {
+            output: Some((format, output)),
+            count: 0,
+            pass: true,
+        }
+    }
+
+    /// Start reporting `case` of test suite `suite`.
+    #[inline]
+    pub fn case(&mut self, suite: &'static str, case: &dyn fmt::Debug) -> CaseReport<'_, 'a> {
+        let mut name = VariantName::new();
+        let _ = write!(name, "{case:?}");
+        CaseReport {
+            reporter: self,
+            suite,
+            name,
+            ret: None,
+        }
+    }
+
+    /// Finish reporting, and return whether no case has failed.
+    #[inline]
+    pub fn finish(self) -> bool {
+        if let Some((Format::Tap, output)) = self.output {
+            let _ = writeln!(output, "1..{}", self.count);
+        }
+        self.pass
+    }
+
+    fn emit(
+        &mut self,
+        suite: &str,
+        case: &str,
+        ret: Option<SbiRet>,
+        level: Level,
+        message: fmt::Arguments,
+    ) {
+        let verdict = Verdict::from_level(level);
+        if verdict == Verdict::Fail {
+            self.pass = false;
+        }
+        self.count += 1;
+        match &mut self.output {
+            None => log!(target: TARGET, level, "{message}"),
+            Some((Format::Tap, output)) => {
+                let _ = write_tap(*output, self.count, suite, case, ret, verdict, message);
+            }
+            Some((Format::JsonLines, output)) => {
+                let _ = write_json(*output, suite, case, ret, verdict, message);
+            }
+        }
+    }
+}
+
+fn write_tap(
+    output: &mut dyn Write,
+    number: usize,
+    suite: &str,
+    case: &str,
+    ret: Option<SbiRet>,
+    verdict: Verdict,
+    message: fmt::Arguments,
+) -> fmt::Result {
+    if verdict == Verdict::Fail {
+        output.write_str("not ")?;
+    }
+    write!(output, "ok {number} - {suite}/{case}: ")?;
+    write!(TapEscape(output), "{message}")?;
+    if verdict == Verdict::Skip {
+        output.write_str(" # SKIP")?;
+    }
+    output.write_char('\n')?;
+    if let Some(ret) = ret {
+        writeln!(output, "  ---")?;
+        writeln!(output, "  error: {}", ret.error as isize)?;
+        writeln!(output, "  value: {:#x}", ret.value)?;
+        writeln!(output, "  ...")?;
+    }
+    Ok(())
+}
+
+fn write_json(
+    output: &mut dyn Write,
+    suite: &str,
+    case: &str,
+    ret: Option<SbiRet>,
+    verdict: Verdict,
+    message: fmt::Arguments,
+) -> fmt::Result {
+    write!(
+        output,
+        r#"{{"suite":"{suite}","case":"{case}","verdict":"{}","message":""#,
+        verdict.as_str()
+    )?;
+    write!(JsonEscape(output), "{message}")?;
+    output.write_char('"')?;
+    if let Some(ret) = ret {
+        write!(
+            output,
+            r#","error":{},"value":{}"#,
+            ret.error as isize, ret.value
+        )?;
+    }
+    output.write_str("}\n")
+}
+
+/// Report of one case event.
+pub(crate) struct CaseReport<'r, 'a> {
+    reporter: &'r mut Reporter<'a>,
+    suite: &'static str,
+    name: VariantName,
+    ret: Option<SbiRet>,
+}
+
+impl CaseReport<'_, '_> {
+    /// Attach the `SbiRet` value carried by the case.
+    #[inline]
+    pub fn ret(mut self, ret: SbiRet) -> Self {
+        self.ret = Some(ret);
+        self
+    }
+
+    /// Report a passed case event with debug level.
+    #[inline]
+    pub fn debug(self, message: fmt::Arguments) {
+        self.emit(Level::Debug, message)
+    }
+
+    /// Report a passed case event.
+    #[inline]
+    pub fn info(self, message: fmt::Arguments) {
+        self.emit(Level::Info, message)
+    }
+
+    /// Report a skipped case event.
+    #[inline]
+    pub fn warn(self, message: fmt::Arguments) {
+        self.emit(Level::Warn, message)
+    }
+
+    /// Report a failed case event.
+    #[inline]
+    pub fn error(self, message: fmt::Arguments) {
+        self.emit(Level::Error, message)
+    }
+
+    #[inline]
+    fn emit(self, level: Level, message: fmt::Arguments) {
+        let case = self.name.as_str();
+        self.reporter
+            .emit(self.suite, case, self.ret, level, message)
+    }
+}
+
+/// Variant name of a `Case`, taken from the leading identifier of its `Debug` output.
+struct VariantName {
+    buf: [u8; 32],
+    len: usize,
+}
+
+impl VariantName {
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            buf: [0; 32],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for VariantName {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            if !(byte.is_ascii_alphanumeric() || byte == b'_') || self.len == self.buf.len() {
+                // 标识符结束，停止格式化剩余内容
+                return Err(fmt::Error);
+            }
+            self.buf[self.len] = byte;
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Escape `#` in TAP descriptions, which would otherwise start a directive.
+struct TapEscape<'w>(&'w mut dyn Write);
+
+impl Write for TapEscape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, part) in s.split('#').enumerate() {
+            if i != 0 {
+                self.0.write_str("\\#")?;
+            }
+            self.0.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+/// Escape characters in JSON strings.
+struct JsonEscape<'w>(&'w mut dyn Write);
+
+impl Write for JsonEscape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                c if c.is_control() => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
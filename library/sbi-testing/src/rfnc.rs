@@ -0,0 +1,202 @@
+//! Remote fence extension test suite.
+
+use sbi::{HartMask, SbiRet};
+use sbi_spec::hsm::hart_state;
+
+/// Remote fence function under test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+    /// `sbi_remote_fence_i`.
+    FenceI,
+    /// `sbi_remote_sfence_vma`.
+    SfenceVma,
+    /// `sbi_remote_sfence_vma_asid`.
+    SfenceVmaAsid,
+    /// `sbi_remote_hfence_gvma_vmid`.
+    HfenceGvmaVmid,
+    /// `sbi_remote_hfence_gvma`.
+    HfenceGvma,
+    /// `sbi_remote_hfence_vvma_asid`.
+    HfenceVvmaAsid,
+    /// `sbi_remote_hfence_vvma`.
+    HfenceVvma,
+}
+
+impl Function {
+    const ALL: [Self; 7] = [
+        Self::FenceI,
+        Self::SfenceVma,
+        Self::SfenceVmaAsid,
+        Self::HfenceGvmaVmid,
+        Self::HfenceGvma,
+        Self::HfenceVvmaAsid,
+        Self::HfenceVvma,
+    ];
+
+    #[inline]
+    const fn is_hfence(self) -> bool {
+        matches!(
+            self,
+            Self::HfenceGvmaVmid | Self::HfenceGvma | Self::HfenceVvmaAsid | Self::HfenceVvma
+        )
+    }
+
+    #[inline]
+    fn call(self, hart_mask: HartMask, start_addr: usize, size: usize) -> SbiRet {
+        match self {
+            Self::FenceI => sbi::remote_fence_i(hart_mask),
+            Self::SfenceVma => sbi::remote_sfence_vma(hart_mask, start_addr, size),
+            Self::SfenceVmaAsid => sbi::remote_sfence_vma_asid(hart_mask, start_addr, size, 0),
+            Self::HfenceGvmaVmid => sbi::remote_hfence_gvma_vmid(hart_mask, start_addr, size, 0),
+            Self::HfenceGvma => sbi::remote_hfence_gvma(hart_mask, start_addr, size),
+            Self::HfenceVvmaAsid => sbi::remote_hfence_vvma_asid(hart_mask, start_addr, size, 0),
+            Self::HfenceVvma => sbi::remote_hfence_vvma(hart_mask, start_addr, size),
+        }
+    }
+}
+
+/// Remote fence extension test cases.
+#[derive(Clone, Debug)]
+pub enum Case {
+    /// Can't proceed test for remote fence extension does not exist.
+    NotExist,
+    /// Test begin.
+    Begin,
+    /// Test process for remote fence on the current hart over a page has passed.
+    LocalFence(Function),
+    /// Test process for remote fence on all harts with `hart_mask_base` of `usize::MAX` has passed.
+    AllHartsFence(Function),
+    /// Test process for flushing the full address space with `size` of `usize::MAX` has passed.
+    FullFlush(Function),
+    /// Test process for hypervisor fence skipped for hypervisor extension is not supported.
+    HfenceNotSupported(Function),
+    /// Test process for a fence on an unaligned address range returned success or invalid address.
+    UnalignedAddress(Function, SbiRet),
+    /// Test process for a fence targeting a hart which does not exist was rejected.
+    InvalidHartRejected(Function),
+    /// Test failed for a fence targeting a hart which does not exist didn't return invalid param.
+    InvalidHartNotRejected(Function, SbiRet),
+    /// Test process for a fence targeting a stopped hart has passed.
+    StoppedHart(usize),
+    /// Test process for stopped hart skipped for no other hart is stopped.
+    NoStoppedHart,
+    /// Test failed for a fence call returned an unexpected value.
+    Failed(Function, SbiRet),
+    /// All test cases on remote fence extension has passed.
+    Pass,
+}
+
+/// Test remote fence extension.
+///
+/// `hart_mask` and `hart_mask_base` should include all harts available to the supervisor;
+/// the first hart ID beyond them is used as a hart which does not exist.
+pub fn test(hart_id: usize, hart_mask: usize, hart_mask_base: usize, mut f: impl FnMut(Case)) {
+    if sbi::probe_extension(sbi::Fence).is_unavailable() {
+        f(Case::NotExist);
+        return;
+    }
+    f(Case::Begin);
+
+    let mut pass = true;
+
+    let page = test_page();
+    let local = HartMask::from_mask_base(1, hart_id);
+    let all = HartMask::from_mask_base(0, usize::MAX);
+    for function in Function::ALL {
+        let ret = function.call(local, page, PAGE_SIZE);
+        pass &= check(function, ret, Case::LocalFence(function), &mut f);
+        let ret = function.call(all, page, PAGE_SIZE);
+        pass &= check(function, ret, Case::AllHartsFence(function), &mut f);
+        if function != Function::FenceI {
+            let ret = function.call(local, 0, usize::MAX);
+            pass &= check(function, ret, Case::FullFlush(function), &mut f);
+        }
+    }
+
+    // 地址不对齐时允许成功或返回 invalid address
+    for function in Function::ALL {
+        if function == Function::FenceI {
+            continue;
+        }
+        let ret = function.call(local, page + 1, PAGE_SIZE - 1);
+        if ret == SbiRet::invalid_address() {
+            f(Case::UnalignedAddress(function, ret));
+        } else {
+            pass &= check(function, ret, Case::UnalignedAddress(function, ret), &mut f);
+        }
+    }
+
+    // 不存在的核必须被拒绝
+    let highest_bit = usize::BITS - hart_mask.leading_zeros();
+    if let Some(invalid_hart) = hart_mask_base.checked_add(highest_bit as usize) {
+        for function in Function::ALL {
+            let ret = function.call(HartMask::from_mask_base(1, invalid_hart), page, PAGE_SIZE);
+            if ret == SbiRet::invalid_param() {
+                f(Case::InvalidHartRejected(function));
+            } else if function.is_hfence() && ret == SbiRet::not_supported() {
+                f(Case::HfenceNotSupported(function));
+            } else {
+                f(Case::InvalidHartNotRejected(function, ret));
+                pass = false;
+            }
+        }
+    }
+
+    // 停止状态的核仍是合法的目标
+    let stopped_hart = (0..usize::BITS as usize)
+        .filter(|bit| hart_mask & (1 << bit) != 0)
+        .map(|bit| hart_mask_base + bit)
+        .find(|&hartid| {
+            hartid != hart_id
+                && sbi::hart_get_status(hartid) == SbiRet::success(hart_state::STOPPED)
+        });
+    match stopped_hart {
+        Some(hartid) => {
+            let target = HartMask::from_mask_base(1, hartid);
+            let ret = Function::FenceI.call(target, 0, 0);
+            let sfence_ret = Function::SfenceVma.call(target, 0, usize::MAX);
+            if ret.is_ok() && sfence_ret.is_ok() {
+                f(Case::StoppedHart(hartid));
+            } else {
+                let (function, ret) = if ret.is_ok() {
+                    (Function::SfenceVma, sfence_ret)
+                } else {
+                    (Function::FenceI, ret)
+                };
+                f(Case::Failed(function, ret));
+                pass = false;
+            }
+        }
+        None => f(Case::NoStoppedHart),
+    }
+
+    if pass {
+        f(Case::Pass);
+    }
+}
+
+/// 调用成功，或 hfence 因不支持虚拟化扩展而返回 not supported
+fn check(function: Function, ret: SbiRet, case: Case, mut f: impl FnMut(Case)) -> bool {
+    if ret.is_ok() {
+        f(case);
+        true
+    } else if function.is_hfence() && ret == SbiRet::not_supported() {
+        f(Case::HfenceNotSupported(function));
+        true
+    } else {
+        f(Case::Failed(function, ret));
+        false
+    }
+}
+
+const PAGE_SIZE: usize = 4096;
+
+#[repr(C, align(4096))]
+struct Page([u8; PAGE_SIZE]);
+
+static TEST_PAGE: Page = Page([0; PAGE_SIZE]);
+
+#[inline]
+fn test_page() -> usize {
+    &TEST_PAGE as *const Page as usize
+}
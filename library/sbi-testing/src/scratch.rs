@@ -0,0 +1,68 @@
+//! Persistent scratch area carrying test state across system resets.
+
+/// Persistent scratch area carrying test state across system resets.
+///
+/// Warm reboot and suspend-to-RAM round trips record their progress here before leaving the
+/// current boot, so that tests in the next boot can tell how the previous boot ended.
+///
+/// The area must be memory that is preserved across warm reboots and used by neither the SBI
+/// implementation nor the supervisor otherwise, e.g. a `no-map` child node of `/reserved-memory`
+/// in the device tree. Its initial content does not matter, as it is validated by a magic number.
+#[repr(C)]
+pub struct Scratch {
+    magic: usize,
+    stage: usize,
+}
+
+/// "SBIT"
+const MAGIC: usize = 0x5449_4253;
+
+/// Progress of a round trip recorded in the scratch area.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Stage {
+    /// No round trip in progress.
+    Idle,
+    /// System reset requested with a reserved reset reason, which must be rejected.
+    ReservedReason,
+    /// Warm reboot requested.
+    WarmReboot,
+    /// System suspended to RAM.
+    Suspend,
+}
+
+impl Scratch {
+    /// Size of the scratch area in bytes.
+    pub const SIZE: usize = size_of::<Self>();
+
+    /// Reads the round trip stage; an area never written by tests is idle.
+    #[inline]
+    pub(crate) fn stage(&self) -> Stage {
+        let magic = unsafe { (&raw const self.magic).read_volatile() };
+        if magic != MAGIC {
+            return Stage::Idle;
+        }
+        match unsafe { (&raw const self.stage).read_volatile() } {
+            1 => Stage::ReservedReason,
+            2 => Stage::WarmReboot,
+            3 => Stage::Suspend,
+            _ => Stage::Idle,
+        }
+    }
+
+    /// Records the round trip stage.
+    #[inline]
+    pub(crate) fn set_stage(&mut self, stage: Stage) {
+        let stage = match stage {
+            Stage::Idle => 0,
+            Stage::ReservedReason => 1,
+            Stage::WarmReboot => 2,
+            Stage::Suspend => 3,
+        };
+        unsafe {
+            (&raw mut self.magic).write_volatile(MAGIC);
+            (&raw mut self.stage).write_volatile(stage);
+        }
+        // 复位前确保写入已完成
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,117 @@
+//! System reset extension test suite.
+
+use crate::scratch::{Scratch, Stage};
+use sbi::SbiRet;
+use sbi_spec::srst::{RESET_REASON_NO_REASON, RESET_TYPE_WARM_REBOOT};
+
+/// System reset extension test cases.
+#[derive(Clone, Debug)]
+pub enum Case {
+    /// Can't proceed test for system reset extension does not exist.
+    NotExist,
+    /// Test begin.
+    Begin,
+    /// Test process for reserved reset types rejected with invalid parameter.
+    ReservedTypeRejected,
+    /// Test process for a reserved reset reason rejected with invalid parameter.
+    ReservedReasonRejected,
+    /// Test process for warm reboot skipped for no persistent scratch area is provided.
+    NoScratch,
+    /// Test process for warm reboot requested; the test continues in the next boot.
+    WarmRebootRequested,
+    /// Test process for the system rebooted on the warm reboot request of the previous boot.
+    WarmRebooted,
+    /// Test failed for the system was reset on a request with a reserved reset reason.
+    ResetWithReservedReason,
+    /// Test failed for an SRST call returned an unexpected value.
+    Failed(&'static str, SbiRet),
+    /// All test cases on system reset extension has passed.
+    Pass,
+}
+
+/// First and last reset type of the reserved range `0x3..=0xefff_ffff`.
+const RESERVED_TYPES: [u32; 2] = [0x3, 0xefff_ffff];
+/// First reset reason of the reserved range `0x2..=0xdfff_ffff`.
+const RESERVED_REASON: u32 = 0x2;
+
+/// Reset type or reason passed as a raw value.
+struct Raw(u32);
+
+impl sbi::ResetType for Raw {
+    #[inline]
+    fn raw(&self) -> u32 {
+        self.0
+    }
+}
+
+impl sbi::ResetReason for Raw {
+    #[inline]
+    fn raw(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Test system reset extension.
+///
+/// Reserved parameters are tested without resetting the system. If `scratch` is provided,
+/// the system is then warm rebooted; call this function again in the next boot with the same
+/// scratch area to finish the test.
+pub fn test(mut scratch: Option<&mut Scratch>, mut f: impl FnMut(Case)) {
+    if sbi::probe_extension(sbi::Reset).is_unavailable() {
+        f(Case::NotExist);
+        return;
+    }
+    f(Case::Begin);
+
+    // 上次启动请求了复位，本次启动检查复位的结果
+    if let Some(scratch) = scratch.as_deref_mut() {
+        match scratch.stage() {
+            Stage::WarmReboot => {
+                scratch.set_stage(Stage::Idle);
+                f(Case::WarmRebooted);
+                f(Case::Pass);
+                return;
+            }
+            Stage::ReservedReason => {
+                scratch.set_stage(Stage::Idle);
+                f(Case::ResetWithReservedReason);
+                return;
+            }
+            Stage::Suspend | Stage::Idle => {}
+        }
+    }
+    // 其他测试的往返过程尚未结束，不能覆盖其状态
+    let scratch = scratch.filter(|scratch| scratch.stage() == Stage::Idle);
+
+    // 保留的复位类型必须被拒绝
+    for reset_type in RESERVED_TYPES {
+        let ret = sbi::system_reset(Raw(reset_type), Raw(RESET_REASON_NO_REASON));
+        if ret != SbiRet::invalid_param() {
+            f(Case::Failed("reset with a reserved type", ret));
+            return;
+        }
+    }
+    f(Case::ReservedTypeRejected);
+
+    let Some(scratch) = scratch else {
+        f(Case::NoScratch);
+        f(Case::Pass);
+        return;
+    };
+    // 保留的复位原因必须被拒绝；如果系统被复位，下次启动时报告失败
+    scratch.set_stage(Stage::ReservedReason);
+    let ret = sbi::system_reset(Raw(RESET_TYPE_WARM_REBOOT), Raw(RESERVED_REASON));
+    scratch.set_stage(Stage::Idle);
+    if ret != SbiRet::invalid_param() {
+        f(Case::Failed("warm reboot with a reserved reason", ret));
+        return;
+    }
+    f(Case::ReservedReasonRejected);
+
+    // 热重启，下次启动时继续测试
+    scratch.set_stage(Stage::WarmReboot);
+    f(Case::WarmRebootRequested);
+    let ret = sbi::system_reset(sbi::WarmReboot, sbi::NoReason);
+    scratch.set_stage(Stage::Idle);
+    f(Case::Failed("warm reboot", ret));
+}
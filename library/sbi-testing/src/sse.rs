@@ -0,0 +1,293 @@
+//! Supervisor software events extension test suite.
+
+use crate::thread::Thread;
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use riscv::{
+    interrupt::supervisor::{Exception, Interrupt},
+    register::{
+        scause::{self, Trap},
+        sie::{self, Sie},
+    },
+};
+use sbi::{SbiRet, SharedPtr, SseContext, SseEvent};
+use sbi_spec::sse::{attr_id, event_id, event_state};
+
+/// Supervisor software events extension test cases.
+#[derive(Clone, Debug)]
+pub enum Case {
+    /// Can't proceed test for supervisor software events extension does not exist.
+    NotExist,
+    /// Test begin.
+    Begin,
+    /// Test process for `sse_complete` outside of any event handler returned success.
+    CompleteOutsideHandler,
+    /// Test process for the local software injected event delivered to its handler
+    /// and the interrupted context resumed.
+    Delivered,
+    /// Test process for the event returned to `ENABLED` state after the handler completed.
+    Completed,
+    /// Test process for a higher priority event injected from a handler preempted the handler.
+    Preempted,
+    /// Test process for a lower priority event injected from a handler ran after the handler completed.
+    Deferred,
+    /// Test process for priority tests skipped for the global software injected event is not supported.
+    GlobalEventNotSupported,
+    /// Test failed for the injected event was not delivered.
+    NotDelivered,
+    /// Test failed for events were handled in a wrong order.
+    WrongOrder(&'static str),
+    /// Test failed for the local event was handled on another hart.
+    WrongHart(usize),
+    /// Test failed for the event is not in the expected state.
+    UnexpectedState {
+        /// Expected event state.
+        expected: usize,
+        /// Event state read from the `STATUS` attribute.
+        found: usize,
+    },
+    /// Test failed for unexpected trap occurred upon tests.
+    UnexpectedTrap(Trap<usize, usize>),
+    /// Test failed for an SSE call returned an unexpected value.
+    Failed(&'static str, SbiRet),
+    /// All test cases on supervisor software events extension has passed.
+    Pass,
+}
+
+/// Test supervisor software events extension.
+pub fn test(hart_id: usize, mut f: impl FnMut(Case)) {
+    if sbi::probe_extension(sbi::Sse).is_unavailable() {
+        f(Case::NotExist);
+        return;
+    }
+    f(Case::Begin);
+    match run(hart_id, &mut f) {
+        Ok(()) => f(Case::Pass),
+        Err(case) => f(case),
+    }
+}
+
+fn run(hart_id: usize, f: &mut impl FnMut(Case)) -> Result<(), Case> {
+    const LOCAL: u32 = event_id::LOCAL_SOFTWARE_INJECTED;
+    const GLOBAL: u32 = event_id::GLOBAL_SOFTWARE_INJECTED;
+
+    let ret = sbi::sse_hart_unmask();
+    if !ret.is_ok() && ret != SbiRet::already_started() {
+        return Err(Case::Failed("hart unmask", ret));
+    }
+    // 没有事件处于 RUNNING 状态时，complete 什么也不做
+    let ret = sbi::sse_complete();
+    if !ret.is_ok() {
+        return Err(Case::Failed("complete", ret));
+    }
+    f(Case::CompleteOutsideHandler);
+
+    check_state(LOCAL, event_state::UNUSED)?;
+    let event = &raw mut LOCAL_EVENT;
+    let event: &'static mut SseEvent = unsafe { &mut *event };
+    let mut local = event
+        .register(LOCAL, handler)
+        .map_err(|(_, error)| Case::Failed("register", error.into()))?;
+    check_state(LOCAL, event_state::REGISTERED)?;
+    // 数值越小优先级越高；本地事件设为较低优先级，与默认的按事件号排序相反
+    write_attr(LOCAL, attr_id::PRIORITY, 1)?;
+    local
+        .enable()
+        .map_err(|error| Case::Failed("enable", error.into()))?;
+    check_state(LOCAL, event_state::ENABLED)?;
+
+    // 注入本地事件，处理函数返回后应回到被打断的线程
+    reset_log();
+    inject_in_thread(LOCAL, hart_id)?;
+    match take_log() {
+        [0, 0, 0, 0] => return Err(Case::NotDelivered),
+        [LOCAL_BEGIN, LOCAL_END, 0, 0] => {}
+        _ => return Err(Case::WrongOrder("single event")),
+    }
+    let handler_hart = HANDLER_HART.load(Ordering::Relaxed);
+    if handler_hart != hart_id {
+        return Err(Case::WrongHart(handler_hart));
+    }
+    let running = RUNNING_STATE.load(Ordering::Relaxed);
+    if running != event_state::RUNNING {
+        return Err(Case::UnexpectedState {
+            expected: event_state::RUNNING,
+            found: running,
+        });
+    }
+    f(Case::Delivered);
+    check_state(LOCAL, event_state::ENABLED)?;
+    f(Case::Completed);
+
+    let event = &raw mut GLOBAL_EVENT;
+    let event: &'static mut SseEvent = unsafe { &mut *event };
+    let mut global = match event.register(GLOBAL, handler) {
+        Ok(global) => global,
+        Err((_, error)) => {
+            let ret = SbiRet::from(error);
+            if ret == SbiRet::not_supported() {
+                f(Case::GlobalEventNotSupported);
+                return unregister(local);
+            }
+            return Err(Case::Failed("register", ret));
+        }
+    };
+    write_attr(GLOBAL, attr_id::PREFERRED_HART, hart_id)?;
+    write_attr(GLOBAL, attr_id::PRIORITY, 0)?;
+    global
+        .enable()
+        .map_err(|error| Case::Failed("enable", error.into()))?;
+
+    // 处理函数中注入更高优先级的事件，应立即抢占
+    reset_log();
+    NESTED.store(GLOBAL as _, Ordering::Relaxed);
+    inject_in_thread(LOCAL, hart_id)?;
+    if take_log() != [LOCAL_BEGIN, GLOBAL_BEGIN, GLOBAL_END, LOCAL_END] {
+        return Err(Case::WrongOrder("preemption"));
+    }
+    f(Case::Preempted);
+
+    // 处理函数中注入更低优先级的事件，应在当前事件完成后处理
+    reset_log();
+    NESTED.store(LOCAL as _, Ordering::Relaxed);
+    inject_in_thread(GLOBAL, hart_id)?;
+    if take_log() != [GLOBAL_BEGIN, GLOBAL_END, LOCAL_BEGIN, LOCAL_END] {
+        return Err(Case::WrongOrder("deferral"));
+    }
+    f(Case::Deferred);
+
+    global
+        .unregister()
+        .map_err(|(_, error)| Case::Failed("unregister", error.into()))?;
+    unregister(local)
+}
+
+fn unregister(registration: sbi::SseRegistration) -> Result<(), Case> {
+    let event_id = registration.event_id();
+    registration
+        .unregister()
+        .map_err(|(_, error)| Case::Failed("unregister", error.into()))?;
+    check_state(event_id, event_state::UNUSED)
+}
+
+static mut LOCAL_EVENT: SseEvent = SseEvent::new();
+static mut GLOBAL_EVENT: SseEvent = SseEvent::new();
+
+const NO_EVENT: usize = usize::MAX;
+/// 处理函数中要注入的事件
+static NESTED: AtomicUsize = AtomicUsize::new(NO_EVENT);
+static HANDLER_HART: AtomicUsize = AtomicUsize::new(usize::MAX);
+static RUNNING_STATE: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+const LOCAL_BEGIN: u8 = 1;
+const LOCAL_END: u8 = 2;
+const GLOBAL_BEGIN: u8 = 3;
+const GLOBAL_END: u8 = 4;
+/// 事件处理顺序记录
+static LOG: [AtomicU8; 4] = [const { AtomicU8::new(0) }; 4];
+static LOG_LEN: AtomicUsize = AtomicUsize::new(0);
+
+fn handler(ctx: &mut SseContext) {
+    let global = ctx.event_id() & event_id::GLOBAL_BIT != 0;
+    let (begin, end) = if global {
+        (GLOBAL_BEGIN, GLOBAL_END)
+    } else {
+        (LOCAL_BEGIN, LOCAL_END)
+    };
+    record(begin);
+    HANDLER_HART.store(ctx.hart_id(), Ordering::Relaxed);
+    let state =
+        read_attr(ctx.event_id(), attr_id::STATUS).map_or(usize::MAX, |status| status & 0b11);
+    RUNNING_STATE.store(state, Ordering::Relaxed);
+    let nested = NESTED.swap(NO_EVENT, Ordering::Relaxed);
+    if nested != NO_EVENT {
+        let _ = sbi::sse_inject(nested as _, ctx.hart_id());
+    }
+    record(end);
+}
+
+fn record(entry: u8) {
+    let idx = LOG_LEN.fetch_add(1, Ordering::Relaxed);
+    if let Some(slot) = LOG.get(idx) {
+        slot.store(entry, Ordering::Relaxed);
+    }
+}
+
+fn reset_log() {
+    for slot in &LOG {
+        slot.store(0, Ordering::Relaxed);
+    }
+    LOG_LEN.store(0, Ordering::Relaxed);
+}
+
+fn take_log() -> [u8; 4] {
+    let log = LOG.each_ref().map(|slot| slot.load(Ordering::Relaxed));
+    reset_log();
+    log
+}
+
+/// 线程中注入调用的返回值
+static INJECT_RET: [AtomicUsize; 2] = [const { AtomicUsize::new(0) }; 2];
+
+/// 在线程中注入事件，事件处理完成后线程执行 `unimp` 陷入
+fn inject_in_thread(event_id: u32, hart_id: usize) -> Result<(), Case> {
+    fn inject(event_id: usize, hart_id: usize) -> ! {
+        let ret = sbi::sse_inject(event_id as _, hart_id);
+        INJECT_RET[0].store(ret.error, Ordering::Relaxed);
+        INJECT_RET[1].store(ret.value, Ordering::Relaxed);
+        unsafe { core::arch::asm!("unimp", options(noreturn, nomem)) };
+    }
+
+    let mut stack = [0usize; 64];
+    let mut thread = Thread::new(inject as *const () as _, stack.as_mut_ptr_range().end as _);
+    *thread.a_mut(0) = event_id as _;
+    *thread.a_mut(1) = hart_id;
+    unsafe {
+        // 屏蔽所有中断，只由注入的事件打断线程
+        let sie = sie::read();
+        sie::write(Sie::from_bits(0));
+        thread.execute();
+        sie::write(sie);
+    }
+    let trap = scause::read().cause();
+    match trap.try_into::<Interrupt, Exception>() {
+        Ok(Trap::Exception(Exception::IllegalInstruction)) => {}
+        _ => return Err(Case::UnexpectedTrap(trap)),
+    }
+    let ret = SbiRet {
+        error: INJECT_RET[0].load(Ordering::Relaxed),
+        value: INJECT_RET[1].load(Ordering::Relaxed),
+    };
+    if ret.is_ok() {
+        Ok(())
+    } else {
+        Err(Case::Failed("inject", ret))
+    }
+}
+
+fn check_state(event_id: u32, expected: usize) -> Result<(), Case> {
+    let found = read_attr(event_id, attr_id::STATUS)
+        .map_err(|ret| Case::Failed("read STATUS", ret))?
+        & 0b11;
+    if found == expected {
+        Ok(())
+    } else {
+        Err(Case::UnexpectedState { expected, found })
+    }
+}
+
+fn read_attr(event_id: u32, attr_id: u32) -> Result<usize, SbiRet> {
+    let mut value = 0usize;
+    let output = SharedPtr::new(&raw mut value as usize, 0);
+    let ret = sbi::sse_read_attrs(event_id, attr_id, 1, output);
+    if ret.is_ok() { Ok(value) } else { Err(ret) }
+}
+
+fn write_attr(event_id: u32, attr_id: u32, value: usize) -> Result<(), Case> {
+    let input = SharedPtr::new(&raw const value as usize, 0);
+    let ret = sbi::sse_write_attrs(event_id, attr_id, 1, input);
+    if ret.is_ok() {
+        Ok(())
+    } else {
+        Err(Case::Failed("write attribute", ret))
+    }
+}
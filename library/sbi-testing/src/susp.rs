@@ -0,0 +1,231 @@
+//! System suspend extension test suite.
+
+use crate::{
+    scratch::{Scratch, Stage},
+    thread::Thread,
+};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use riscv::{
+    interrupt::supervisor::{Exception, Interrupt},
+    register::{
+        scause::{self, Trap},
+        sie::{self, Sie},
+        time,
+    },
+};
+use sbi::SbiRet;
+
+/// System suspend extension test cases.
+#[derive(Clone, Debug)]
+pub enum Case {
+    /// Can't proceed test for system suspend extension does not exist.
+    NotExist,
+    /// Test begin.
+    Begin,
+    /// Test process for reserved sleep types rejected with invalid parameter.
+    ReservedTypeRejected,
+    /// Test process for suspend to RAM skipped for no persistent scratch area is provided.
+    NoScratch,
+    /// Test process for suspend to RAM skipped for it is not supported by the platform.
+    NotSupported,
+    /// Test process for suspend to RAM skipped for its entry criteria are not satisfied,
+    /// e.g. other harts are not stopped.
+    Denied,
+    /// Test process for the system suspended to RAM and resumed at the resume address.
+    Resumed,
+    /// Test failed for the system was reset instead of resumed in the previous boot.
+    ResetOnSuspend,
+    /// Test failed for the system resumed with unexpected arguments.
+    WrongResumeArgs {
+        /// Hart ID in `a0` on resume.
+        hart_id: usize,
+        /// Opaque value in `a1` on resume.
+        opaque: usize,
+    },
+    /// Test failed for unexpected trap occurred upon tests.
+    UnexpectedTrap(Trap<usize, usize>),
+    /// Test failed for a SUSP call returned an unexpected value.
+    Failed(&'static str, SbiRet),
+    /// All test cases on system suspend extension has passed.
+    Pass,
+}
+
+/// First and last sleep type of the reserved range `0x1..=0x7fff_ffff`.
+const RESERVED_TYPES: [u32; 2] = [0x1, 0x7fff_ffff];
+/// Opaque value passed to the resume address.
+const OPAQUE: usize = 0x5355_5350;
+
+/// Sleep type passed as a raw value.
+struct Raw(u32);
+
+impl sbi::SleepType for Raw {
+    #[inline]
+    fn raw(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Test system suspend extension on the current hart.
+///
+/// Reserved sleep types are tested without suspending the system. If `scratch` is provided,
+/// the system is then suspended to RAM and woken up by a timer after `delay` ticks; all other
+/// harts must be stopped.
+pub fn test(
+    hart_id: usize,
+    delay: u64,
+    mut scratch: Option<&mut Scratch>,
+    mut f: impl FnMut(Case),
+) {
+    if sbi::probe_extension(sbi::Suspend).is_unavailable() {
+        f(Case::NotExist);
+        return;
+    }
+    f(Case::Begin);
+
+    // 上次启动休眠后没有恢复，而是被复位
+    if let Some(scratch) = scratch.as_deref_mut()
+        && scratch.stage() == Stage::Suspend
+    {
+        scratch.set_stage(Stage::Idle);
+        f(Case::ResetOnSuspend);
+        return;
+    }
+    // 其他测试的往返过程尚未结束，不能覆盖其状态
+    let scratch = scratch.filter(|scratch| scratch.stage() == Stage::Idle);
+
+    // 保留的休眠类型必须被拒绝
+    for sleep_type in RESERVED_TYPES {
+        let ret = sbi::system_suspend(Raw(sleep_type), resume_entry as *const () as _, OPAQUE);
+        if ret != SbiRet::invalid_param() {
+            f(Case::Failed("suspend with a reserved type", ret));
+            return;
+        }
+    }
+    f(Case::ReservedTypeRejected);
+
+    let Some(scratch) = scratch else {
+        f(Case::NoScratch);
+        f(Case::Pass);
+        return;
+    };
+    scratch.set_stage(Stage::Suspend);
+    let result = suspend_in_thread(delay);
+    scratch.set_stage(Stage::Idle);
+    match result {
+        Ok(()) => {}
+        Err(Case::Failed(_, ret)) if ret == SbiRet::not_supported() => {
+            f(Case::NotSupported);
+            f(Case::Pass);
+            return;
+        }
+        Err(Case::Failed(_, ret)) if ret == SbiRet::denied() => {
+            f(Case::Denied);
+            f(Case::Pass);
+            return;
+        }
+        Err(case) => {
+            f(case);
+            return;
+        }
+    }
+    let (resumed_hart, opaque) = (
+        RESUMED_HART.load(Ordering::Relaxed),
+        RESUMED_OPAQUE.load(Ordering::Relaxed),
+    );
+    if resumed_hart != hart_id || opaque != OPAQUE {
+        f(Case::WrongResumeArgs {
+            hart_id: resumed_hart,
+            opaque,
+        });
+        return;
+    }
+    f(Case::Resumed);
+    f(Case::Pass);
+}
+
+/// 休眠调用返回时的返回值
+static SUSPEND_RET: [AtomicUsize; 2] = [const { AtomicUsize::new(0) }; 2];
+/// 是否从恢复地址恢复执行
+static RESUMED: AtomicBool = AtomicBool::new(false);
+static RESUMED_HART: AtomicUsize = AtomicUsize::new(usize::MAX);
+static RESUMED_OPAQUE: AtomicUsize = AtomicUsize::new(0);
+/// 线程上下文和陷入入口，恢复后重新设置以回到调度器
+static THREAD: AtomicUsize = AtomicUsize::new(0);
+static TRAP_ENTRY: AtomicUsize = AtomicUsize::new(0);
+
+/// 在线程中休眠，从恢复地址恢复后线程执行 `unimp` 陷入
+fn suspend_in_thread(delay: u64) -> Result<(), Case> {
+    fn suspend(resume_addr: usize, opaque: usize) -> ! {
+        let stvec: usize;
+        unsafe { core::arch::asm!("csrr {}, stvec", out(reg) stvec) };
+        TRAP_ENTRY.store(stvec, Ordering::Relaxed);
+        let ret = sbi::system_suspend(sbi::SuspendToRam, resume_addr, opaque);
+        SUSPEND_RET[0].store(ret.error, Ordering::Relaxed);
+        SUSPEND_RET[1].store(ret.value, Ordering::Relaxed);
+        unsafe { core::arch::asm!("unimp", options(noreturn, nomem)) };
+    }
+
+    RESUMED.store(false, Ordering::Relaxed);
+    let mut stack = [0usize; 64];
+    let mut thread = Thread::new(suspend as *const () as _, stack.as_mut_ptr_range().end as _);
+    *thread.a_mut(0) = resume_entry as *const () as _;
+    *thread.a_mut(1) = OPAQUE;
+    THREAD.store(&raw mut thread as _, Ordering::Relaxed);
+    unsafe {
+        // 只开启时钟中断作为唤醒源，中断不会在线程中处理
+        let sie = sie::read();
+        let mut wake = Sie::from_bits(0);
+        wake.set_stimer(true);
+        sie::write(wake);
+        sbi::set_timer(time::read64() + delay);
+        thread.execute();
+        sbi::set_timer(u64::MAX);
+        sie::write(sie);
+    }
+    let trap = scause::read().cause();
+    match trap.try_into::<Interrupt, Exception>() {
+        Ok(Trap::Exception(Exception::IllegalInstruction)) => {}
+        _ => return Err(Case::UnexpectedTrap(trap)),
+    }
+    if RESUMED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let ret = SbiRet {
+        error: SUSPEND_RET[0].load(Ordering::Relaxed),
+        value: SUSPEND_RET[1].load(Ordering::Relaxed),
+    };
+    Err(Case::Failed("suspend to RAM", ret))
+}
+
+const RESUME_STACK_SIZE: usize = 512;
+static mut RESUME_STACK: [u128; RESUME_STACK_SIZE / 16] = [0; RESUME_STACK_SIZE / 16];
+
+/// 恢复地址入口
+#[unsafe(naked)]
+unsafe extern "C" fn resume_entry(hart_id: usize, opaque: usize) -> ! {
+    core::arch::naked_asm!(
+        "la   sp, {stack} + {stack_size}",
+        "j    {resume_main}",
+        stack       =   sym RESUME_STACK,
+        stack_size  = const RESUME_STACK_SIZE,
+        resume_main =   sym resume_main,
+    )
+}
+
+extern "C" fn resume_main(hart_id: usize, opaque: usize) -> ! {
+    RESUMED_HART.store(hart_id, Ordering::Relaxed);
+    RESUMED_OPAQUE.store(opaque, Ordering::Relaxed);
+    RESUMED.store(true, Ordering::Relaxed);
+    // 恢复后除 a0、a1 外的状态未定义，重新设置陷入入口和线程上下文，再陷入以回到调度器
+    unsafe {
+        core::arch::asm!(
+            "csrw sie, zero",
+            "csrw stvec, {stvec}",
+            "csrw sscratch, {thread}",
+            "unimp",
+            stvec  = in(reg) TRAP_ENTRY.load(Ordering::Relaxed),
+            thread = in(reg) THREAD.load(Ordering::Relaxed),
+            options(noreturn, nomem),
+        )
+    };
+}
@@ -0,0 +1,44 @@
+use crate::{Machine, current_hartid};
+use rustsbi::{Ipi, Timer};
+use sbi_spec::binary::{HartMask, SbiRet};
+use std::sync::Arc;
+
+/// Simulated inter-processor interrupt and timer extensions.
+///
+/// `SimClint` implements the IPI extension by raising machine software interrupts on target harts,
+/// and the Timer extension by programming the timer compare value of the calling hart, like
+/// a firmware running over a CLINT or ACLINT device does.
+#[derive(Clone)]
+pub struct SimClint {
+    machine: Arc<Machine>,
+}
+
+impl SimClint {
+    /// Create simulated IPI and timer extensions over `machine`.
+    #[inline]
+    pub fn new(machine: Arc<Machine>) -> Self {
+        Self { machine }
+    }
+}
+
+impl Ipi for SimClint {
+    #[inline]
+    fn send_ipi(&self, hart_mask: HartMask) -> SbiRet {
+        match self.machine.harts_in(hart_mask) {
+            Ok(harts) => {
+                for hartid in harts {
+                    self.machine.set_msip(hartid);
+                }
+                SbiRet::success(0)
+            }
+            Err(error) => error,
+        }
+    }
+}
+
+impl Timer for SimClint {
+    #[inline]
+    fn set_timer(&self, stime_value: u64) {
+        self.machine.set_mtimecmp(current_hartid(), stime_value);
+    }
+}
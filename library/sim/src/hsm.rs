@@ -0,0 +1,88 @@
+use crate::{HartEntry, Machine, current_hartid};
+use rustsbi::Hsm;
+use sbi_spec::{
+    binary::SbiRet,
+    hsm::{hart_state, suspend_type},
+};
+use std::sync::Arc;
+
+/// Simulated Hart State Management extension.
+///
+/// `SimHsm` moves harts between states of the SBI HSM state machine. Transitions which are
+/// completed by the target hart on hardware are completed by the test instead: a started hart
+/// stays `START_PENDING` until [`Machine::try_start`] or [`Machine::wait_start`], and a suspended
+/// hart stays `SUSPENDED` until it receives an interrupt or [`Machine::resume`] is called.
+#[derive(Clone)]
+pub struct SimHsm {
+    machine: Arc<Machine>,
+}
+
+impl SimHsm {
+    /// Create a simulated Hart State Management extension over `machine`.
+    #[inline]
+    pub fn new(machine: Arc<Machine>) -> Self {
+        Self { machine }
+    }
+}
+
+impl Hsm for SimHsm {
+    fn hart_start(&self, hartid: usize, start_addr: usize, opaque: usize) -> SbiRet {
+        if hartid >= self.machine.hart_count() {
+            return SbiRet::invalid_param();
+        }
+        let mut hsm = self.machine.hsm(hartid);
+        if hsm.status != hart_state::STOPPED {
+            return SbiRet::already_available();
+        }
+        hsm.status = hart_state::START_PENDING;
+        hsm.entry = Some(HartEntry {
+            addr: start_addr,
+            opaque,
+        });
+        self.machine.notify_hsm(hartid);
+        SbiRet::success(0)
+    }
+
+    fn hart_stop(&self) -> SbiRet {
+        let hartid = current_hartid();
+        let mut hsm = self.machine.hsm(hartid);
+        if hsm.status != hart_state::STARTED {
+            return SbiRet::failed();
+        }
+        hsm.status = hart_state::STOPPED;
+        hsm.entry = None;
+        self.machine.notify_hsm(hartid);
+        SbiRet::success(0)
+    }
+
+    fn hart_get_status(&self, hartid: usize) -> SbiRet {
+        if hartid >= self.machine.hart_count() {
+            return SbiRet::invalid_param();
+        }
+        SbiRet::success(self.machine.hart_status(hartid))
+    }
+
+    fn hart_suspend(&self, suspend_type: u32, resume_addr: usize, opaque: usize) -> SbiRet {
+        let entry = match suspend_type {
+            suspend_type::RETENTIVE => None,
+            suspend_type::NON_RETENTIVE => Some(HartEntry {
+                addr: resume_addr,
+                opaque,
+            }),
+            // platform specific suspend types
+            0x1000_0000..=0x7FFF_FFFF | 0x9000_0000..=0xFFFF_FFFF => {
+                return SbiRet::not_supported();
+            }
+            _ => return SbiRet::invalid_param(),
+        };
+        let hartid = current_hartid();
+        let mut hsm = self.machine.hsm(hartid);
+        if hsm.status != hart_state::STARTED {
+            return SbiRet::failed();
+        }
+        hsm.status = hart_state::SUSPENDED;
+        hsm.entry = entry;
+        self.machine.notify_hsm(hartid);
+        SbiRet::success(0)
+    }
+}
@@ -0,0 +1,98 @@
+//! Host-side simulation harness for testing RustSBI implementations off-target.
+//!
+//! Testing an SBI implementation usually requires booting it in an emulator together with
+//! a test kernel. This crate simulates a multi-hart RISC-V machine on the host instead, so that
+//! `RustSBI` implementations can be unit tested using `cargo test` on any architecture.
+//!
+//! A [`Machine`] simulates a number of harts, each with a software interrupt pending bit,
+//! a timer compare value, a Hart State Management state and a queue of received remote fences.
+//! SBI calls are delivered to any [`RustSBI`](rustsbi::RustSBI) implementation on behalf of
+//! a simulated hart using [`Machine::ecall`]; during the call, [`current_hartid`] returns the
+//! calling hart. Harts may be simulated by different threads to test concurrent SBI calls.
+//!
+//! The crate also provides extension implementations over the simulated machine, which may be
+//! combined with extensions under test using `#[derive(RustSBI)]`:
+//!
+//! | Structure | Extensions |
+//! |:----------|:-----------|
+//! | [`SimClint`] | IPI and Timer extensions, through simulated software interrupts and timers |
+//! | [`SimHsm`] | Hart State Management extension, through simulated hart states |
+//! | [`SimFence`] | Remote Fence extension, through per-hart fence queues |
+//! | [`SimEnvInfo`] | Machine environment information for the Base extension |
+//!
+//! # Examples
+//!
+//! ```
+//! use rustsbi::RustSBI;
+//! use rustsbi_sim::{HartEntry, Machine, SimClint, SimEnvInfo, SimHsm};
+//! use sbi_spec::{hsm, spi};
+//!
+//! #[derive(RustSBI)]
+//! struct MySBI {
+//!     #[rustsbi(ipi, timer)]
+//!     clint: SimClint,
+//!     hsm: SimHsm,
+//!     info: SimEnvInfo,
+//! }
+//!
+//! let machine = Machine::new(2);
+//! let sbi = MySBI {
+//!     clint: SimClint::new(machine.clone()),
+//!     hsm: SimHsm::new(machine.clone()),
+//!     info: SimEnvInfo::default(),
+//! };
+//!
+//! std::thread::scope(|s| {
+//!     // Hart 1 waits to be started, then sends an IPI to hart 0.
+//!     s.spawn(|| {
+//!         let entry = machine.wait_start(1);
+//!         assert_eq!(entry, HartEntry { addr: 0x8020_0000, opaque: 42 });
+//!         let ret = machine.ecall(&sbi, 1, spi::EID_SPI, spi::SEND_IPI, [0b1, 0, 0, 0, 0, 0]);
+//!         assert!(ret.is_ok());
+//!     });
+//!     // Hart 0 starts hart 1.
+//!     let ret = machine.ecall(&sbi, 0, hsm::EID_HSM, hsm::HART_START, [1, 0x8020_0000, 42, 0, 0, 0]);
+//!     assert!(ret.is_ok());
+//! });
+//!
+//! assert_eq!(machine.hart_status(1), hsm::hart_state::STARTED);
+//! assert!(machine.clear_msip(0));
+//! ```
+
+mod clint;
+mod hsm;
+mod machine;
+mod rfence;
+
+pub use clint::SimClint;
+pub use hsm::SimHsm;
+pub use machine::{HartEntry, Machine, Resume, current_hartid};
+pub use rfence::{FenceRequest, SimFence};
+
+/// Simulated machine environment information.
+///
+/// The Base extension reports these values as IDs of the simulated machine.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SimEnvInfo {
+    /// Vendor ID reported by `get_mvendorid`.
+    pub mvendorid: usize,
+    /// Architecture ID reported by `get_marchid`.
+    pub marchid: usize,
+    /// Implementation ID reported by `get_mimpid`.
+    pub mimpid: usize,
+}
+
+impl rustsbi::EnvInfo for SimEnvInfo {
+    #[inline]
+    fn mvendorid(&self) -> usize {
+        self.mvendorid
+    }
+    #[inline]
+    fn marchid(&self) -> usize {
+        self.marchid
+    }
+    #[inline]
+    fn mimpid(&self) -> usize {
+        self.mimpid
+    }
+}
@@ -0,0 +1,285 @@
+use crate::FenceRequest;
+use rustsbi::RustSBI;
+use sbi_spec::{
+    binary::{HartMask, SbiRet},
+    hsm::hart_state,
+};
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    sync::{
+        Arc, Condvar, Mutex, MutexGuard,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+/// Simulated RISC-V machine with a fixed number of harts.
+///
+/// Each simulated hart holds a machine software interrupt pending bit and a timer compare value
+/// like an ACLINT device, a Hart State Management state, and a queue of remote fence requests
+/// it has received. All harts share a single machine timer.
+///
+/// The machine is shared through an `Arc`, so that simulated extensions and test threads running
+/// as different harts may refer to it at the same time.
+pub struct Machine {
+    harts: Box<[HartState]>,
+    mtime: AtomicU64,
+}
+
+struct HartState {
+    msip: AtomicBool,
+    mtimecmp: AtomicU64,
+    hsm: Mutex<HsmState>,
+    hsm_changed: Condvar,
+    fences: Mutex<VecDeque<FenceRequest>>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct HsmState {
+    pub status: usize,
+    // start address and opaque value of a pending start, or resume address
+    // and opaque value of a non-retentive suspend; `None` for a retentive suspend
+    pub entry: Option<HartEntry>,
+}
+
+/// Entry of a simulated hart when it starts, or resumes from non-retentive suspend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HartEntry {
+    /// Address the hart jumps to in supervisor mode.
+    pub addr: usize,
+    /// Opaque value passed in register `a1`.
+    pub opaque: usize,
+}
+
+/// How a simulated hart resumes from `SUSPENDED` state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Resume {
+    /// The hart resumes from a retentive suspend, continuing after its `hart_suspend` call.
+    Retentive,
+    /// The hart resumes from a non-retentive suspend at the given entry.
+    NonRetentive(HartEntry),
+}
+
+thread_local! {
+    static CURRENT_HART: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Get the ID of the simulated hart the current thread is running as.
+///
+/// # Panics
+///
+/// Panics if it is not called inside [`Machine::ecall`] or [`Machine::run_on`].
+#[inline]
+pub fn current_hartid() -> usize {
+    CURRENT_HART
+        .get()
+        .expect("current thread is not running as a simulated hart")
+}
+
+impl Machine {
+    /// Create a simulated machine with `hart_count` harts.
+    ///
+    /// Hart 0 is the boot hart and starts in `STARTED` state; all other harts are `STOPPED`.
+    pub fn new(hart_count: usize) -> Arc<Machine> {
+        assert!(
+            hart_count > 0,
+            "simulated machine must have at least one hart"
+        );
+        let harts = (0..hart_count)
+            .map(|hartid| HartState {
+                msip: AtomicBool::new(false),
+                mtimecmp: AtomicU64::new(u64::MAX),
+                hsm: Mutex::new(HsmState {
+                    status: if hartid == 0 {
+                        hart_state::STARTED
+                    } else {
+                        hart_state::STOPPED
+                    },
+                    entry: None,
+                }),
+                hsm_changed: Condvar::new(),
+                fences: Mutex::new(VecDeque::new()),
+            })
+            .collect();
+        Arc::new(Machine {
+            harts,
+            mtime: AtomicU64::new(0),
+        })
+    }
+    /// Number of harts on this machine.
+    #[inline]
+    pub fn hart_count(&self) -> usize {
+        self.harts.len()
+    }
+    /// Run `f` on the current thread as simulated hart `hartid`.
+    ///
+    /// Inside `f`, [`current_hartid`] returns `hartid`.
+    pub fn run_on<R>(&self, hartid: usize, f: impl FnOnce() -> R) -> R {
+        assert!(
+            hartid < self.hart_count(),
+            "hart {hartid} does not exist on this simulated machine"
+        );
+        struct Restore(Option<usize>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_HART.set(self.0);
+            }
+        }
+        let _restore = Restore(CURRENT_HART.replace(Some(hartid)));
+        f()
+    }
+    /// Deliver an SBI environment call made by hart `hartid` to the SBI implementation `sbi`.
+    ///
+    /// Unlike on hardware, a successful `hart_stop` or `hart_suspend` call returns to the caller;
+    /// the test should then stop making calls on this hart until it is started or resumed again.
+    #[inline]
+    pub fn ecall<S: RustSBI + ?Sized>(
+        &self,
+        sbi: &S,
+        hartid: usize,
+        extension: usize,
+        function: usize,
+        param: [usize; 6],
+    ) -> SbiRet {
+        self.run_on(hartid, || sbi.handle_ecall(extension, function, param))
+    }
+
+    /// Whether the machine software interrupt of hart `hartid` is pending.
+    #[inline]
+    pub fn msip(&self, hartid: usize) -> bool {
+        self.harts[hartid].msip.load(Ordering::Acquire)
+    }
+    /// Raise the machine software interrupt of hart `hartid`, resuming it if it is suspended.
+    #[inline]
+    pub fn set_msip(&self, hartid: usize) {
+        self.harts[hartid].msip.store(true, Ordering::Release);
+        self.wake(hartid);
+    }
+    /// Clear the machine software interrupt of hart `hartid`, returning whether it was pending.
+    #[inline]
+    pub fn clear_msip(&self, hartid: usize) -> bool {
+        self.harts[hartid].msip.swap(false, Ordering::AcqRel)
+    }
+    /// Current value of the machine timer.
+    #[inline]
+    pub fn mtime(&self) -> u64 {
+        self.mtime.load(Ordering::Acquire)
+    }
+    /// Advance the machine timer by `ticks`, resuming suspended harts whose timer becomes pending.
+    pub fn advance_time(&self, ticks: u64) {
+        self.mtime.fetch_add(ticks, Ordering::AcqRel);
+        for hartid in 0..self.hart_count() {
+            if self.timer_pending(hartid) {
+                self.wake(hartid);
+            }
+        }
+    }
+    /// Timer compare value of hart `hartid`.
+    #[inline]
+    pub fn mtimecmp(&self, hartid: usize) -> u64 {
+        self.harts[hartid].mtimecmp.load(Ordering::Acquire)
+    }
+    /// Set timer compare value of hart `hartid`.
+    #[inline]
+    pub fn set_mtimecmp(&self, hartid: usize, value: u64) {
+        self.harts[hartid].mtimecmp.store(value, Ordering::Release);
+    }
+    /// Whether the timer interrupt of hart `hartid` is pending.
+    #[inline]
+    pub fn timer_pending(&self, hartid: usize) -> bool {
+        self.mtime() >= self.mtimecmp(hartid)
+    }
+
+    /// Hart State Management state of hart `hartid`, as defined in [`sbi_spec::hsm::hart_state`].
+    #[inline]
+    pub fn hart_status(&self, hartid: usize) -> usize {
+        self.hsm(hartid).status
+    }
+    /// Complete a pending start of hart `hartid`, moving it from `START_PENDING` to `STARTED`.
+    ///
+    /// Returns the start address and opaque value provided to `hart_start`, or `None` if
+    /// the hart is not pending to start.
+    pub fn try_start(&self, hartid: usize) -> Option<HartEntry> {
+        let mut hsm = self.hsm(hartid);
+        if hsm.status != hart_state::START_PENDING {
+            return None;
+        }
+        hsm.status = hart_state::STARTED;
+        self.harts[hartid].hsm_changed.notify_all();
+        hsm.entry
+    }
+    /// Block the current thread until hart `hartid` is requested to start, then complete its start.
+    ///
+    /// This is useful for threads simulating secondary harts waiting for `hart_start` calls.
+    pub fn wait_start(&self, hartid: usize) -> HartEntry {
+        let hart = &self.harts[hartid];
+        let mut hsm = hart
+            .hsm_changed
+            .wait_while(self.hsm(hartid), |hsm| {
+                hsm.status != hart_state::START_PENDING
+            })
+            .unwrap();
+        hsm.status = hart_state::STARTED;
+        hart.hsm_changed.notify_all();
+        hsm.entry.expect("start pending hart has a start entry")
+    }
+    /// Resume hart `hartid` from `SUSPENDED` state into `STARTED` state.
+    ///
+    /// Returns `None` if the hart is not suspended.
+    pub fn resume(&self, hartid: usize) -> Option<Resume> {
+        let mut hsm = self.hsm(hartid);
+        if hsm.status != hart_state::SUSPENDED {
+            return None;
+        }
+        hsm.status = hart_state::STARTED;
+        self.harts[hartid].hsm_changed.notify_all();
+        Some(hsm.entry.map_or(Resume::Retentive, Resume::NonRetentive))
+    }
+
+    /// Take all remote fence requests received by hart `hartid`, in the order they were made.
+    #[inline]
+    pub fn take_fences(&self, hartid: usize) -> Vec<FenceRequest> {
+        self.harts[hartid]
+            .fences
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect()
+    }
+
+    /// Get IDs of harts selected by `hart_mask`, or `SbiRet::invalid_param()` if the mask selects
+    /// a hart which does not exist on this machine.
+    pub fn harts_in(&self, hart_mask: HartMask) -> Result<Vec<usize>, SbiRet> {
+        let (_, hart_mask_base) = hart_mask.into_inner();
+        if hart_mask_base == HartMask::IGNORE_MASK {
+            return Ok((0..self.hart_count()).collect());
+        }
+        let harts: Vec<usize> = hart_mask.iter().collect();
+        if harts.iter().any(|&hartid| hartid >= self.hart_count()) {
+            return Err(SbiRet::invalid_param());
+        }
+        Ok(harts)
+    }
+
+    #[inline]
+    pub(crate) fn hsm(&self, hartid: usize) -> MutexGuard<'_, HsmState> {
+        self.harts[hartid].hsm.lock().unwrap()
+    }
+    #[inline]
+    pub(crate) fn notify_hsm(&self, hartid: usize) {
+        self.harts[hartid].hsm_changed.notify_all();
+    }
+    #[inline]
+    pub(crate) fn push_fence(&self, hartid: usize, request: FenceRequest) {
+        self.harts[hartid].fences.lock().unwrap().push_back(request);
+    }
+
+    // a pending interrupt resumes a suspended hart
+    fn wake(&self, hartid: usize) {
+        let mut hsm = self.hsm(hartid);
+        if hsm.status == hart_state::SUSPENDED {
+            hsm.status = hart_state::STARTED;
+            self.harts[hartid].hsm_changed.notify_all();
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Machine;
+use rustsbi::Fence;
+use sbi_spec::binary::{HartMask, SbiRet};
+use std::sync::Arc;
+
+/// Remote fence request received by a simulated hart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FenceRequest {
+    /// `FENCE.I` instruction.
+    FenceI,
+    /// `SFENCE.VMA` instruction covering the given virtual address range.
+    SfenceVma {
+        /// Start of the virtual address range.
+        start_addr: usize,
+        /// Size of the virtual address range.
+        size: usize,
+    },
+    /// `SFENCE.VMA` instruction covering the given virtual address range and ASID.
+    SfenceVmaAsid {
+        /// Start of the virtual address range.
+        start_addr: usize,
+        /// Size of the virtual address range.
+        size: usize,
+        /// Address space identifier.
+        asid: usize,
+    },
+    /// `HFENCE.GVMA` instruction covering the given guest physical address range and VMID.
+    HfenceGvmaVmid {
+        /// Start of the guest physical address range.
+        start_addr: usize,
+        /// Size of the guest physical address range.
+        size: usize,
+        /// Virtual machine identifier.
+        vmid: usize,
+    },
+    /// `HFENCE.GVMA` instruction covering the given guest physical address range for all guests.
+    HfenceGvma {
+        /// Start of the guest physical address range.
+        start_addr: usize,
+        /// Size of the guest physical address range.
+        size: usize,
+    },
+    /// `HFENCE.VVMA` instruction covering the given guest virtual address range and ASID.
+    HfenceVvmaAsid {
+        /// Start of the guest virtual address range.
+        start_addr: usize,
+        /// Size of the guest virtual address range.
+        size: usize,
+        /// Address space identifier.
+        asid: usize,
+    },
+    /// `HFENCE.VVMA` instruction covering the given guest virtual address range.
+    HfenceVvma {
+        /// Start of the guest virtual address range.
+        start_addr: usize,
+        /// Size of the guest virtual address range.
+        size: usize,
+    },
+}
+
+/// Simulated Remote Fence extension.
+///
+/// `SimFence` fans every remote fence request out to the fence queues of all target harts, where
+/// they can be inspected using [`Machine::take_fences`].
+#[derive(Clone)]
+pub struct SimFence {
+    machine: Arc<Machine>,
+}
+
+impl SimFence {
+    /// Create a simulated Remote Fence extension over `machine`.
+    #[inline]
+    pub fn new(machine: Arc<Machine>) -> Self {
+        Self { machine }
+    }
+
+    fn fan_out(&self, hart_mask: HartMask, request: FenceRequest) -> SbiRet {
+        match self.machine.harts_in(hart_mask) {
+            Ok(harts) => {
+                for hartid in harts {
+                    self.machine.push_fence(hartid, request);
+                }
+                SbiRet::success(0)
+            }
+            Err(error) => error,
+        }
+    }
+}
+
+impl Fence for SimFence {
+    #[inline]
+    fn remote_fence_i(&self, hart_mask: HartMask) -> SbiRet {
+        self.fan_out(hart_mask, FenceRequest::FenceI)
+    }
+    #[inline]
+    fn remote_sfence_vma(&self, hart_mask: HartMask, start_addr: usize, size: usize) -> SbiRet {
+        self.fan_out(hart_mask, FenceRequest::SfenceVma { start_addr, size })
+    }
+    #[inline]
+    fn remote_sfence_vma_asid(
+        &self,
+        hart_mask: HartMask,
+        start_addr: usize,
+        size: usize,
+        asid: usize,
+    ) -> SbiRet {
+        self.fan_out(
+            hart_mask,
+            FenceRequest::SfenceVmaAsid {
+                start_addr,
+                size,
+                asid,
+            },
+        )
+    }
+    #[inline]
+    fn remote_hfence_gvma_vmid(
+        &self,
+        hart_mask: HartMask,
+        start_addr: usize,
+        size: usize,
+        vmid: usize,
+    ) -> SbiRet {
+        self.fan_out(
+            hart_mask,
+            FenceRequest::HfenceGvmaVmid {
+                start_addr,
+                size,
+                vmid,
+            },
+        )
+    }
+    #[inline]
+    fn remote_hfence_gvma(&self, hart_mask: HartMask, start_addr: usize, size: usize) -> SbiRet {
+        self.fan_out(hart_mask, FenceRequest::HfenceGvma { start_addr, size })
+    }
+    #[inline]
+    fn remote_hfence_vvma_asid(
+        &self,
+        hart_mask: HartMask,
+        start_addr: usize,
+        size: usize,
+        asid: usize,
+    ) -> SbiRet {
+        self.fan_out(
+            hart_mask,
+            FenceRequest::HfenceVvmaAsid {
+                start_addr,
+                size,
+                asid,
+            },
+        )
+    }
+    #[inline]
+    fn remote_hfence_vvma(&self, hart_mask: HartMask, start_addr: usize, size: usize) -> SbiRet {
+        self.fan_out(hart_mask, FenceRequest::HfenceVvma { start_addr, size })
+    }
+}
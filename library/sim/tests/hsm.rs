@@ -0,0 +1,191 @@
+use rustsbi::RustSBI;
+use rustsbi_sim::{HartEntry, Machine, Resume, SimClint, SimEnvInfo, SimHsm};
+use sbi_spec::{
+    binary::SbiRet,
+    hsm::{self, hart_state, suspend_type},
+    spi,
+};
+use std::sync::{Arc, Barrier};
+
+#[derive(RustSBI)]
+struct SimSBI {
+    #[rustsbi(ipi, timer)]
+    clint: SimClint,
+    hsm: SimHsm,
+    info: SimEnvInfo,
+}
+
+fn sim_sbi(machine: &Arc<Machine>) -> SimSBI {
+    SimSBI {
+        clint: SimClint::new(machine.clone()),
+        hsm: SimHsm::new(machine.clone()),
+        info: SimEnvInfo::default(),
+    }
+}
+
+fn hart_start(machine: &Machine, sbi: &SimSBI, caller: usize, hartid: usize) -> SbiRet {
+    machine.ecall(
+        sbi,
+        caller,
+        hsm::EID_HSM,
+        hsm::HART_START,
+        [hartid, 0x8020_0000, caller, 0, 0, 0],
+    )
+}
+
+#[test]
+fn hart_start_stop_cycle() {
+    let machine = Machine::new(2);
+    let sbi = sim_sbi(&machine);
+    let status = |hartid| {
+        machine.ecall(
+            &sbi,
+            0,
+            hsm::EID_HSM,
+            hsm::HART_GET_STATUS,
+            [hartid, 0, 0, 0, 0, 0],
+        )
+    };
+    assert_eq!(status(0), SbiRet::success(hart_state::STARTED));
+    assert_eq!(status(1), SbiRet::success(hart_state::STOPPED));
+    assert_eq!(status(2), SbiRet::invalid_param());
+    assert_eq!(hart_start(&machine, &sbi, 0, 2), SbiRet::invalid_param());
+    assert_eq!(
+        hart_start(&machine, &sbi, 0, 0),
+        SbiRet::already_available()
+    );
+
+    assert_eq!(machine.try_start(1), None);
+    assert!(hart_start(&machine, &sbi, 0, 1).is_ok());
+    assert_eq!(status(1), SbiRet::success(hart_state::START_PENDING));
+    assert_eq!(
+        hart_start(&machine, &sbi, 0, 1),
+        SbiRet::already_available()
+    );
+    assert_eq!(
+        machine.try_start(1),
+        Some(HartEntry {
+            addr: 0x8020_0000,
+            opaque: 0
+        })
+    );
+    assert_eq!(status(1), SbiRet::success(hart_state::STARTED));
+
+    let stop = machine.ecall(&sbi, 1, hsm::EID_HSM, hsm::HART_STOP, [0; 6]);
+    assert!(stop.is_ok());
+    assert_eq!(status(1), SbiRet::success(hart_state::STOPPED));
+    // A stopped hart can be started again
+    assert!(hart_start(&machine, &sbi, 0, 1).is_ok());
+}
+
+#[test]
+fn concurrent_hart_start() {
+    const HARTS: usize = 8;
+    let machine = Machine::new(HARTS);
+    let sbi = sim_sbi(&machine);
+    // All harts except hart 0 are started by hart 0; then every started hart
+    // races with all other ones to start hart 0 after it stops.
+    for hartid in 1..HARTS {
+        assert!(hart_start(&machine, &sbi, 0, hartid).is_ok());
+        machine.try_start(hartid).unwrap();
+    }
+    assert!(
+        machine
+            .ecall(&sbi, 0, hsm::EID_HSM, hsm::HART_STOP, [0; 6])
+            .is_ok()
+    );
+    let barrier = Barrier::new(HARTS - 1);
+    let results: Vec<SbiRet> = std::thread::scope(|s| {
+        let handles: Vec<_> = (1..HARTS)
+            .map(|hartid| {
+                let (machine, sbi, barrier) = (&machine, &sbi, &barrier);
+                s.spawn(move || {
+                    barrier.wait();
+                    hart_start(machine, sbi, hartid, 0)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert_eq!(results.iter().filter(|ret| ret.is_ok()).count(), 1);
+    assert!(
+        results
+            .iter()
+            .filter(|ret| ret.is_err())
+            .all(|ret| *ret == SbiRet::already_available())
+    );
+    let winner = results.iter().position(|ret| ret.is_ok()).unwrap() + 1;
+    assert_eq!(machine.try_start(0).map(|entry| entry.opaque), Some(winner));
+}
+
+#[test]
+fn secondary_harts_wait_for_start() {
+    const HARTS: usize = 4;
+    let machine = Machine::new(HARTS);
+    let sbi = sim_sbi(&machine);
+    std::thread::scope(|s| {
+        for hartid in 1..HARTS {
+            let (machine, sbi) = (&machine, &sbi);
+            s.spawn(move || {
+                let entry = machine.wait_start(hartid);
+                assert_eq!(entry.addr, 0x8020_0000);
+                let ret = machine.ecall(sbi, hartid, hsm::EID_HSM, hsm::HART_STOP, [0; 6]);
+                assert!(ret.is_ok());
+            });
+        }
+        for hartid in 1..HARTS {
+            assert!(hart_start(&machine, &sbi, 0, hartid).is_ok());
+        }
+    });
+    for hartid in 1..HARTS {
+        assert_eq!(machine.hart_status(hartid), hart_state::STOPPED);
+    }
+}
+
+#[test]
+fn suspend_and_resume() {
+    let machine = Machine::new(2);
+    let sbi = sim_sbi(&machine);
+    assert!(hart_start(&machine, &sbi, 0, 1).is_ok());
+    machine.try_start(1).unwrap();
+    let suspend = |suspend_type: u32, resume_addr| {
+        machine.ecall(
+            &sbi,
+            1,
+            hsm::EID_HSM,
+            hsm::HART_SUSPEND,
+            [suspend_type as usize, resume_addr, 7, 0, 0, 0],
+        )
+    };
+    assert_eq!(suspend(1, 0), SbiRet::invalid_param());
+    assert_eq!(suspend(0x1000_0000, 0), SbiRet::not_supported());
+
+    // Retentive suspend, resumed by an IPI
+    assert!(suspend(suspend_type::RETENTIVE, 0).is_ok());
+    assert_eq!(machine.hart_status(1), hart_state::SUSPENDED);
+    let ipi = machine.ecall(&sbi, 0, spi::EID_SPI, spi::SEND_IPI, [0b10, 0, 0, 0, 0, 0]);
+    assert!(ipi.is_ok());
+    assert_eq!(machine.hart_status(1), hart_state::STARTED);
+    assert!(machine.clear_msip(1));
+
+    // Non-retentive suspend, resumed by the test
+    assert!(suspend(suspend_type::NON_RETENTIVE, 0x8030_0000).is_ok());
+    assert_eq!(
+        machine.resume(1),
+        Some(Resume::NonRetentive(HartEntry {
+            addr: 0x8030_0000,
+            opaque: 7
+        }))
+    );
+    assert_eq!(machine.resume(1), None);
+
+    // Retentive suspend, resumed by a timer interrupt
+    let set_timer = machine.ecall(&sbi, 1, 0x54494D45, 0, [100, 0, 0, 0, 0, 0]);
+    assert!(set_timer.is_ok());
+    assert!(suspend(suspend_type::RETENTIVE, 0).is_ok());
+    machine.advance_time(99);
+    assert_eq!(machine.hart_status(1), hart_state::SUSPENDED);
+    machine.advance_time(1);
+    assert!(machine.timer_pending(1));
+    assert_eq!(machine.hart_status(1), hart_state::STARTED);
+}
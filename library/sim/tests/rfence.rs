@@ -0,0 +1,117 @@
+use rustsbi::RustSBI;
+use rustsbi_sim::{FenceRequest, Machine, SimClint, SimEnvInfo, SimFence, current_hartid};
+use sbi_spec::{binary::SbiRet, rfnc, spi};
+
+#[derive(RustSBI)]
+struct SimSBI {
+    #[rustsbi(ipi, timer)]
+    clint: SimClint,
+    fence: SimFence,
+    info: SimEnvInfo,
+}
+
+#[test]
+fn rfence_fan_out() {
+    let machine = Machine::new(4);
+    let sbi = SimSBI {
+        clint: SimClint::new(machine.clone()),
+        fence: SimFence::new(machine.clone()),
+        info: SimEnvInfo::default(),
+    };
+    // hart_mask = 0b101, hart_mask_base = 1: harts 1 and 3
+    let ret = machine.ecall(
+        &sbi,
+        0,
+        rfnc::EID_RFNC,
+        rfnc::REMOTE_SFENCE_VMA_ASID,
+        [0b101, 1, 0x1000, 0x2000, 5, 0],
+    );
+    assert!(ret.is_ok());
+    let request = FenceRequest::SfenceVmaAsid {
+        start_addr: 0x1000,
+        size: 0x2000,
+        asid: 5,
+    };
+    assert_eq!(machine.take_fences(0), []);
+    assert_eq!(machine.take_fences(1), [request]);
+    assert_eq!(machine.take_fences(2), []);
+    assert_eq!(machine.take_fences(3), [request]);
+    assert_eq!(machine.take_fences(3), []);
+
+    // hart_mask_base = -1: all harts
+    let ret = machine.ecall(
+        &sbi,
+        2,
+        rfnc::EID_RFNC,
+        rfnc::REMOTE_FENCE_I,
+        [0, usize::MAX, 0, 0, 0, 0],
+    );
+    assert!(ret.is_ok());
+    for hartid in 0..4 {
+        assert_eq!(machine.take_fences(hartid), [FenceRequest::FenceI]);
+    }
+
+    // Harts outside of the machine
+    let ret = machine.ecall(
+        &sbi,
+        0,
+        rfnc::EID_RFNC,
+        rfnc::REMOTE_FENCE_I,
+        [0b11, 3, 0, 0, 0, 0],
+    );
+    assert_eq!(ret, SbiRet::invalid_param());
+    assert_eq!(machine.take_fences(3), []);
+    let ret = machine.ecall(&sbi, 0, spi::EID_SPI, spi::SEND_IPI, [0b1, 4, 0, 0, 0, 0]);
+    assert_eq!(ret, SbiRet::invalid_param());
+}
+
+#[test]
+fn concurrent_rfence_and_timer() {
+    const HARTS: usize = 4;
+    const ROUNDS: usize = 100;
+    let machine = Machine::new(HARTS);
+    let sbi = SimSBI {
+        clint: SimClint::new(machine.clone()),
+        fence: SimFence::new(machine.clone()),
+        info: SimEnvInfo::default(),
+    };
+    std::thread::scope(|s| {
+        for hartid in 0..HARTS {
+            let (machine, sbi) = (&machine, &sbi);
+            s.spawn(move || {
+                for round in 0..ROUNDS {
+                    let ret = machine.ecall(
+                        sbi,
+                        hartid,
+                        rfnc::EID_RFNC,
+                        rfnc::REMOTE_SFENCE_VMA,
+                        [0, usize::MAX, hartid, round, 0, 0],
+                    );
+                    assert!(ret.is_ok());
+                }
+                // Timer extension programs the timer of the calling hart only
+                let ret = machine.ecall(sbi, hartid, 0x54494D45, 0, [hartid, 0, 0, 0, 0, 0]);
+                assert!(ret.is_ok());
+                machine.run_on(hartid, || assert_eq!(current_hartid(), hartid));
+            });
+        }
+    });
+    for hartid in 0..HARTS {
+        let fences = machine.take_fences(hartid);
+        assert_eq!(fences.len(), HARTS * ROUNDS);
+        // Requests from each hart arrive in order
+        for sender in 0..HARTS {
+            let sizes: Vec<usize> = fences
+                .iter()
+                .filter_map(|request| match *request {
+                    FenceRequest::SfenceVma { start_addr, size } if start_addr == sender => {
+                        Some(size)
+                    }
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(sizes, (0..ROUNDS).collect::<Vec<_>>());
+        }
+        assert_eq!(machine.mtimecmp(hartid), hartid as u64);
+    }
+}
@@ -56,7 +56,7 @@ fn is_work_hart(_dynamic_info_addr: usize) -> bool {
     }
 }
 
-use alloc::{format, vec};
+use alloc::{format, vec, vec::Vec};
 use core::arch::asm;
 use core::ops::Range;
 
@@ -101,12 +101,26 @@ impl BootInfo {
     }
 
     /// Returns the next-stage handoff; `opaque` carries the unpatched
-    /// device tree address. Must be called after the console is up:
-    /// prints and stops on invalid `DynamicInfo`.
+    /// device tree address. Must be called after platform initialization:
+    /// prints and stops on invalid `DynamicInfo`, and warns on a next
+    /// address outside every memory region, such as an XIP flash image.
     ///
     /// Transitional: the future machine layer will own the transfer.
     pub fn next_stage(&self) -> NextStage {
         let (next_mode, start_addr) = get_boot_info(self.dynamic_info_addr);
+        if self.is_boot_hart
+            && unsafe {
+                crate::platform::PLATFORM
+                    .info
+                    .memory_region_of(start_addr, 1)
+            }
+            .is_none()
+        {
+            warn!(
+                "Next stage address 0x{:x} is outside every memory region",
+                start_addr
+            );
+        }
         NextStage {
             start_addr,
             next_mode,
@@ -216,8 +230,20 @@ pub fn patch_device_tree(device_tree_ptr: usize) -> usize {
     } else {
         0
     };
+    // Firmware outside every memory region is never handed to the next stage,
+    // so there is nothing to reserve.
+    let in_memory = unsafe {
+        crate::platform::PLATFORM
+            .info
+            .memory_region_of(sbi_start, sbi_end - sbi_start)
+    }
+    .is_some();
 
-    let list = &patches[start_idx..];
+    let list = if in_memory {
+        &patches[start_idx..]
+    } else {
+        &[]
+    };
 
     let patched_length = serde_device_tree::ser::probe_dtb_length(&tree, &list).unwrap();
 
@@ -525,107 +551,165 @@ static mut SBI_END_ADDRESS: usize = 0;
 static mut RODATA_START_ADDRESS: usize = 0;
 static mut RODATA_END_ADDRESS: usize = 0;
 
-pub fn set_pmp(memory_range: &Range<usize>) {
-    unsafe {
-        // [0..memory_range.start] RWX
-        // [memory_range.start..sbi_start] RWX
-        // [sbi_start..sbi_rodata_start] R
-        // [sbi_rodata_start..sbi_rodata_end] NONE
-        // [sbi_rodata_end..sbi_end] R
-        // [sbi_end..memory_range.end] RWX
-        // [memory_range.end..INF] RWX
-        use riscv::register::*;
+/// Returns the memory region holding the firmware image, or the image itself
+/// if no memory region holds it.
+///
+/// Only this region is split around the firmware in PMP; other memory
+/// regions fall in the RWX entries below and above it.
+fn firmware_region(memory: &[Range<usize>]) -> Range<usize> {
+    let (sbi_start, sbi_end) = unsafe { (SBI_START_ADDRESS, SBI_END_ADDRESS) };
+    memory
+        .iter()
+        .find(|region| region.start <= sbi_start && sbi_end <= region.end)
+        .cloned()
+        .unwrap_or(sbi_start..sbi_end)
+}
 
+/// Number of PMP entries the layout may use at most.
+const PMP_ENTRY_MAX: usize = 16;
+
+pub fn set_pmp(memory: &[Range<usize>]) {
+    let (sbi_start, sbi_end, rodata_start, rodata_end) = unsafe {
         asm!("la {}, sbi_start", out(reg) SBI_START_ADDRESS, options(nomem));
         asm!("la {}, sbi_end", out(reg) SBI_END_ADDRESS, options(nomem));
         asm!("la {}, sbi_rodata_start", out(reg) RODATA_START_ADDRESS, options(nomem));
         asm!("la {}, sbi_rodata_end", out(reg) RODATA_END_ADDRESS, options(nomem));
+        (
+            SBI_START_ADDRESS,
+            SBI_END_ADDRESS,
+            RODATA_START_ADDRESS,
+            RODATA_END_ADDRESS,
+        )
+    };
+    let memory_range = firmware_region(memory);
+
+    assert_eq!(memory_range.start & 0x3, 0);
+    assert_eq!(memory_range.end & 0x3, 0);
+    assert_eq!(sbi_start & 0x3, 0);
+    assert_eq!(sbi_end & 0x3, 0);
+    assert_eq!(rodata_start & 0x3, 0);
+    assert_eq!(rodata_end & 0x3, 0);
+
+    // memory_range is the memory region holding the firmware.
+    // [0..memory_range.start] RWX
+    // [memory_range.start..sbi_start] RWX
+    // [sbi_start..sbi_rodata_start] R
+    // [sbi_rodata_start..sbi_rodata_end] NONE
+    // [sbi_rodata_end..sbi_end] R
+    // [sbi_end..memory_range.end] RWX
+    // [memory_range.end..INF] RWX
+    let firmware = [
+        (memory_range.start..sbi_start, Permission::RWX),
+        (sbi_start..rodata_start, Permission::R),
+        (rodata_start..rodata_end, Permission::NONE),
+        (rodata_end..sbi_end, Permission::RW), // FIXME: Should be Permission::R, temporarily fix for possible S-mode DTB modification
+        (sbi_end..memory_range.end, Permission::RWX),
+    ];
+
+    // When AIA is active, block S-mode access to M-level interrupt
+    // controller regions while keeping other MMIO visible.
+    // This matches OpenSBI's domain isolation approach.
+    let isolated = match unsafe { crate::platform::PLATFORM.info.aia.as_ref() } {
+        Some(aia_info) if crate::platform::aia::is_aia_active() => aia_info
+            .isolated_regions()
+            .into_iter()
+            .filter(|region| region.end <= memory_range.start || region.start >= memory_range.end)
+            .collect(),
+        _ => Vec::new(),
+    };
 
-        assert_eq!(memory_range.start & 0x3, 0);
-        assert_eq!(memory_range.end & 0x3, 0);
-        assert_eq!(SBI_START_ADDRESS & 0x3, 0);
-        assert_eq!(SBI_END_ADDRESS & 0x3, 0);
-        assert_eq!(RODATA_START_ADDRESS & 0x3, 0);
-        assert_eq!(RODATA_END_ADDRESS & 0x3, 0);
-
-        // When AIA is active, block S-mode access to M-level interrupt
-        // controller regions while keeping other low MMIO visible.
-        // This matches OpenSBI's domain isolation approach.
-        if crate::platform::aia::is_aia_active()
-            && crate::platform::PLATFORM.info.is_qemu_virt()
-            && let Some(aia_info) = crate::platform::PLATFORM.info.aia.as_ref()
-        {
-            const QEMU_VIRT_M_APLIC_BASE: usize = 0x0c00_0000;
-            const QEMU_VIRT_CLINT_BASE: usize = 0x0200_0000;
-            const QEMU_VIRT_APLIC_SIZE: usize = 0x8000;
-            const QEMU_VIRT_CLINT_SIZE: usize = 0x1_0000;
-
-            let clint_base = crate::platform::PLATFORM
-                .info
-                .ipi
-                .as_ref()
-                .map(|(base, _)| *base)
-                .unwrap_or(QEMU_VIRT_CLINT_BASE);
-            let clint_end = clint_base + QEMU_VIRT_CLINT_SIZE;
-            let aplic_base = QEMU_VIRT_M_APLIC_BASE;
-            let aplic_end = aplic_base + QEMU_VIRT_APLIC_SIZE;
-            let m_base = aia_info.layout.machine_base;
-            let m_end = aia_info
-                .hart_imsic_map
-                .iter()
-                .flatten()
-                .copied()
-                .max()
-                .and_then(|addr| addr.checked_add(0x1000))
-                .unwrap_or(m_base + 0x1000);
-
-            pmpcfg0::set_pmp(0, Range::OFF, Permission::NONE, false);
-            pmpaddr0::write(0);
-            pmpcfg0::set_pmp(1, Range::TOR, Permission::RWX, false);
-            pmpaddr1::write(clint_base >> 2);
-            pmpcfg0::set_pmp(2, Range::TOR, Permission::NONE, false);
-            pmpaddr2::write(clint_end >> 2);
-            pmpcfg0::set_pmp(3, Range::TOR, Permission::RWX, false);
-            pmpaddr3::write(aplic_base >> 2);
-            pmpcfg0::set_pmp(4, Range::TOR, Permission::NONE, false);
-            pmpaddr4::write(aplic_end >> 2);
-            pmpcfg0::set_pmp(5, Range::TOR, Permission::RWX, false);
-            pmpaddr5::write(m_base >> 2);
-            pmpcfg0::set_pmp(6, Range::TOR, Permission::NONE, false);
-            pmpaddr6::write(m_end >> 2);
-            pmpcfg0::set_pmp(7, Range::TOR, Permission::RWX, false);
-            pmpaddr7::write(memory_range.start >> 2);
-            pmpcfg2::set_pmp(0, Range::TOR, Permission::RWX, false);
-            pmpaddr8::write(SBI_START_ADDRESS >> 2);
-            pmpcfg2::set_pmp(1, Range::TOR, Permission::R, false);
-            pmpaddr9::write(RODATA_START_ADDRESS >> 2);
-            pmpcfg2::set_pmp(2, Range::TOR, Permission::NONE, false);
-            pmpaddr10::write(RODATA_END_ADDRESS >> 2);
-            pmpcfg2::set_pmp(3, Range::TOR, Permission::RW, false);
-            pmpaddr11::write(SBI_END_ADDRESS >> 2);
-            pmpcfg2::set_pmp(4, Range::TOR, Permission::RWX, false);
-            pmpaddr12::write(memory_range.end >> 2);
-            pmpcfg2::set_pmp(5, Range::TOR, Permission::RWX, false);
-            pmpaddr13::write(usize::MAX >> 2);
-            return;
+    let entry_count = pmp_entry_count();
+    if entry_count == 0 {
+        // Without PMP, lower privilege modes may access the whole address space.
+        warn!("PMP: no entries implemented, leaving the firmware unprotected");
+        return;
+    }
+    let mut entries = pmp_layout(&isolated, &firmware);
+    if entries.len() > entry_count && !isolated.is_empty() {
+        warn!(
+            "PMP: {} entries needed to isolate AIA regions but {} implemented, leaving them accessible",
+            entries.len(),
+            entry_count
+        );
+        entries = pmp_layout(&[], &firmware);
+    }
+    if entries.len() > entry_count {
+        // A truncated layout drops the last RWX entry, which leaves S-mode without
+        // access to memory above it.
+        error!(
+            "PMP: {} entries needed to protect the firmware but {} implemented",
+            entries.len(),
+            entry_count
+        );
+        fail::stop();
+    }
+    for (idx, &(addr, range, permission)) in entries.iter().enumerate() {
+        write_pmp_entry(idx, range, permission, addr);
+    }
+}
+
+/// Returns the number of PMP entries implemented on the current hart.
+///
+/// Address registers of unimplemented entries are read-only zero, so each one is
+/// probed by writing all ones and reading it back, as OpenSBI does.
+fn pmp_entry_count() -> usize {
+    use riscv::register::*;
+    seq_macro::seq!(N in 0..16 {
+        #(
+            let old = pastey::paste! { [<pmpaddr ~N>]::read() };
+            unsafe { pastey::paste! { [<pmpaddr ~N>]::write(usize::MAX) } };
+            let probed = pastey::paste! { [<pmpaddr ~N>]::read() };
+            unsafe { pastey::paste! { [<pmpaddr ~N>]::write(old) } };
+            if probed == 0 {
+                return N;
+            }
+        )*
+    });
+    PMP_ENTRY_MAX
+}
+
+/// Lays out PMP entries as (top address, address matching, permission) covering
+/// the whole address space, with `isolated` regions inaccessible and RWX gaps.
+fn pmp_layout(
+    isolated: &[Range<usize>],
+    firmware: &[(Range<usize>, Permission)],
+) -> Vec<(usize, register::Range, Permission)> {
+    let mut spans: Vec<(Range<usize>, Permission)> = isolated
+        .iter()
+        .map(|region| (region.clone(), Permission::NONE))
+        .chain(firmware.iter().cloned())
+        .collect();
+    // Stable, so that empty firmware spans keep their order.
+    spans.sort_by_key(|(range, _)| range.start);
+
+    let mut entries = vec![(0, register::Range::OFF, Permission::NONE)];
+    let mut top = 0;
+    for (range, permission) in spans {
+        if range.start > top {
+            entries.push((range.start, register::Range::TOR, Permission::RWX));
         }
+        entries.push((range.end, register::Range::TOR, permission));
+        top = range.end;
+    }
+    entries.push((usize::MAX, register::Range::TOR, Permission::RWX));
+    entries
+}
 
-        pmpcfg0::set_pmp(0, Range::OFF, Permission::NONE, false);
-        pmpaddr0::write(0);
-        pmpcfg0::set_pmp(1, Range::TOR, Permission::RWX, false);
-        pmpaddr1::write(memory_range.start >> 2);
-        pmpcfg0::set_pmp(2, Range::TOR, Permission::RWX, false);
-        pmpaddr2::write(SBI_START_ADDRESS >> 2);
-        pmpcfg0::set_pmp(3, Range::TOR, Permission::R, false);
-        pmpaddr3::write(RODATA_START_ADDRESS >> 2);
-        pmpcfg0::set_pmp(4, Range::TOR, Permission::NONE, false);
-        pmpaddr4::write(RODATA_END_ADDRESS >> 2);
-        pmpcfg0::set_pmp(5, Range::TOR, Permission::RW, false); // FIXME: Should be Permission::R, temporarily fix for possible S-mode DTB modification
-        pmpaddr5::write(SBI_END_ADDRESS >> 2);
-        pmpcfg0::set_pmp(6, Range::TOR, Permission::RWX, false);
-        pmpaddr6::write(memory_range.end >> 2);
-        pmpcfg0::set_pmp(7, Range::TOR, Permission::RWX, false);
-        pmpaddr7::write(usize::MAX >> 2);
+/// Writes PMP entry `idx`, matching up to `addr` with `range`.
+fn write_pmp_entry(idx: usize, range: register::Range, permission: Permission, addr: usize) {
+    use riscv::register::*;
+    unsafe {
+        if idx < 8 {
+            pmpcfg0::set_pmp(idx, range, permission, false);
+        } else {
+            pmpcfg2::set_pmp(idx - 8, range, permission, false);
+        }
+        seq_macro::seq!(N in 0..16 {
+            match idx {
+                #(N => pastey::paste! { [<pmpaddr ~N>]::write(addr >> 2) },)*
+                _ => unreachable!(),
+            }
+        });
     }
 }
 
@@ -663,13 +747,15 @@ impl fmt::Display for RangeWrapper {
     }
 }
 
-pub fn log_pmp_cfg(_memory_range: &Range<usize>) {
+pub fn log_pmp_cfg(memory: &[Range<usize>]) {
     use riscv::register::*;
-    let pmp = pmpcfg0::read();
+    let pmp = [pmpcfg0::read(), pmpcfg2::read()];
 
-    let get_pmp_range = |i: usize| -> RangeWrapper { RangeWrapper(pmp.into_config(i).range) };
-    let get_pmp_permission =
-        |i: usize| -> PermissionWrapper { PermissionWrapper(pmp.into_config(i).permission) };
+    let get_pmp_range =
+        |i: usize| -> RangeWrapper { RangeWrapper(pmp[i / 8].into_config(i % 8).range) };
+    let get_pmp_permission = |i: usize| -> PermissionWrapper {
+        PermissionWrapper(pmp[i / 8].into_config(i % 8).permission)
+    };
     info!("PMP Configuration");
 
     info!(
@@ -677,15 +763,24 @@ pub fn log_pmp_cfg(_memory_range: &Range<usize>) {
         "PMP", "Range", "Permission", "Address"
     );
 
-    seq_macro::seq!(N in 0..8 {
-        info!(
-            "{:<5} {:<10} {:<15} 0x{:016x}",
-            N,
-            get_pmp_range(N),
-            get_pmp_permission(N),
-            pastey::paste! { [<pmpaddr ~N>]::read() } << 2,
-        );
+    let entry_count = pmp_entry_count();
+    seq_macro::seq!(N in 0..16 {
+        if N < entry_count {
+            info!(
+                "{:<5} {:<10} {:<15} 0x{:016x}",
+                N,
+                get_pmp_range(N),
+                get_pmp_permission(N),
+                pastey::paste! { [<pmpaddr ~N>]::read() } << 2,
+            );
+        }
     });
+
+    let firmware = firmware_region(memory);
+    info!(
+        "{:<30}: 0x{:x} - 0x{:x}",
+        "PMP firmware memory range", firmware.start, firmware.end
+    );
 }
 
 #[cfg(all(feature = "fdt", not(feature = "payload")))]
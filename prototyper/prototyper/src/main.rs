@@ -42,9 +42,9 @@ fn boot_hart(boot: &BootInfo) {
     heap::init();
     platform::init_board(boot.fdt_address());
 
-    let mem = platform::memory_range();
-    firmware::set_pmp(&mem);
-    firmware::log_pmp_cfg(&mem);
+    let memory = platform::memory_regions();
+    firmware::set_pmp(memory);
+    firmware::log_pmp_cfg(memory);
 
     let hart_id = current_hartid();
     info!("{:<30}: {}", "Boot HART ID", hart_id);
@@ -73,7 +73,7 @@ fn secondary_hart(boot: &BootInfo) {
 
     platform::wait_until_ready();
     platform::secondary_hart_init();
-    firmware::set_pmp(&platform::memory_range());
+    firmware::set_pmp(platform::memory_regions());
 
     let next = boot.next_stage();
     check_privilege(next.next_mode);
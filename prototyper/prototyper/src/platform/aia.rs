@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+use core::ops::Range;
 use core::sync::atomic::{AtomicBool, Ordering};
 use riscv_aia::Iid;
 use riscv_aia::peripheral::imsic::system::AddressLayout;
@@ -9,12 +11,10 @@ use crate::sbi::features::{Extension, hart_extension_probe};
 use crate::sbi::ipi::IpiDevice;
 
 pub(crate) const IMSIC_COMPATIBLE: [&str; 2] = ["riscv,imsics", "riscv,imsic"];
+pub(crate) const APLIC_COMPATIBLE: [&str; 1] = ["riscv,aplic"];
 
 static AIA_ACTIVE: AtomicBool = AtomicBool::new(false);
 
-const QEMU_VIRT_M_APLIC_BASE: usize = 0x0c00_0000;
-const QEMU_VIRT_S_IMSIC_BASE: usize = 0x2800_0000;
-const QEMU_VIRT_APLIC_NUM_SOURCES: usize = 0x60;
 const APLIC_DOMAINCFG: usize = 0x0000;
 const APLIC_SOURCECFG_BASE: usize = 0x0004;
 const APLIC_MMSICFGADDR: usize = 0x1bc0;
@@ -24,7 +24,13 @@ const APLIC_SMSICFGADDRH: usize = 0x1bcc;
 const APLIC_CLRIE_BASE: usize = 0x1f00;
 const APLIC_SOURCECFG_DELEGATE: u32 = 1 << 10;
 const APLIC_MSICFGADDRH_LOCK: u32 = 1 << 31;
+const APLIC_MSICFGADDRH_PPN_MASK: u32 = 0xfff;
 const APLIC_MSICFGADDRH_LHXW_SHIFT: u32 = 12;
+const APLIC_MSICFGADDRH_HHXW_SHIFT: u32 = 16;
+const APLIC_MSICFGADDRH_LHXS_SHIFT: u32 = 20;
+const APLIC_MSICFGADDRH_HHXS_SHIFT: u32 = 24;
+/// Group index shift of the MSI address with `HHXS` = 0, in bits.
+const IMSIC_GROUP_INDEX_SHIFT_MIN: u32 = 24;
 
 pub fn is_aia_active() -> bool {
     AIA_ACTIVE.load(Ordering::Relaxed)
@@ -49,53 +55,80 @@ pub fn per_hart_init() {
     }
 }
 
-pub fn init_qemu_m_aplic_delegation(machine_imsic_base: usize, hart_index_bits: u32) {
-    let base = QEMU_VIRT_M_APLIC_BASE;
+/// Delegates the sources of every M-level APLIC domain to its child domains as the
+/// device tree describes, and points M-level and S-level MSIs at the IMSICs.
+pub fn init_m_aplic_delegation(info: &AiaInfo) {
+    let msicfgaddrh = info.msicfgaddrh_fields();
+    if msicfgaddrh.is_none() {
+        warn!("AIA: IMSIC topology cannot be encoded in APLIC MSI address configuration");
+    }
 
-    write_aplic(base + APLIC_DOMAINCFG, 0);
+    let mut found = false;
+    for aplic in info.machine_aplics() {
+        found = true;
+        let base = aplic.base();
 
-    for source in (0..=QEMU_VIRT_APLIC_NUM_SOURCES).step_by(32) {
-        write_aplic(base + APLIC_CLRIE_BASE + (source / 32) * 4, u32::MAX);
-    }
+        write_aplic(base + APLIC_DOMAINCFG, 0);
 
-    for source in 1..=QEMU_VIRT_APLIC_NUM_SOURCES {
-        write_aplic(
-            base + APLIC_SOURCECFG_BASE + (source - 1) * 4,
-            APLIC_SOURCECFG_DELEGATE,
-        );
-    }
+        for source in (0..=aplic.num_sources as usize).step_by(32) {
+            write_aplic(base + APLIC_CLRIE_BASE + (source / 32) * 4, u32::MAX);
+        }
 
-    if read_aplic(base + APLIC_MMSICFGADDRH) & APLIC_MSICFGADDRH_LOCK == 0 {
-        write_msicfg(
-            base + APLIC_MMSICFGADDR,
-            base + APLIC_MMSICFGADDRH,
-            machine_imsic_base,
-            hart_index_bits,
-        );
-        write_msicfg(
-            base + APLIC_SMSICFGADDR,
-            base + APLIC_SMSICFGADDRH,
-            QEMU_VIRT_S_IMSIC_BASE,
-            hart_index_bits,
+        let mut delegated = 0;
+        for source in 1..=aplic.num_sources {
+            let sourcecfg = match aplic.child_index(source) {
+                Some(child_index) => {
+                    delegated += 1;
+                    APLIC_SOURCECFG_DELEGATE | child_index
+                }
+                None => 0,
+            };
+            write_aplic(
+                base + APLIC_SOURCECFG_BASE + (source as usize - 1) * 4,
+                sourcecfg,
+            );
+        }
+
+        if let Some((machine_fields, supervisor_fields)) = msicfgaddrh {
+            if read_aplic(base + APLIC_MMSICFGADDRH) & APLIC_MSICFGADDRH_LOCK == 0 {
+                write_msicfg(
+                    base + APLIC_MMSICFGADDR,
+                    base + APLIC_MMSICFGADDRH,
+                    info.machine.base_ppn(info.machine.guest_index_bits),
+                    machine_fields,
+                );
+                if let Some(ref supervisor) = info.supervisor {
+                    write_msicfg(
+                        base + APLIC_SMSICFGADDR,
+                        base + APLIC_SMSICFGADDRH,
+                        supervisor.base_ppn(supervisor.guest_index_bits),
+                        supervisor_fields,
+                    );
+                }
+            } else {
+                warn!(
+                    "AIA: M-level APLIC at 0x{:x} MSI configuration is locked",
+                    base
+                );
+            }
+        }
+
+        info!(
+            "AIA: delegated {} of {} M-level APLIC sources at 0x{:x} to child domains",
+            delegated, aplic.num_sources, base
         );
-    } else {
-        warn!("AIA: M-level APLIC MSI configuration is locked");
     }
 
-    info!(
-        "AIA: delegated M-level APLIC IRQs 1..={} to S-level child",
-        QEMU_VIRT_APLIC_NUM_SOURCES
-    );
+    if !found {
+        warn!("AIA: no M-level APLIC found, wired interrupts are not delegated");
+    }
 }
 
-fn write_msicfg(addr: usize, addrh: usize, imsic_base: usize, hart_index_bits: u32) {
-    let mut base_ppn = imsic_base >> 12;
-    base_ppn &= !((1usize << hart_index_bits) - 1);
-
+fn write_msicfg(addr: usize, addrh: usize, base_ppn: usize, fields: u32) {
     write_aplic(addr, base_ppn as u32);
     write_aplic(
         addrh,
-        ((base_ppn >> 32) as u32) | (hart_index_bits << APLIC_MSICFGADDRH_LHXW_SHIFT),
+        ((base_ppn >> 32) as u32 & APLIC_MSICFGADDRH_PPN_MASK) | fields,
     );
 }
 
@@ -109,11 +142,139 @@ fn write_aplic(addr: usize, value: u32) {
     }
 }
 
+/// Interrupt file topology of an IMSIC, read from its device tree node.
+pub struct ImsicTopology {
+    pub phandle: Option<u32>,
+    /// Register ranges; the first one starts at the interrupt file of hart index 0.
+    pub regs: Vec<Range<usize>>,
+    pub hart_index_bits: u32,
+    pub group_index_bits: u32,
+    pub group_index_shift: u32,
+    pub guest_index_bits: u32,
+}
+
+impl ImsicTopology {
+    /// Returns the base PPN of the interrupt files, with the hart and group index
+    /// fields and the `low_bits` guest index bits below them cleared.
+    fn base_ppn(&self, low_bits: u32) -> usize {
+        let index_mask = |bits: u32| (1usize << bits) - 1;
+        let mut base_ppn = self.regs[0].start >> 12;
+        base_ppn &= !index_mask(self.hart_index_bits + low_bits);
+        if self.group_index_bits != 0 {
+            base_ppn &=
+                !(index_mask(self.group_index_bits) << self.group_index_shift.saturating_sub(12));
+        }
+        base_ppn
+    }
+}
+
+/// An APLIC interrupt domain, read from a `riscv,aplic` device tree node.
+pub struct AplicDomain {
+    pub regs: Vec<Range<usize>>,
+    pub num_sources: u32,
+    /// Phandles of the child domains, in child index order.
+    pub children: Vec<u32>,
+    /// Delegated sources as (child domain phandle, first source, last source).
+    pub delegation: Vec<(u32, u32, u32)>,
+    /// Phandle of the IMSIC this domain forwards interrupts to as MSIs.
+    pub msi_parent: Option<u32>,
+}
+
+impl AplicDomain {
+    fn base(&self) -> usize {
+        self.regs[0].start
+    }
+
+    /// Returns the index of the child domain `source` is delegated to, if any.
+    fn child_index(&self, source: u32) -> Option<u32> {
+        let &(child, _, _) = self
+            .delegation
+            .iter()
+            .find(|(_, first, last)| (*first..=*last).contains(&source))?;
+        let index = self.children.iter().position(|phandle| *phandle == child)?;
+        Some(index as u32)
+    }
+}
+
 pub struct AiaInfo {
     pub layout: AddressLayout,
     pub num_ids: u16,
     pub firmware_ipi_iid: Iid,
     pub hart_imsic_map: [Option<usize>; NUM_HART_MAX],
+    /// M-level IMSIC topology.
+    pub machine: ImsicTopology,
+    /// S-level IMSIC topology, if the device tree has one.
+    pub supervisor: Option<ImsicTopology>,
+    pub aplics: Vec<AplicDomain>,
+    /// CLINT register range, whose M-level software interrupts AIA replaces.
+    pub clint: Option<Range<usize>>,
+}
+
+impl AiaInfo {
+    /// Returns the APLIC domains that forward interrupts to the M-level IMSIC.
+    pub fn machine_aplics(&self) -> impl Iterator<Item = &AplicDomain> {
+        self.aplics
+            .iter()
+            .filter(|aplic| aplic.msi_parent.is_some() && aplic.msi_parent == self.machine.phandle)
+    }
+
+    /// Returns the M-level regions hidden from S-mode, sorted and merged.
+    pub fn isolated_regions(&self) -> Vec<Range<usize>> {
+        let mut regions: Vec<Range<usize>> = self
+            .clint
+            .iter()
+            .chain(self.machine_aplics().flat_map(|aplic| aplic.regs.iter()))
+            .chain(self.machine.regs.iter())
+            .filter(|region| !region.is_empty())
+            .cloned()
+            .collect();
+        regions.sort_unstable_by_key(|region| region.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for region in regions {
+            match merged.last_mut() {
+                Some(last) if region.start <= last.end => last.end = last.end.max(region.end),
+                _ => merged.push(region),
+            }
+        }
+        merged
+    }
+
+    /// Encodes the IMSIC topology into the `mmsicfgaddrH` and `smsicfgaddrH` fields,
+    /// or returns `None` if it does not fit.
+    ///
+    /// `LHXW`, `HHXW` and `HHXS` in `mmsicfgaddrH` are shared by both privilege levels,
+    /// while each level takes `LHXS` from its own register.
+    fn msicfgaddrh_fields(&self) -> Option<(u32, u32)> {
+        let machine = &self.machine;
+        // Interrupt files of a hart are `LHXS` bits apart to hold guest files.
+        let machine_lhxs = machine.guest_index_bits;
+        let supervisor_lhxs = self
+            .supervisor
+            .as_ref()
+            .map_or(0, |supervisor| supervisor.guest_index_bits);
+        let hhxs = if machine.group_index_bits == 0 {
+            0
+        } else {
+            machine
+                .group_index_shift
+                .checked_sub(IMSIC_GROUP_INDEX_SHIFT_MIN)?
+        };
+        if machine.hart_index_bits > 0xf
+            || machine.group_index_bits > 0x7
+            || machine_lhxs > 0x7
+            || supervisor_lhxs > 0x7
+            || hhxs > 0x1f
+        {
+            return None;
+        }
+        Some((
+            machine.hart_index_bits << APLIC_MSICFGADDRH_LHXW_SHIFT
+                | machine.group_index_bits << APLIC_MSICFGADDRH_HHXW_SHIFT
+                | machine_lhxs << APLIC_MSICFGADDRH_LHXS_SHIFT
+                | hhxs << APLIC_MSICFGADDRH_HHXS_SHIFT,
+            supervisor_lhxs << APLIC_MSICFGADDRH_LHXS_SHIFT,
+        ))
+    }
 }
 
 pub struct ImsicDevice {
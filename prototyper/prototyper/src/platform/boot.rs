@@ -41,9 +41,9 @@ pub fn wait_until_ready() {
     }
 }
 
-/// Returns the board's memory range (set during `Platform::init`).
-pub fn memory_range() -> Range<usize> {
-    unsafe { PLATFORM.info.memory_range.as_ref().unwrap().clone() }
+/// Returns the board's memory regions sorted by start address (set during `Platform::init`).
+pub fn memory_regions() -> &'static [Range<usize>] {
+    unsafe { &PLATFORM.info.memory }
 }
 
 /// Reconciles the enabled-CPU table with the per-hart privilege checks.
@@ -0,0 +1,150 @@
+use alloc::boxed::Box;
+use rustsbi::SbiRet;
+use sbi_spec::cppc::reg_id::*;
+
+use crate::cfg::NUM_HART_MAX;
+use crate::platform::PLATFORM;
+use crate::riscv::current_hartid;
+use crate::sbi::cppc::CppcDevice;
+use crate::sbi::features::{PrivilegedVersion, hart_privileged_version};
+
+pub(crate) const GENERIC_CPPC_COMPATIBLE: [&str; 1] = ["rustsbi,cppc"];
+
+/// Static performance capabilities read from the `rustsbi,cppc` device tree node.
+///
+/// Performance values are abstract and unitless; frequencies are in MHz and the transition
+/// latency in nanoseconds, as the CPPC registers define them.
+///
+/// As the reference performance counter is `mtime`, performance values must be scaled to the
+/// timebase: a hart at performance `p` runs at `p / reference * timebase-frequency`, where
+/// `reference` is `reference_performance`, or `nominal_performance` if it is absent.
+#[derive(Clone, Copy, Debug)]
+pub struct CppcInfo {
+    pub highest_performance: u32,
+    pub nominal_performance: u32,
+    pub lowest_nonlinear_performance: u32,
+    pub lowest_performance: u32,
+    pub reference_performance: Option<u32>,
+    pub lowest_frequency: Option<u32>,
+    pub nominal_frequency: Option<u32>,
+    pub transition_latency: Option<u32>,
+}
+
+/// Board-specific hook that applies a performance level to a hart.
+pub trait DvfsDriver: Send {
+    /// Moves hart `hart_id` to performance level `perf`, already clamped to the range the
+    /// platform declares.
+    fn set_performance(&mut self, hart_id: usize, perf: u32) -> SbiRet;
+}
+
+/// CPPC registers served from static device tree values.
+///
+/// The reference performance counter is `mtime` and the delivered performance counter is
+/// `mcycle`; the latter is unimplemented while the SBI PMU keeps `mcycle` inhibited, as it
+/// would not advance. Desired performance requests are recorded per hart and forwarded to
+/// the DVFS driver, if the board has one.
+pub struct GenericCppc {
+    info: CppcInfo,
+    desired: [u32; NUM_HART_MAX],
+    enabled: [bool; NUM_HART_MAX],
+    dvfs: Option<Box<dyn DvfsDriver>>,
+}
+
+impl GenericCppc {
+    pub fn new(info: CppcInfo, dvfs: Option<Box<dyn DvfsDriver>>) -> Self {
+        Self {
+            info,
+            desired: [info.nominal_performance; NUM_HART_MAX],
+            enabled: [false; NUM_HART_MAX],
+            dvfs,
+        }
+    }
+}
+
+impl CppcDevice for GenericCppc {
+    fn width(&self, reg_id: u32) -> usize {
+        match reg_id {
+            HIGHEST_PERFORMANCE
+            | NOMINAL_PERFORMANCE
+            | LOWEST_NONLINEAR_PERFORMANCE
+            | LOWEST_PERFORMANCE
+            | DESIRED_PERFORMANCE
+            | CPPC_ENABLE => 32,
+            REFERENCE_PERFORMANCE_COUNTER => 64,
+            DELIVERED_PERFORMANCE_COUNTER if !cycle_inhibited() => 64,
+            REFERENCE_PERFORMANCE if self.info.reference_performance.is_some() => 32,
+            LOWEST_FREQUENCY if self.info.lowest_frequency.is_some() => 32,
+            NOMINAL_FREQUENCY if self.info.nominal_frequency.is_some() => 32,
+            TRANSITION_LATENCY if self.info.transition_latency.is_some() => 32,
+            _ => 0,
+        }
+    }
+
+    fn read(&self, reg_id: u32) -> u64 {
+        let hart_id = current_hartid();
+        let value = match reg_id {
+            HIGHEST_PERFORMANCE => self.info.highest_performance,
+            NOMINAL_PERFORMANCE => self.info.nominal_performance,
+            LOWEST_NONLINEAR_PERFORMANCE => self.info.lowest_nonlinear_performance,
+            LOWEST_PERFORMANCE => self.info.lowest_performance,
+            DESIRED_PERFORMANCE => self.desired[hart_id],
+            CPPC_ENABLE => self.enabled[hart_id] as u32,
+            REFERENCE_PERFORMANCE_COUNTER => {
+                return unsafe { PLATFORM.sbi.ipi.as_ref().unwrap().get_time() as u64 };
+            }
+            DELIVERED_PERFORMANCE_COUNTER => return riscv::register::mcycle::read64(),
+            REFERENCE_PERFORMANCE => self.info.reference_performance.unwrap_or_default(),
+            LOWEST_FREQUENCY => self.info.lowest_frequency.unwrap_or_default(),
+            NOMINAL_FREQUENCY => self.info.nominal_frequency.unwrap_or_default(),
+            TRANSITION_LATENCY => self.info.transition_latency.unwrap_or_default(),
+            _ => 0,
+        };
+        value as u64
+    }
+
+    fn write(&mut self, reg_id: u32, val: u64) -> SbiRet {
+        let hart_id = current_hartid();
+        match reg_id {
+            DESIRED_PERFORMANCE => {
+                let perf = val.clamp(
+                    self.info.lowest_performance as u64,
+                    self.info.highest_performance as u64,
+                ) as u32;
+                if self.enabled[hart_id]
+                    && let Some(dvfs) = self.dvfs.as_mut()
+                {
+                    let ret = dvfs.set_performance(hart_id, perf);
+                    if ret.is_err() {
+                        return ret;
+                    }
+                }
+                self.desired[hart_id] = perf;
+            }
+            CPPC_ENABLE => match val {
+                0 => self.enabled[hart_id] = false,
+                1 => {
+                    // Apply the level requested while performance control was disabled.
+                    if !self.enabled[hart_id]
+                        && let Some(dvfs) = self.dvfs.as_mut()
+                    {
+                        let ret = dvfs.set_performance(hart_id, self.desired[hart_id]);
+                        if ret.is_err() {
+                            return ret;
+                        }
+                    }
+                    self.enabled[hart_id] = true;
+                }
+                _ => return SbiRet::invalid_param(),
+            },
+            _ => return SbiRet::denied(),
+        }
+        SbiRet::success(0)
+    }
+}
+
+/// Checks whether `mcycle` of the calling hart is stopped by `mcountinhibit.CY`.
+#[inline]
+fn cycle_inhibited() -> bool {
+    hart_privileged_version(current_hartid()) >= PrivilegedVersion::Version1_11
+        && riscv::register::mcountinhibit::read().cy()
+}
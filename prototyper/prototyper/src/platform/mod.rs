@@ -1,10 +1,11 @@
 use alloc::string::String;
-use alloc::{boxed::Box, string::ToString, vec::Vec};
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
 use clint::{SifiveClintWrap, THeadClintWrap};
 use core::{
     ops::Range,
     sync::atomic::{AtomicBool, Ordering},
 };
+use cppc::{CppcInfo, DvfsDriver, GENERIC_CPPC_COMPATIBLE, GenericCppc};
 use reset::SifiveTestDeviceWrap;
 use spin::Mutex;
 use uart_xilinx::MmioUartAxiLite;
@@ -28,23 +29,28 @@ use crate::platform::reset::SIFIVETEST_COMPATIBLE;
 use crate::riscv::spacemit_k1;
 use crate::sbi::SBI;
 use crate::sbi::console::SbiConsole;
+use crate::sbi::cppc::SbiCppc;
+use crate::sbi::dbtr::SbiDbtr;
 use crate::sbi::features::extension_detection;
+use crate::sbi::fwft::SbiFwft;
 use crate::sbi::hsm::SbiHsm;
 use crate::sbi::ipi::SbiIpi;
 use crate::sbi::logger;
 use crate::sbi::pmu::{EventToCounterMap, RawEventToCounterMap};
 use crate::sbi::reset::SbiReset;
 use crate::sbi::rfence::SbiRFence;
+use crate::sbi::sse::SbiSse;
 use crate::sbi::suspend::SbiSuspend;
 
 pub(crate) mod aia;
 mod boot;
 mod clint;
 mod console;
+mod cppc;
 mod reset;
 
 pub use boot::{
-    init_board, memory_range, refresh_enabled_cpus, secondary_hart_init, wait_until_ready,
+    init_board, memory_regions, refresh_enabled_cpus, secondary_hart_init, wait_until_ready,
 };
 
 pub(crate) static CPU_PRIVILEGED_ENABLED: [AtomicBool; NUM_HART_MAX] =
@@ -57,6 +63,7 @@ pub(crate) static CPU_PRIVILEGED_ENABLED: [AtomicBool; NUM_HART_MAX] =
 /// this flag (main.rs secondary-hart path).
 pub(crate) static IS_K1_PLATFORM: AtomicBool = AtomicBool::new(false);
 
+const RISCV_SUPERVISOR_EXTERNAL_IRQ: u32 = 9;
 const RISCV_MACHINE_EXTERNAL_IRQ: u32 = 11;
 
 type BaseAddress = usize;
@@ -124,6 +131,44 @@ fn prop_u32_cells(node: &serde_device_tree::buildin::Node, name: &str) -> Option
     }
 }
 
+/// Reads the static performance capabilities of a `rustsbi,cppc` node.
+///
+/// The highest, nominal and lowest performance levels are required; lowest nonlinear
+/// performance defaults to the lowest performance.
+fn parse_cppc_info(node: &serde_device_tree::buildin::Node) -> Option<CppcInfo> {
+    let prop = |name| node.get_prop(name).map(|prop| prop.deserialize::<u32>());
+    let (Some(highest), Some(nominal), Some(lowest)) = (
+        prop("highest-performance"),
+        prop("nominal-performance"),
+        prop("lowest-performance"),
+    ) else {
+        warn!("CPPC: missing required performance level properties, skipping");
+        return None;
+    };
+    if !(lowest <= nominal && nominal <= highest) {
+        warn!("CPPC: performance levels are not ordered, skipping");
+        return None;
+    }
+    Some(CppcInfo {
+        highest_performance: highest,
+        nominal_performance: nominal,
+        lowest_nonlinear_performance: prop("lowest-nonlinear-performance").unwrap_or(lowest),
+        lowest_performance: lowest,
+        reference_performance: prop("reference-performance"),
+        lowest_frequency: prop("lowest-frequency"),
+        nominal_frequency: prop("nominal-frequency"),
+        transition_latency: prop("transition-latency"),
+    })
+}
+
+/// Returns the DVFS driver of the current board, if any.
+///
+/// Boards with frequency scaling hardware hook their driver in here; without one, CPPC
+/// only reports the static capabilities and records requested performance levels.
+fn board_dvfs_driver() -> Option<Box<dyn DvfsDriver>> {
+    None
+}
+
 fn hart_for_cpu_intc(cpu_intc_harts: &[(u32, usize)], phandle: u32) -> Option<usize> {
     cpu_intc_harts
         .iter()
@@ -131,6 +176,107 @@ fn hart_for_cpu_intc(cpu_intc_harts: &[(u32, usize)], phandle: u32) -> Option<us
         .map(|(_, hart_id)| *hart_id)
 }
 
+/// Counts the interrupt files of an IMSIC node wired to CPU interrupt `irq`.
+fn imsic_file_count(node: &serde_device_tree::buildin::Node, irq: u32) -> usize {
+    prop_u32_cells(node, "interrupts-extended").map_or(0, |cells| {
+        cells
+            .chunks_exact(2)
+            .filter(|interrupt| interrupt[1] == irq)
+            .count()
+    })
+}
+
+/// Returns the number of index bits that can address `count` items.
+fn index_bits(count: u32) -> u32 {
+    if count <= 1 {
+        0
+    } else {
+        u32::BITS - (count - 1).leading_zeros()
+    }
+}
+
+/// Reads the topology of an S-level IMSIC node with `file_count` interrupt files.
+fn parse_supervisor_imsic(
+    node: &serde_device_tree::buildin::Node,
+    reg_ranges: &[Range<usize>],
+    file_count: usize,
+) -> Option<aia::ImsicTopology> {
+    let prop = |name| node.get_prop(name).map(|prop| prop.deserialize::<u32>());
+    let Some(first_reg_range) = reg_ranges.first() else {
+        warn!("IMSIC: S-level node is missing reg ranges, skipping");
+        return None;
+    };
+    let topology = aia::ImsicTopology {
+        phandle: node_phandle(node),
+        regs: reg_ranges.to_vec(),
+        hart_index_bits: prop("riscv,hart-index-bits").unwrap_or(index_bits(file_count as u32)),
+        group_index_bits: prop("riscv,group-index-bits").unwrap_or(0),
+        group_index_shift: prop("riscv,group-index-shift").unwrap_or(24),
+        guest_index_bits: prop("riscv,guest-index-bits").unwrap_or(0),
+    };
+    if first_reg_range.start & 0xFFF != 0
+        || topology.hart_index_bits + topology.guest_index_bits >= u32::BITS
+        || topology.group_index_bits >= u32::BITS
+        || topology.group_index_shift >= u32::BITS
+    {
+        warn!(
+            "IMSIC: invalid S-level topology at 0x{:x}, skipping",
+            first_reg_range.start
+        );
+        return None;
+    }
+    Some(topology)
+}
+
+/// Reads an APLIC domain node.
+fn parse_aplic(
+    node: &serde_device_tree::buildin::Node,
+    reg_ranges: &[Range<usize>],
+) -> Option<aia::AplicDomain> {
+    let Some(first_reg_range) = reg_ranges.first() else {
+        warn!("APLIC: missing reg ranges, skipping");
+        return None;
+    };
+    let Some(num_sources) = node
+        .get_prop("riscv,num-sources")
+        .map(|prop| prop.deserialize::<u32>())
+    else {
+        warn!(
+            "APLIC: 0x{:x} is missing required riscv,num-sources property, skipping",
+            first_reg_range.start
+        );
+        return None;
+    };
+    if !(1..=1023).contains(&num_sources) {
+        warn!(
+            "APLIC: 0x{:x} has invalid riscv,num-sources {}, skipping",
+            first_reg_range.start, num_sources
+        );
+        return None;
+    }
+    // `riscv,delegate` is the name used by older device trees.
+    let delegation_cells = prop_u32_cells(node, "riscv,delegation")
+        .or_else(|| prop_u32_cells(node, "riscv,delegate"))
+        .unwrap_or_default();
+    if delegation_cells.len() % 3 != 0 {
+        warn!(
+            "APLIC: 0x{:x} has malformed riscv,delegation property, skipping",
+            first_reg_range.start
+        );
+        return None;
+    }
+    Some(aia::AplicDomain {
+        regs: reg_ranges.to_vec(),
+        num_sources,
+        children: prop_u32_cells(node, "riscv,children").unwrap_or_default(),
+        delegation: delegation_cells
+            .chunks_exact(3)
+            .map(|cells| (cells[0], cells[1], cells[2]))
+            .collect(),
+        msi_parent: prop_u32_cells(node, "msi-parent").and_then(|cells| cells.first().copied()),
+    })
+}
+
 fn imsic_machine_hart_files(
     node: &serde_device_tree::buildin::Node,
     cpu_intc_harts: &[(u32, usize)],
@@ -157,7 +303,8 @@ fn imsic_machine_hart_files(
 }
 
 pub struct BoardInfo {
-    pub memory_range: Option<Range<usize>>,
+    /// Memory regions sorted by start address.
+    pub memory: Vec<Range<usize>>,
     pub console: Option<(BaseAddress, MachineConsoleType)>,
     pub console_clock: Option<u32>,
     pub reset: Option<BaseAddress>,
@@ -168,12 +315,13 @@ pub struct BoardInfo {
     pub model: String,
     /// P1 PMIC reset info: (I2C controller base, PMIC address)
     pub pmic_reset: Option<(usize, u8)>,
+    pub cppc: Option<CppcInfo>,
 }
 
 impl BoardInfo {
     pub const fn new() -> Self {
         BoardInfo {
-            memory_range: None,
+            memory: Vec::new(),
             console: None,
             console_clock: None,
             reset: None,
@@ -183,11 +331,16 @@ impl BoardInfo {
             cpu_num: None,
             model: String::new(),
             pmic_reset: None,
+            cppc: None,
         }
     }
 
-    pub fn is_qemu_virt(&self) -> bool {
-        self.model == "riscv-virtio,qemu"
+    /// Returns the memory region that holds all of `[start, start + len)`, if any.
+    pub fn memory_region_of(&self, start: usize, len: usize) -> Option<&Range<usize>> {
+        let end = start.checked_add(len)?;
+        self.memory
+            .iter()
+            .find(|region| region.start <= start && end <= region.end)
     }
 }
 
@@ -222,6 +375,14 @@ impl Platform {
         self.sbi_init_ipi_reset_hsm_rfence(&root);
         // Initialize pmu extension
         self.sbi_init_pmu(&root);
+        // Firmware features are probed per hart on each call.
+        self.sbi_fwft_init();
+        // Software events are notified to remote harts by IPI.
+        self.sbi_sse_init();
+        // Debug triggers are probed per hart at boot.
+        self.sbi_dbtr_init();
+        // Get static performance capabilities, init sbi cppc extension.
+        self.sbi_init_cppc(&root);
 
         // Record K1 platform detection *before* releasing the ready flag, so
         // that secondary harts observing `ready()` also observe the flag.
@@ -294,6 +455,9 @@ impl Platform {
     fn sbi_init_ipi_reset_hsm_rfence(&mut self, root: &serde_device_tree::buildin::Node) {
         // Get ipi and reset device info
         let cpu_intc_harts = collect_cpu_intc_harts(root);
+        let mut clint_range = None;
+        let mut supervisor_imsic = None;
+        let mut aplics = Vec::new();
         let mut find_device =
             |node: &serde_device_tree::buildin::Node,
              parent: Option<&serde_device_tree::buildin::Node>| {
@@ -303,6 +467,11 @@ impl Platform {
                     let base_address = regs[0].start;
                     for device_id in compatible.iter() {
                         // Initialize clint device.
+                        if SIFIVE_CLINT_COMPATIBLE.contains(&device_id)
+                            || THEAD_CLINT_COMPATIBLE.contains(&device_id)
+                        {
+                            clint_range = Some(regs[0].clone());
+                        }
                         if SIFIVE_CLINT_COMPATIBLE.contains(&device_id) {
                             if node.get_prop("clint,has-no-64bit-mmio").is_some() {
                                 self.info.ipi = Some((base_address, MachineClintType::TheadClint));
@@ -329,14 +498,33 @@ impl Platform {
                                 .unwrap_or(base_address);
                             self.info.pmic_reset = Some((i2c_base, pmic_addr));
                         }
-                        // Discover the M-level IMSIC from its CPU interrupt wiring.
-                        if aia::IMSIC_COMPATIBLE.contains(&device_id) && self.info.aia.is_none() {
-                            self.sbi_discover_imsic(node, &regs, &cpu_intc_harts);
+                        // Discover the IMSICs of each level from their CPU interrupt wiring.
+                        if aia::IMSIC_COMPATIBLE.contains(&device_id) {
+                            let supervisor_files =
+                                imsic_file_count(node, RISCV_SUPERVISOR_EXTERNAL_IRQ);
+                            if supervisor_files > 0 {
+                                if supervisor_imsic.is_none() {
+                                    supervisor_imsic =
+                                        parse_supervisor_imsic(node, &regs, supervisor_files);
+                                }
+                            } else if self.info.aia.is_none() {
+                                self.sbi_discover_imsic(node, &regs, &cpu_intc_harts);
+                            }
+                        }
+                        if aia::APLIC_COMPATIBLE.contains(&device_id)
+                            && let Some(aplic) = parse_aplic(node, &regs)
+                        {
+                            aplics.push(aplic);
                         }
                     }
                 }
             };
         search_with_parent(root, &mut find_device);
+        if let Some(ref mut aia_info) = self.info.aia {
+            aia_info.supervisor = supervisor_imsic;
+            aia_info.aplics = aplics;
+            aia_info.clint = clint_range;
+        }
         self.sbi_ipi_init();
         self.sbi_hsm_init();
         self.sbi_reset_init();
@@ -404,18 +592,34 @@ impl Platform {
         }
     }
 
+    fn sbi_init_cppc(&mut self, root: &serde_device_tree::buildin::Node) {
+        let mut find_cppc = |node: &serde_device_tree::buildin::Node| {
+            let Some(compatible_strseq) = get_compatible(node) else {
+                return;
+            };
+            if compatible_strseq
+                .iter()
+                .any(|compatible| GENERIC_CPPC_COMPATIBLE.contains(&compatible))
+            {
+                self.info.cppc = parse_cppc_info(node);
+            }
+        };
+        root.search(&mut find_cppc);
+        self.sbi_cppc_init();
+    }
+
     fn sbi_misc_init(&mut self, tree: &Tree) {
-        // Get memory info
-        // TODO: More than one memory node or range?
-        let memory_reg = tree
-            .memory
-            .iter()
-            .next()
-            .unwrap()
-            .deserialize::<Memory>()
-            .reg;
-        let memory_range = memory_reg.iter().next().unwrap().0;
-        self.info.memory_range = Some(memory_range);
+        // Get memory info from every memory node and range.
+        for memory_node in tree.memory.iter() {
+            let memory_reg = memory_node.deserialize::<Memory>().reg;
+            for region in memory_reg.iter() {
+                let memory_range = region.0;
+                if !memory_range.is_empty() {
+                    self.info.memory.push(memory_range);
+                }
+            }
+        }
+        self.info.memory.sort_unstable_by_key(|region| region.start);
 
         // Get cpu number info
         self.info.cpu_num = Some(tree.cpus.cpu.len());
@@ -563,12 +767,7 @@ impl Platform {
             return;
         }
 
-        let machine_hart_count = machine_hart_files.len() as u32;
-        let default_hart_index_bits = if machine_hart_count <= 1 {
-            0
-        } else {
-            u32::BITS - (machine_hart_count - 1).leading_zeros()
-        };
+        let default_hart_index_bits = index_bits(machine_hart_files.len() as u32);
 
         let hart_index_bits: u32 = node
             .get_prop("riscv,hart-index-bits")
@@ -698,6 +897,17 @@ impl Platform {
             num_ids,
             firmware_ipi_iid,
             hart_imsic_map,
+            machine: aia::ImsicTopology {
+                phandle: node_phandle(node),
+                regs: reg_ranges.to_vec(),
+                hart_index_bits,
+                group_index_bits,
+                group_index_shift,
+                guest_index_bits: 0,
+            },
+            supervisor: None,
+            aplics: Vec::new(),
+            clint: None,
         });
     }
 
@@ -732,17 +942,7 @@ impl Platform {
                 let ipi_dev =
                     aia::ImsicDevice::new(aia_info.firmware_ipi_iid, aia_info.hart_imsic_map);
                 self.sbi.ipi = Some(SbiIpi::new(Mutex::new(Box::new(ipi_dev)), max_hart_id));
-                if self.info.is_qemu_virt() {
-                    aia::init_qemu_m_aplic_delegation(
-                        aia_info.layout.machine_base,
-                        aia_info.layout.hart_index_bits,
-                    );
-                } else {
-                    warn!(
-                        "AIA: skipping QEMU virt M-APLIC setup on '{}'",
-                        self.info.model
-                    );
-                }
+                aia::init_m_aplic_delegation(aia_info);
                 aia::set_aia_active(true);
                 info!("AIA: IMSIC IPI + Sstc timer backend initialized");
                 return;
@@ -782,6 +982,36 @@ impl Platform {
         }
     }
 
+    fn sbi_fwft_init(&mut self) {
+        self.sbi.fwft = Some(SbiFwft);
+    }
+
+    fn sbi_dbtr_init(&mut self) {
+        self.sbi.dbtr = Some(SbiDbtr);
+    }
+
+    fn sbi_cppc_init(&mut self) {
+        // Reference performance counter is read from mtime.
+        if let Some(info) = self.info.cppc
+            && self.sbi.ipi.is_some()
+        {
+            self.sbi.cppc = Some(SbiCppc::new(Mutex::new(Box::new(GenericCppc::new(
+                info,
+                board_dvfs_driver(),
+            )))));
+        } else {
+            self.sbi.cppc = None;
+        }
+    }
+
+    fn sbi_sse_init(&mut self) {
+        if self.sbi.ipi.is_some() {
+            self.sbi.sse = Some(SbiSse::new());
+        } else {
+            self.sbi.sse = None;
+        }
+    }
+
     pub fn print_board_info(&self) {
         info!("RustSBI version {}", rustsbi::VERSION);
         rustsbi::LOGO.lines().for_each(|line| info!("{}", line));
@@ -830,6 +1060,10 @@ impl Platform {
         self.print_rfence_info();
         self.print_susp_info();
         self.print_pmu_info();
+        self.print_fwft_info();
+        self.print_sse_info();
+        self.print_dbtr_info();
+        self.print_cppc_info();
     }
 
     #[inline]
@@ -920,15 +1154,54 @@ impl Platform {
         }
     }
 
+    #[inline]
+    fn print_fwft_info(&self) {
+        if self.have_fwft() {
+            info!("{:<30}: {}", "Platform FWFT Extension", "Available");
+        } else {
+            warn!("{:<30}: {}", "Platform FWFT Extension", "Not Available");
+        }
+    }
+
+    #[inline]
+    fn print_sse_info(&self) {
+        if self.have_sse() {
+            info!("{:<30}: {}", "Platform SSE Extension", "Available");
+        } else {
+            warn!("{:<30}: {}", "Platform SSE Extension", "Not Available");
+        }
+    }
+
+    #[inline]
+    fn print_dbtr_info(&self) {
+        if self.have_dbtr() {
+            info!("{:<30}: {}", "Platform DBTR Extension", "Available");
+        } else {
+            warn!("{:<30}: {}", "Platform DBTR Extension", "Not Available");
+        }
+    }
+
+    #[inline]
+    fn print_cppc_info(&self) {
+        if self.have_cppc() {
+            info!("{:<30}: {}", "Platform CPPC Extension", "Available");
+        } else {
+            warn!("{:<30}: {}", "Platform CPPC Extension", "Not Available");
+        }
+    }
+
     #[inline]
     fn print_memory_info(&self) {
-        if let Some(memory_range) = &self.info.memory_range {
+        if self.info.memory.is_empty() {
+            warn!("{:<30}: Not Available", "Memory range");
+        }
+        for (idx, memory_range) in self.info.memory.iter().enumerate() {
             info!(
                 "{:<30}: 0x{:x} - 0x{:x}",
-                "Memory range", memory_range.start, memory_range.end
+                format!("Memory range {}", idx),
+                memory_range.start,
+                memory_range.end
             );
-        } else {
-            warn!("{:<30}: Not Available", "Memory range");
         }
     }
 
@@ -978,6 +1251,22 @@ impl Platform {
         self.sbi.pmu.is_some()
     }
 
+    pub fn have_fwft(&self) -> bool {
+        self.sbi.fwft.is_some()
+    }
+
+    pub fn have_sse(&self) -> bool {
+        self.sbi.sse.is_some()
+    }
+
+    pub fn have_dbtr(&self) -> bool {
+        self.sbi.dbtr.is_some()
+    }
+
+    pub fn have_cppc(&self) -> bool {
+        self.sbi.cppc.is_some()
+    }
+
     pub fn ready(&self) -> bool {
         self.ready.load(Ordering::Acquire)
     }
@@ -22,6 +22,13 @@ seq!(N in 3..32 {
     pub const CSR_MHPMEVENT~N: u16 = 0x320 + N;
 });
 
+// Debug/Trace Trigger Registers (Sdtrig extension)
+pub const CSR_TSELECT: u16 = 0x7a0;
+pub const CSR_TDATA1: u16 = 0x7a1;
+pub const CSR_TDATA2: u16 = 0x7a2;
+pub const CSR_TDATA3: u16 = 0x7a3;
+pub const CSR_TINFO: u16 = 0x7a4;
+
 // Machine Counter/Timers
 pub const CSR_MCYCLE: u16 = 0xb00;
 pub const CSR_MINSTRET: u16 = 0xb02;
@@ -68,6 +75,16 @@ pub mod menvcfg {
     pub const CBCFE: usize = 0x1 << 6;
     /// Cache block zero for enclave.
     pub const CBZE: usize = 0x1 << 7;
+    /// Pointer masking mode for the next-lower privilege mode (Smnpm extension).
+    pub const PMM: usize = 0x3 << 32;
+    /// Pointer masking mode `PMLEN=7`.
+    pub const PMM_PMLEN_7: usize = 0x2 << 32;
+    /// Pointer masking mode `PMLEN=16`.
+    pub const PMM_PMLEN_16: usize = 0x3 << 32;
+    /// Double trap enable (Ssdbltrp extension).
+    pub const DTE: usize = 0x1 << 59;
+    /// Hardware updating of PTE A/D bits enable (Svadu extension).
+    pub const ADUE: usize = 0x1 << 61;
     /// Page-based memory types enable.
     pub const PBMTE: usize = 0x1 << 62;
     /// Supervisor timer counter enable.
@@ -79,6 +96,23 @@ pub mod menvcfg {
         set_bits(STCE);
     }
 
+    /// Reads the menvcfg register.
+    #[inline(always)]
+    pub fn read() -> usize {
+        let bits: usize;
+        unsafe {
+            asm!("csrr {}, menvcfg", out(reg) bits, options(nomem));
+        }
+        bits
+    }
+
+    /// Clears specified bits in menvcfg register.
+    pub fn clear_bits(option: usize) {
+        unsafe {
+            asm!("csrc menvcfg, {}", in(reg) option, options(nomem));
+        }
+    }
+
     /// Sets specified bits in menvcfg register.
     pub fn set_bits(option: usize) {
         let mut bits: usize;
@@ -140,6 +174,61 @@ pub mod stimecmp {
     }
 }
 
+/// Debug trigger register operations (Sdtrig extension).
+pub mod trigger {
+    use core::arch::asm;
+
+    /// Selects the trigger at hardware index `idx`, returning the index read back.
+    ///
+    /// `tselect` is WARL; the index read back differs from `idx` if the trigger does not exist.
+    #[inline(always)]
+    pub fn select(idx: usize) -> usize {
+        let selected: usize;
+        unsafe {
+            asm!("csrw tselect, {}", "csrr {}, tselect", in(reg) idx, lateout(reg) selected, options(nomem));
+        }
+        selected
+    }
+
+    /// Reads `tdata1`, `tdata2` and `tdata3` of the selected trigger.
+    #[inline(always)]
+    pub fn read_tdata() -> [usize; 3] {
+        let (tdata1, tdata2, tdata3): (usize, usize, usize);
+        unsafe {
+            asm!(
+                "csrr {}, tdata1",
+                "csrr {}, tdata2",
+                "csrr {}, tdata3",
+                out(reg) tdata1,
+                out(reg) tdata2,
+                out(reg) tdata3,
+                options(nomem),
+            );
+        }
+        [tdata1, tdata2, tdata3]
+    }
+
+    /// Writes `tdata1`, `tdata2` and `tdata3` of the selected trigger.
+    ///
+    /// The trigger is disabled while `tdata2` and `tdata3` change, so it never fires on a
+    /// partial configuration.
+    #[inline(always)]
+    pub fn write_tdata([tdata1, tdata2, tdata3]: [usize; 3]) {
+        unsafe {
+            asm!(
+                "csrw tdata1, zero",
+                "csrw tdata2, {}",
+                "csrw tdata3, {}",
+                "csrw tdata1, {}",
+                in(reg) tdata2,
+                in(reg) tdata3,
+                in(reg) tdata1,
+                options(nomem),
+            );
+        }
+    }
+}
+
 pub mod mcycle {
     use core::arch::asm;
     pub fn write(value: u64) {
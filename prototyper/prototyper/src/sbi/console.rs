@@ -89,7 +89,7 @@ impl SbiConsole {
     // Rejects buffers that this firmware cannot safely turn into raw slices.
     //
     // The SBI address tuple may still be valid,
-    // but this implementation only accepts buffers inside `PLATFORM.info.memory_range`.
+    // but this implementation only accepts buffers inside one of `PLATFORM.info.memory`.
     #[inline]
     fn checked_physical_buffer<P>(&self, bytes: &Physical<P>) -> Result<(usize, usize), SbiRet> {
         let len = bytes.num_bytes();
@@ -102,11 +102,8 @@ impl SbiConsole {
             Err(err) => return Err(err),
         };
 
-        match unsafe { PLATFORM.info.memory_range.as_ref() } {
-            Some(range)
-                if start >= range.start
-                    && start.checked_add(len).is_some_and(|end| end <= range.end) => {}
-            _ => return Err(SbiRet::failed()),
+        if unsafe { PLATFORM.info.memory_region_of(start, len) }.is_none() {
+            return Err(SbiRet::failed());
         }
 
         Ok((start, len))
@@ -126,7 +123,7 @@ impl Console for SbiConsole {
         }
 
         // SAFETY: `checked_physical_buffer` only returns ranges that
-        // were accepted as representable and within `memory_range`.
+        // were accepted as representable and within a memory region.
         let buf = unsafe { core::slice::from_raw_parts(start as *const u8, len) };
         let bytes_written = self.inner.lock().write(buf);
         SbiRet::success(bytes_written)
@@ -144,7 +141,7 @@ impl Console for SbiConsole {
         }
 
         // SAFETY: `checked_physical_buffer` only returns ranges that
-        // were accepted as representable and within `memory_range`.
+        // were accepted as representable and within a memory region.
         let buf = unsafe { core::slice::from_raw_parts_mut(start as *mut u8, len) };
         let bytes_read = self.inner.lock().read(buf);
         SbiRet::success(bytes_read)
@@ -0,0 +1,100 @@
+use alloc::boxed::Box;
+use rustsbi::SbiRet;
+use sbi_spec::cppc::reg_id::*;
+use spin::Mutex;
+
+/// A trait that must be implemented by platform frequency providers to serve CPPC registers.
+///
+/// Registers are those of the calling hart.
+pub trait CppcDevice: Send {
+    /// Returns the width in bits of register `reg_id`, or 0 if it is not implemented.
+    fn width(&self, reg_id: u32) -> usize;
+    /// Reads implemented register `reg_id`.
+    fn read(&self, reg_id: u32) -> u64;
+    /// Writes implemented read-write register `reg_id`.
+    fn write(&mut self, reg_id: u32, val: u64) -> SbiRet;
+}
+
+/// Implementation of SBI CPPC extension.
+pub struct SbiCppc {
+    pub cppc_dev: Mutex<Box<dyn CppcDevice>>,
+}
+
+impl SbiCppc {
+    pub fn new(cppc_dev: Mutex<Box<dyn CppcDevice>>) -> Self {
+        Self { cppc_dev }
+    }
+
+    /// Returns the width of register `reg_id`, or an error for reserved and unimplemented
+    /// registers.
+    #[inline]
+    fn implemented(&self, reg_id: u32) -> Result<usize, SbiRet> {
+        if is_reserved(reg_id) {
+            return Err(SbiRet::invalid_param());
+        }
+        match self.cppc_dev.lock().width(reg_id) {
+            0 => Err(SbiRet::not_supported()),
+            width => Ok(width),
+        }
+    }
+}
+
+impl rustsbi::Cppc for SbiCppc {
+    fn probe(&self, reg_id: u32) -> SbiRet {
+        if is_reserved(reg_id) {
+            return SbiRet::invalid_param();
+        }
+        SbiRet::success(self.cppc_dev.lock().width(reg_id))
+    }
+
+    fn read(&self, reg_id: u32) -> SbiRet {
+        match self.implemented(reg_id) {
+            Ok(_) => SbiRet::success(self.cppc_dev.lock().read(reg_id) as usize),
+            Err(err) => err,
+        }
+    }
+
+    fn read_hi(&self, reg_id: u32) -> SbiRet {
+        // RustSBI Prototyper only supports RV64, where `read` returns all the bits.
+        match self.implemented(reg_id) {
+            Ok(_) => SbiRet::success(0),
+            Err(err) => err,
+        }
+    }
+
+    fn write(&self, reg_id: u32, val: u64) -> SbiRet {
+        if let Err(err) = self.implemented(reg_id) {
+            return err;
+        }
+        if is_read_only(reg_id) {
+            return SbiRet::denied();
+        }
+        self.cppc_dev.lock().write(reg_id, val)
+    }
+}
+
+/// Checks whether `reg_id` is reserved for future use.
+#[inline]
+fn is_reserved(reg_id: u32) -> bool {
+    reg_id > NOMINAL_FREQUENCY && reg_id != TRANSITION_LATENCY
+}
+
+/// Checks whether the standard register `reg_id` is read-only.
+#[inline]
+fn is_read_only(reg_id: u32) -> bool {
+    matches!(
+        reg_id,
+        HIGHEST_PERFORMANCE
+            | NOMINAL_PERFORMANCE
+            | LOWEST_NONLINEAR_PERFORMANCE
+            | LOWEST_PERFORMANCE
+            | GUARANTEED_PERFORMANCE
+            | COUNTER_WRAPAROUND_TIME
+            | REFERENCE_PERFORMANCE_COUNTER
+            | DELIVERED_PERFORMANCE_COUNTER
+            | REFERENCE_PERFORMANCE
+            | LOWEST_FREQUENCY
+            | NOMINAL_FREQUENCY
+            | TRANSITION_LATENCY
+    )
+}
@@ -0,0 +1,399 @@
+use rustsbi::{SbiRet, SharedPtr, TriggerMask};
+
+use crate::platform::PLATFORM;
+use crate::riscv::csr::{CSR_TINFO, CSR_TSELECT, trigger};
+use crate::riscv::current_hartid;
+use crate::sbi::early_trap::{TrapInfo, csr_read_allow};
+use crate::sbi::trap_stack::{hart_context, hart_context_mut};
+
+/// Maximum number of debug triggers supported on a hart.
+const DBTR_TRIGGER_MAX: usize = 16;
+/// Words in a shared memory entry: `tstate` (or trigger index), `tdata1`, `tdata2` and `tdata3`.
+const ENTRY_WORDS: usize = 4;
+/// `tstate` bit set for a trigger installed by S-mode.
+const TSTATE_MAPPED: usize = 1 << 0;
+
+/// Trigger type field of `tdata1`.
+const TDATA1_TYPE_SHIFT: usize = 60;
+/// Debug mode only bit of `tdata1`, common to all trigger types.
+const TDATA1_DMODE: usize = 1 << 59;
+/// Instruction count trigger type.
+const TYPE_ICOUNT: usize = 3;
+/// Match control type 6 trigger type.
+const TYPE_MCONTROL6: usize = 6;
+/// `tinfo.info` value of a trigger index with no trigger behind it.
+const TINFO_NONE: usize = 1 << 0;
+/// `tinfo.info` field, a bitmap of supported trigger types.
+const TINFO_INFO: usize = 0xffff;
+
+/// Machine mode, privilege enable and action fields of `mcontrol6`.
+mod mcontrol6 {
+    pub const MODES: usize = U | S | VU | VS;
+    pub const U: usize = 1 << 3;
+    pub const S: usize = 1 << 4;
+    pub const M: usize = 1 << 6;
+    pub const CHAIN: usize = 1 << 11;
+    pub const ACTION: usize = 0xf << 12;
+    pub const VU: usize = 1 << 23;
+    pub const VS: usize = 1 << 24;
+}
+
+/// Machine mode, privilege enable and action fields of `icount`.
+mod icount {
+    pub const MODES: usize = U | S | VU | VS;
+    pub const ACTION: usize = 0x3f;
+    pub const U: usize = 1 << 6;
+    pub const S: usize = 1 << 7;
+    pub const PENDING: usize = 1 << 8;
+    pub const M: usize = 1 << 9;
+    pub const VU: usize = 1 << 25;
+    pub const VS: usize = 1 << 26;
+}
+
+/// `mhvalue` and `mhselect` fields of `textra64`, matching M-mode and hypervisor contexts.
+const TEXTRA_MHCONTEXT: usize = 0xffff << 48;
+
+/// A debug trigger of a hart.
+#[derive(Clone, Copy)]
+struct Trigger {
+    /// Index of the trigger in `tselect`.
+    hw_idx: usize,
+    /// Bitmap of supported trigger types.
+    types: usize,
+    installed: bool,
+    enabled: bool,
+    /// Filtered `tdata1`, `tdata2` and `tdata3` installed by S-mode.
+    tdata: [usize; 3],
+}
+
+impl Trigger {
+    const fn new() -> Self {
+        Self {
+            hw_idx: 0,
+            types: 0,
+            installed: false,
+            enabled: false,
+            tdata: [0; 3],
+        }
+    }
+
+    #[inline]
+    fn supports(&self, tdata1: usize) -> bool {
+        self.types & (1 << (tdata1 >> TDATA1_TYPE_SHIFT)) != 0
+    }
+
+    /// Writes the trigger configuration to the hardware, with privilege modes cleared if
+    /// the trigger is disabled.
+    fn apply(&self) {
+        let [mut tdata1, tdata2, tdata3] = self.tdata;
+        if !self.enabled {
+            tdata1 &= !mode_bits(tdata1);
+        }
+        trigger::select(self.hw_idx);
+        trigger::write_tdata([tdata1, tdata2, tdata3]);
+    }
+
+    /// Clears the trigger configuration in the hardware.
+    fn clear(&self) {
+        trigger::select(self.hw_idx);
+        trigger::write_tdata([0; 3]);
+    }
+}
+
+/// Per-hart debug trigger table.
+pub(crate) struct DbtrState {
+    /// Triggers usable by S-mode, indexed by logical trigger index.
+    triggers: [Trigger; DBTR_TRIGGER_MAX],
+    /// Number of triggers on this hart.
+    count: usize,
+    /// Address of the shared memory, if enabled.
+    shmem: Option<usize>,
+}
+
+impl DbtrState {
+    /// Creates the trigger table of the current hart by probing `tselect` and `tinfo`.
+    ///
+    /// Only triggers supporting `mcontrol6` or `icount` are usable by S-mode.
+    pub fn new() -> Self {
+        let mut state = Self {
+            triggers: [Trigger::new(); DBTR_TRIGGER_MAX],
+            count: 0,
+            shmem: None,
+        };
+        if !has_csr!(CSR_TSELECT) {
+            return state;
+        }
+        let mut trap_info = TrapInfo::default();
+        for hw_idx in 0.. {
+            if state.count == DBTR_TRIGGER_MAX || trigger::select(hw_idx) != hw_idx {
+                break;
+            }
+            let tinfo = unsafe { csr_read_allow::<CSR_TINFO>(&mut trap_info) };
+            let types = if trap_info.mcause == usize::MAX {
+                tinfo & TINFO_INFO
+            } else {
+                // Without `tinfo`, each trigger supports the type it reports in `tdata1`.
+                1 << (trigger::read_tdata()[0] >> TDATA1_TYPE_SHIFT)
+            };
+            if types == TINFO_NONE {
+                break;
+            }
+            if types & (1 << TYPE_MCONTROL6 | 1 << TYPE_ICOUNT) == 0 {
+                continue;
+            }
+            state.triggers[state.count] = Trigger {
+                hw_idx,
+                types,
+                ..Trigger::new()
+            };
+            state.count += 1;
+        }
+        state
+    }
+
+    #[inline]
+    fn triggers(&self) -> &[Trigger] {
+        &self.triggers[..self.count]
+    }
+
+    #[inline]
+    fn triggers_mut(&mut self) -> &mut [Trigger] {
+        &mut self.triggers[..self.count]
+    }
+}
+
+/// Uninstalls all the debug triggers of the current hart and disables its shared memory.
+pub(crate) fn dbtr_reset() {
+    let state = &mut hart_context_mut(current_hartid()).dbtr;
+    for trigger in state.triggers_mut().iter_mut().filter(|t| t.installed) {
+        trigger.clear();
+        trigger.installed = false;
+        trigger.enabled = false;
+    }
+    state.shmem = None;
+}
+
+/// Implementation of SBI Debug Triggers extension.
+pub(crate) struct SbiDbtr;
+
+impl rustsbi::Dbtr for SbiDbtr {
+    fn num_triggers(&self, trig_tdata1: usize) -> usize {
+        let triggers = hart_context(current_hartid()).dbtr.triggers();
+        if trig_tdata1 == 0 {
+            return triggers.len();
+        }
+        triggers.iter().filter(|t| t.supports(trig_tdata1)).count()
+    }
+
+    fn set_shmem(&self, shmem: SharedPtr<u8>, flags: usize) -> SbiRet {
+        let state = &mut hart_context_mut(current_hartid()).dbtr;
+        if flags != 0 {
+            return SbiRet::invalid_param();
+        }
+        let (lo, hi) = (shmem.phys_addr_lo(), shmem.phys_addr_hi());
+        if lo == usize::MAX && hi == usize::MAX {
+            state.shmem = None;
+            return SbiRet::success(0);
+        }
+        if lo % size_of::<usize>() != 0 {
+            return SbiRet::invalid_param();
+        }
+        let size = state.count * ENTRY_WORDS * size_of::<usize>();
+        let in_memory = unsafe { PLATFORM.info.memory_region_of(lo, size) }.is_some();
+        if hi != 0 || !in_memory {
+            return SbiRet::invalid_address();
+        }
+        state.shmem = Some(lo);
+        SbiRet::success(0)
+    }
+
+    fn read_triggers(&self, trig_idx_base: usize, trig_count: usize) -> SbiRet {
+        let state = &hart_context(current_hartid()).dbtr;
+        let Some(shmem) = state.shmem else {
+            return SbiRet::no_shmem();
+        };
+        let triggers = state.triggers();
+        let range = trig_idx_base
+            .checked_add(trig_count)
+            .filter(|_| trig_idx_base < triggers.len())
+            .and_then(|end| triggers.get(trig_idx_base..end));
+        let Some(triggers) = range else {
+            return SbiRet::bad_range();
+        };
+        for (i, t) in triggers.iter().enumerate() {
+            // Installed triggers are read back from the hardware to report hit bits.
+            let [tdata1, tdata2, tdata3] = if t.installed {
+                trigger::select(t.hw_idx);
+                trigger::read_tdata()
+            } else {
+                [0; 3]
+            };
+            let tstate = if t.installed { TSTATE_MAPPED } else { 0 };
+            write_entry(shmem, i, [tstate, tdata1, tdata2, tdata3]);
+        }
+        SbiRet::success(0)
+    }
+
+    fn install_triggers(&self, trig_count: usize) -> SbiRet {
+        let state = &mut hart_context_mut(current_hartid()).dbtr;
+        let Some(shmem) = state.shmem else {
+            return SbiRet::no_shmem();
+        };
+        if trig_count > state.count {
+            return SbiRet::bad_range();
+        }
+        // Check and assign all the triggers before installing any of them.
+        let mut assigned = [0; DBTR_TRIGGER_MAX];
+        let mut taken = 0usize;
+        for (i, slot) in assigned.iter_mut().enumerate().take(trig_count) {
+            let [_, tdata1, tdata2, tdata3] = read_entry(shmem, i);
+            if filter_tdata([tdata1, tdata2, tdata3]).is_none() {
+                return SbiRet {
+                    error: SbiRet::invalid_param().error,
+                    value: i,
+                };
+            }
+            let free = (0..state.count).find(|&idx| {
+                let t = &state.triggers[idx];
+                !t.installed && taken & (1 << idx) == 0 && t.supports(tdata1)
+            });
+            let Some(idx) = free else {
+                return SbiRet {
+                    error: SbiRet::failed().error,
+                    value: i,
+                };
+            };
+            taken |= 1 << idx;
+            *slot = idx;
+        }
+        for (i, &idx) in assigned.iter().enumerate().take(trig_count) {
+            let [_, tdata1, tdata2, tdata3] = read_entry(shmem, i);
+            let trigger = &mut state.triggers[idx];
+            trigger.tdata = filter_tdata([tdata1, tdata2, tdata3]).unwrap();
+            trigger.installed = true;
+            trigger.enabled = true;
+            trigger.apply();
+            write_entry(shmem, i, [idx, tdata1, tdata2, tdata3]);
+        }
+        SbiRet::success(0)
+    }
+
+    fn update_triggers(&self, trig_count: usize) -> SbiRet {
+        let state = &mut hart_context_mut(current_hartid()).dbtr;
+        let Some(shmem) = state.shmem else {
+            return SbiRet::no_shmem();
+        };
+        if trig_count > state.count {
+            return SbiRet::bad_range();
+        }
+        // Check all the triggers before updating any of them.
+        for i in 0..trig_count {
+            let [idx, tdata1, tdata2, tdata3] = read_entry(shmem, i);
+            let Some(trigger) = state.triggers().get(idx) else {
+                return SbiRet::bad_range();
+            };
+            if !trigger.installed
+                || !trigger.supports(tdata1)
+                || filter_tdata([tdata1, tdata2, tdata3]).is_none()
+            {
+                return SbiRet {
+                    error: SbiRet::invalid_param().error,
+                    value: i,
+                };
+            }
+        }
+        for i in 0..trig_count {
+            let [idx, tdata1, tdata2, tdata3] = read_entry(shmem, i);
+            let trigger = &mut state.triggers[idx];
+            trigger.tdata = filter_tdata([tdata1, tdata2, tdata3]).unwrap();
+            trigger.apply();
+        }
+        SbiRet::success(0)
+    }
+
+    fn uninstall_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        for_each_installed(triggers, |trigger| {
+            trigger.clear();
+            trigger.installed = false;
+            trigger.enabled = false;
+        })
+    }
+
+    fn enable_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        for_each_installed(triggers, |trigger| {
+            trigger.enabled = true;
+            trigger.apply();
+        })
+    }
+
+    fn disable_triggers(&self, triggers: TriggerMask) -> SbiRet {
+        for_each_installed(triggers, |trigger| {
+            trigger.enabled = false;
+            trigger.apply();
+        })
+    }
+}
+
+/// Runs `f` on each trigger in `triggers`, after checking that all of them are installed.
+fn for_each_installed(triggers: TriggerMask, mut f: impl FnMut(&mut Trigger)) -> SbiRet {
+    let state = &mut hart_context_mut(current_hartid()).dbtr;
+    let (mask, base) = triggers.into_inner();
+    let indices = || {
+        (0..usize::BITS as usize)
+            .filter(move |bit| mask & (1 << bit) != 0)
+            .map(move |bit| base.checked_add(bit))
+    };
+    for idx in indices() {
+        if !idx
+            .and_then(|idx| state.triggers().get(idx))
+            .is_some_and(|t| t.installed)
+        {
+            return SbiRet::invalid_param();
+        }
+    }
+    for idx in indices().flatten() {
+        f(&mut state.triggers[idx]);
+    }
+    SbiRet::success(0)
+}
+
+/// Checks a trigger configuration from S-mode, and filters out the bits reserved for M-mode
+/// and debug mode.
+///
+/// Only `mcontrol6` and `icount` triggers raising breakpoint exceptions are accepted.
+fn filter_tdata([tdata1, tdata2, tdata3]: [usize; 3]) -> Option<[usize; 3]> {
+    let tdata1 = match tdata1 >> TDATA1_TYPE_SHIFT {
+        // Chained triggers must be adjacent, which trigger assignment does not guarantee.
+        TYPE_MCONTROL6 if tdata1 & (mcontrol6::ACTION | mcontrol6::CHAIN) == 0 => {
+            tdata1 & !(TDATA1_DMODE | mcontrol6::M)
+        }
+        TYPE_ICOUNT if tdata1 & icount::ACTION == 0 => {
+            tdata1 & !(TDATA1_DMODE | icount::M | icount::PENDING)
+        }
+        _ => return None,
+    };
+    Some([tdata1, tdata2, tdata3 & !TEXTRA_MHCONTEXT])
+}
+
+/// Returns the privilege mode enable bits of a filtered `tdata1`.
+fn mode_bits(tdata1: usize) -> usize {
+    match tdata1 >> TDATA1_TYPE_SHIFT {
+        TYPE_MCONTROL6 => tdata1 & mcontrol6::MODES,
+        TYPE_ICOUNT => tdata1 & icount::MODES,
+        _ => 0,
+    }
+}
+
+#[inline]
+fn read_entry(shmem: usize, i: usize) -> [usize; ENTRY_WORDS] {
+    let entry = (shmem as *const [usize; ENTRY_WORDS]).wrapping_add(i);
+    // SAFETY: `set_shmem` only accepts aligned memory holding an entry for each trigger.
+    unsafe { entry.read_volatile() }
+}
+
+#[inline]
+fn write_entry(shmem: usize, i: usize, value: [usize; ENTRY_WORDS]) {
+    let entry = (shmem as *mut [usize; ENTRY_WORDS]).wrapping_add(i);
+    // SAFETY: `set_shmem` only accepts aligned memory holding an entry for each trigger.
+    unsafe { entry.write_volatile(value) }
+}
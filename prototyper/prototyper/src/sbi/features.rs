@@ -44,17 +44,25 @@ pub enum Extension {
     Sstc = 0,
     Hypervisor = 1,
     Smaia = 2,
+    Svadu = 3,
+    Smnpm = 4,
+    Ssdbltrp = 5,
+    Sscofpmf = 6,
     // Remember to increment `Extension::COUNT` while implementing new extensions.
 }
 
 impl Extension {
-    pub const COUNT: usize = 3;
+    pub const COUNT: usize = 7;
 
     pub const fn as_str(&self) -> &'static str {
         match self {
             Self::Sstc => "sstc",
             Self::Hypervisor => "h",
             Self::Smaia => "smaia", // TODO verify with DTB standard
+            Self::Svadu => "svadu",
+            Self::Smnpm => "smnpm",
+            Self::Ssdbltrp => "ssdbltrp",
+            Self::Sscofpmf => "sscofpmf",
         }
     }
 
@@ -64,7 +72,16 @@ impl Extension {
     }
 
     pub fn iter() -> impl Iterator<Item = Self> {
-        [Self::Sstc, Self::Hypervisor, Self::Smaia].into_iter()
+        [
+            Self::Sstc,
+            Self::Hypervisor,
+            Self::Smaia,
+            Self::Svadu,
+            Self::Smnpm,
+            Self::Ssdbltrp,
+            Self::Sscofpmf,
+        ]
+        .into_iter()
     }
 }
 
@@ -0,0 +1,187 @@
+use riscv::register::medeleg;
+use rustsbi::SbiRet;
+use sbi_spec::fwft::{feature_type::*, flags::SetFlags};
+
+use crate::riscv::csr::menvcfg;
+use crate::riscv::current_hartid;
+
+use super::features::{
+    Extension, PrivilegedVersion, hart_extension_probe, hart_privileged_version,
+};
+use super::trap_stack::{hart_context, hart_context_mut};
+
+/// Per-hart firmware feature state.
+///
+/// Feature values live in the hart's CSRs; only the lock state is kept here.
+pub(crate) struct FwftState {
+    /// Bitmap of locked features, indexed by feature type.
+    locked: usize,
+}
+
+impl FwftState {
+    /// Creates a new state with no feature locked.
+    pub const fn new() -> Self {
+        Self { locked: 0 }
+    }
+
+    #[inline]
+    fn is_locked(&self, feature: usize) -> bool {
+        self.locked & (1 << feature) != 0
+    }
+
+    #[inline]
+    fn lock(&mut self, feature: usize) {
+        self.locked |= 1 << feature;
+    }
+}
+
+/// Restores every firmware feature of the current hart to its default value and unlocks it.
+pub(crate) fn fwft_reset() {
+    let hart_id = current_hartid();
+    unsafe {
+        medeleg::clear_load_misaligned();
+        medeleg::clear_store_misaligned();
+    }
+    if hart_privileged_version(hart_id) >= PrivilegedVersion::Version1_12 {
+        let mut bits = 0;
+        if hart_extension_probe(hart_id, Extension::Svadu) {
+            bits |= menvcfg::ADUE;
+        }
+        if hart_extension_probe(hart_id, Extension::Smnpm) {
+            bits |= menvcfg::PMM;
+        }
+        if hart_extension_probe(hart_id, Extension::Ssdbltrp) {
+            bits |= menvcfg::DTE;
+        }
+        menvcfg::clear_bits(bits);
+    }
+    hart_context_mut(hart_id).fwft = FwftState::new();
+}
+
+/// Implementation of SBI Firmware Features extension.
+pub(crate) struct SbiFwft;
+
+impl rustsbi::Fwft for SbiFwft {
+    fn set(&self, feature: u32, value: usize, flags: usize) -> SbiRet {
+        let feature = feature as usize;
+        let hart_id = current_hartid();
+        let Some(flags) = SetFlags::from_bits(flags) else {
+            return SbiRet::invalid_param();
+        };
+        if let Err(ret) = check_feature(hart_id, feature) {
+            return ret;
+        }
+        if hart_context(hart_id).fwft.is_locked(feature) {
+            return SbiRet::denied_locked();
+        }
+
+        let ret = match feature {
+            MISALIGNED_EXC_DELEG => set_misaligned_delegation(value),
+            PTE_AD_HW_UPDATING => set_menvcfg_bit(menvcfg::ADUE, value),
+            DOUBLE_TRAP => set_menvcfg_bit(menvcfg::DTE, value),
+            POINTER_MASKING_PMLEN => set_pointer_masking(value),
+            _ => unreachable!(),
+        };
+        if ret.is_ok() && flags.contains(SetFlags::LOCK) {
+            hart_context_mut(hart_id).fwft.lock(feature);
+        }
+        ret
+    }
+
+    fn get(&self, feature: u32) -> SbiRet {
+        let feature = feature as usize;
+        if let Err(ret) = check_feature(current_hartid(), feature) {
+            return ret;
+        }
+
+        match feature {
+            MISALIGNED_EXC_DELEG => {
+                let medeleg = medeleg::read();
+                let delegated = medeleg.load_misaligned() && medeleg.store_misaligned();
+                SbiRet::success(delegated as usize)
+            }
+            PTE_AD_HW_UPDATING => SbiRet::success((menvcfg::read() & menvcfg::ADUE != 0) as usize),
+            DOUBLE_TRAP => SbiRet::success((menvcfg::read() & menvcfg::DTE != 0) as usize),
+            POINTER_MASKING_PMLEN => match menvcfg::read() & menvcfg::PMM {
+                menvcfg::PMM_PMLEN_7 => SbiRet::success(7),
+                menvcfg::PMM_PMLEN_16 => SbiRet::success(16),
+                _ => SbiRet::success(0),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Checks whether `feature` is implemented on the given hart.
+///
+/// Reserved and platform-specific features are denied; standard features missing
+/// hardware support on this hart are not supported.
+fn check_feature(hart_id: usize, feature: usize) -> Result<(), SbiRet> {
+    let has_menvcfg = hart_privileged_version(hart_id) >= PrivilegedVersion::Version1_12;
+    let supported = match feature {
+        MISALIGNED_EXC_DELEG => true,
+        PTE_AD_HW_UPDATING => has_menvcfg && hart_extension_probe(hart_id, Extension::Svadu),
+        DOUBLE_TRAP => has_menvcfg && hart_extension_probe(hart_id, Extension::Ssdbltrp),
+        POINTER_MASKING_PMLEN => has_menvcfg && hart_extension_probe(hart_id, Extension::Smnpm),
+        LANDING_PAD | SHADOW_STACK => false,
+        _ => return Err(SbiRet::denied()),
+    };
+    if supported {
+        Ok(())
+    } else {
+        Err(SbiRet::not_supported())
+    }
+}
+
+/// Delegates misaligned load and store exceptions to S-mode, or handles them in M-mode.
+fn set_misaligned_delegation(value: usize) -> SbiRet {
+    unsafe {
+        match value {
+            0 => {
+                medeleg::clear_load_misaligned();
+                medeleg::clear_store_misaligned();
+            }
+            1 => {
+                medeleg::set_load_misaligned();
+                medeleg::set_store_misaligned();
+            }
+            _ => return SbiRet::invalid_param(),
+        }
+    }
+    SbiRet::success(0)
+}
+
+/// Sets or clears a single-bit feature in `menvcfg`.
+fn set_menvcfg_bit(bit: usize, value: usize) -> SbiRet {
+    match value {
+        0 => menvcfg::clear_bits(bit),
+        1 => menvcfg::set_bits(bit),
+        _ => return SbiRet::invalid_param(),
+    }
+    SbiRet::success(0)
+}
+
+/// Selects the smallest pointer masking tag length no less than `value`.
+///
+/// `PMM` is WARL, so each candidate is written and read back to find a length the hart supports.
+fn set_pointer_masking(value: usize) -> SbiRet {
+    if value == 0 {
+        menvcfg::clear_bits(menvcfg::PMM);
+        return SbiRet::success(0);
+    }
+    let old = menvcfg::read() & menvcfg::PMM;
+    for (pmlen, pmm) in [(7, menvcfg::PMM_PMLEN_7), (16, menvcfg::PMM_PMLEN_16)] {
+        if value > pmlen {
+            continue;
+        }
+        menvcfg::clear_bits(menvcfg::PMM);
+        menvcfg::set_bits(pmm);
+        if menvcfg::read() & menvcfg::PMM == pmm {
+            return SbiRet::success(0);
+        }
+    }
+    // Leave the feature untouched on failure.
+    menvcfg::clear_bits(menvcfg::PMM);
+    menvcfg::set_bits(old);
+    SbiRet::invalid_param()
+}
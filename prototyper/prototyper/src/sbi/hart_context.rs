@@ -8,7 +8,10 @@ use core::sync::atomic::Ordering;
 use fast_trap::FlowContext;
 use riscv::register::mstatus;
 
+use super::dbtr::DbtrState;
+use super::fwft::FwftState;
 use super::pmu::PmuState;
+use super::sse::SseState;
 
 /// Context for managing hart (hardware thread) state and operations.
 pub(crate) struct HartContext {
@@ -24,6 +27,12 @@ pub(crate) struct HartContext {
     pub features: HartFeatures,
     /// PMU State
     pub pmu_state: PmuState,
+    /// Firmware feature lock state.
+    pub fwft: FwftState,
+    /// Software event state.
+    pub sse: SseState,
+    /// Debug trigger table.
+    pub dbtr: DbtrState,
 }
 
 // Make sure HartContext is aligned.
@@ -40,6 +49,9 @@ impl HartContext {
         self.hsm = HsmCell::new();
         self.rfence = RFenceCell::new();
         self.pmu_state = PmuState::new();
+        self.fwft = FwftState::new();
+        self.sse = SseState::new();
+        self.dbtr = DbtrState::new();
     }
 
     /// Get a non-null pointer to the trap context.
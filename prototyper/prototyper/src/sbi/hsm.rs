@@ -12,7 +12,9 @@ use crate::sbi::hart_context::NextStage;
 use crate::sbi::trap_stack::ROOT_STACK;
 use crate::sbi::trap_stack::hart_context_mut;
 
-use super::{trap::boot::boot, trap_stack::hart_context};
+use super::{
+    dbtr::dbtr_reset, fwft::fwft_reset, sse::sse_reset, trap::boot::boot, trap_stack::hart_context,
+};
 
 /// Special state indicating a hart is in the process of starting.
 const HART_STATE_START_PENDING_EXT: usize = usize::MAX;
@@ -233,6 +235,11 @@ impl rustsbi::Hsm for SbiHsm {
     /// Stops execution on the current hart.
     #[inline]
     fn hart_stop(&self) -> SbiRet {
+        // Firmware features, software events and debug triggers return to their defaults
+        // for the next `hart_start`.
+        fwft_reset();
+        sse_reset();
+        dbtr_reset();
         local_hsm().stop();
         unsafe {
             riscv::register::mie::clear_msoft();
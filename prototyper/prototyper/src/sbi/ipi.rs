@@ -17,6 +17,8 @@ use spin::Mutex;
 pub(crate) const IPI_TYPE_SSOFT: u8 = 1 << 0;
 /// IPI type for memory fence operations.
 pub(crate) const IPI_TYPE_FENCE: u8 = 1 << 1;
+/// IPI type for supervisor software events pending on the target hart.
+pub(crate) const IPI_TYPE_SSE: u8 = 1 << 2;
 
 /// Trait defining interface for inter-processor interrupt device
 #[allow(unused)]
@@ -1,11 +1,15 @@
 use rustsbi::RustSBI;
 
 pub mod console;
+pub mod cppc;
+pub mod dbtr;
+pub mod fwft;
 pub mod hsm;
 pub mod ipi;
 pub mod pmu;
 pub mod reset;
 pub mod rfence;
+pub mod sse;
 pub mod suspend;
 
 pub mod early_trap;
@@ -18,15 +22,19 @@ pub mod trap;
 pub mod trap_stack;
 
 use console::SbiConsole;
+use cppc::SbiCppc;
+use dbtr::SbiDbtr;
+use fwft::SbiFwft;
 use hsm::SbiHsm;
 use ipi::SbiIpi;
 use pmu::SbiPmu;
 use reset::SbiReset;
 use rfence::SbiRFence;
+use sse::SbiSse;
 use suspend::SbiSuspend;
 
 #[derive(RustSBI, Default)]
-#[rustsbi(dynamic)]
+#[rustsbi(dynamic, spec_version = "3.0")]
 #[allow(clippy::upper_case_acronyms)]
 pub struct SBI {
     #[rustsbi(console)]
@@ -43,6 +51,14 @@ pub struct SBI {
     pub pmu: Option<SbiPmu>,
     #[rustsbi(susp)]
     pub susp: Option<SbiSuspend>,
+    #[rustsbi(fwft)]
+    pub fwft: Option<SbiFwft>,
+    #[rustsbi(sse)]
+    pub sse: Option<SbiSse>,
+    #[rustsbi(dbtr)]
+    pub dbtr: Option<SbiDbtr>,
+    #[rustsbi(cppc)]
+    pub cppc: Option<SbiCppc>,
 }
 
 impl SBI {
@@ -55,6 +71,10 @@ impl SBI {
             rfence: None,
             pmu: None,
             susp: None,
+            fwft: None,
+            sse: None,
+            dbtr: None,
+            cppc: None,
         }
     }
 }
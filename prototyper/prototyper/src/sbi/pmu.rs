@@ -1,5 +1,6 @@
 use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use core::arch::asm;
 use riscv::register::*;
 use rustsbi::{Pmu, SbiRet};
 use sbi_spec::binary::SharedPtr;
@@ -10,6 +11,7 @@ use crate::riscv::csr::*;
 use crate::{riscv::current_hartid, sbi::features::hart_mhpm_mask};
 
 use super::features::{PrivilegedVersion, hart_privileged_version};
+use super::sse;
 use super::trap_stack::{hart_context, hart_context_mut};
 
 /// Maximum number of hardware performance counters supported.
@@ -1205,3 +1207,33 @@ pub fn pmu_firmware_counter_increment(firmware_event: usize) {
         }
     }
 }
+
+/// Interrupt code of the local counter overflow interrupt (Sscofpmf).
+pub(crate) const IRQ_LCOFI: usize = 13;
+
+/// Routes local counter overflow interrupts to M-mode to raise the PMU overflow software
+/// event, or delegates them back to S-mode.
+pub(crate) fn route_overflow_to_sse(route: bool) {
+    let bit = 1usize << IRQ_LCOFI;
+    unsafe {
+        if route {
+            asm!("csrc mideleg, {0}", "csrs mie, {0}", in(reg) bit);
+        } else {
+            asm!("csrc mie, {0}", "csrs mideleg, {0}", in(reg) bit);
+        }
+    }
+}
+
+/// Handles a local counter overflow interrupt routed to M-mode.
+///
+/// The interrupt stays disabled until the PMU overflow software event completes.
+pub(crate) fn pmu_overflow_handler() {
+    let bit = 1usize << IRQ_LCOFI;
+    unsafe { asm!("csrc mie, {0}", "csrc mip, {0}", in(reg) bit) };
+    sse::raise_local(sbi_spec::sse::event_id::LOCAL_PMU_OVERFLOW);
+}
+
+/// Re-enables local counter overflow interrupts once the PMU overflow software event completes.
+pub(crate) fn overflow_event_completed() {
+    unsafe { asm!("csrs mie, {}", in(reg) 1usize << IRQ_LCOFI) };
+}
@@ -33,6 +33,10 @@ impl rustsbi::Reset for SbiReset {
             RESET_REASON_NO_REASON, RESET_REASON_SYSTEM_FAILURE, RESET_TYPE_COLD_REBOOT,
             RESET_TYPE_SHUTDOWN, RESET_TYPE_WARM_REBOOT,
         };
+        // Reset reasons `0x2..=0xDFFFFFFF` are reserved; reject them before any reset happens.
+        if (RESET_REASON_SYSTEM_FAILURE + 1..0xE000_0000).contains(&reset_reason) {
+            return SbiRet::invalid_param();
+        }
         match reset_type {
             RESET_TYPE_SHUTDOWN => match reset_reason {
                 RESET_REASON_NO_REASON => self.reset_dev.lock().pass(),
@@ -0,0 +1,673 @@
+use core::arch::asm;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use fast_trap::{FastContext, FastResult};
+use riscv::register::{mepc, mstatus, sepc};
+use rustsbi::{Physical, SbiRet};
+use sbi_spec::sse::{
+    attr_id, event_id,
+    event_state::{ENABLED, REGISTERED, RUNNING, UNUSED},
+    flags::{ConfigFlags, InterruptedFlags, StatusFlags},
+};
+use spin::Mutex;
+
+use crate::platform::PLATFORM;
+use crate::riscv::current_hartid;
+use crate::sbi::features::{Extension, hart_extension_probe};
+use crate::sbi::hsm::remote_hsm;
+use crate::sbi::ipi::{IPI_TYPE_SSE, set_ipi_type};
+use crate::sbi::pmu;
+use crate::sbi::trap_stack::{hart_context, hart_context_mut};
+
+/// Local events, in the order of their slots in [`SseState`].
+const LOCAL_EVENTS: [u32; 2] = [
+    event_id::LOCAL_PMU_OVERFLOW,
+    event_id::LOCAL_SOFTWARE_INJECTED,
+];
+/// Global events, in the order of their slots in [`SbiSse`].
+const GLOBAL_EVENTS: [u32; 1] = [event_id::GLOBAL_SOFTWARE_INJECTED];
+/// Maximum number of events running on a hart, each one preempting the previous one.
+const MAX_NESTING: usize = LOCAL_EVENTS.len() + GLOBAL_EVENTS.len();
+
+/// Standard event IDs which are valid but not served by this implementation.
+const UNSUPPORTED_EVENTS: [u32; 5] = [
+    event_id::LOCAL_HIGH_PRIO_RAS,
+    event_id::LOCAL_DOUBLE_TRAP,
+    event_id::GLOBAL_HIGH_PRIO_RAS,
+    event_id::LOCAL_LOW_PRIO_RAS,
+    event_id::GLOBAL_LOW_PRIO_RAS,
+];
+
+const MSTATUS_SIE: usize = 1 << 1;
+const MSTATUS_SPIE: usize = 1 << 5;
+const MSTATUS_SPP: usize = 1 << 8;
+const MSTATUS_MPP: usize = 0b11 << 11;
+const MSTATUS_MPP_SUPERVISOR: usize = 0b01 << 11;
+const MSTATUS_MPV: usize = 1 << 39;
+
+/// Slot of an event, in the local events of a hart or in the global events.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Local(usize),
+    Global(usize),
+}
+
+impl Slot {
+    #[inline]
+    fn event_id(self) -> u32 {
+        match self {
+            Slot::Local(idx) => LOCAL_EVENTS[idx],
+            Slot::Global(idx) => GLOBAL_EVENTS[idx],
+        }
+    }
+}
+
+/// Attributes of a software event.
+#[derive(Clone, Copy)]
+struct Event {
+    state: usize,
+    priority: u32,
+    config: ConfigFlags,
+    preferred_hart: usize,
+    entry_pc: usize,
+    entry_arg: usize,
+    /// `INTERRUPTED_SEPC`, `INTERRUPTED_FLAGS`, `INTERRUPTED_A6` and `INTERRUPTED_A7`.
+    interrupted: [usize; 4],
+}
+
+impl Event {
+    const fn new() -> Self {
+        Self {
+            state: UNUSED,
+            priority: 0,
+            config: ConfigFlags::empty(),
+            preferred_hart: 0,
+            entry_pc: 0,
+            entry_arg: 0,
+            interrupted: [0; 4],
+        }
+    }
+}
+
+/// Per-hart software event state.
+pub(crate) struct SseState {
+    /// Local events of this hart.
+    events: [Event; LOCAL_EVENTS.len()],
+    /// Bitmap of pending local events, set by any hart.
+    pending: AtomicU32,
+    /// Whether events are masked on this hart.
+    masked: bool,
+    /// Events running on this hart, the innermost one last.
+    running: [Option<Slot>; MAX_NESTING],
+    /// Interrupted state of the event completed by the ongoing `sse_complete` call.
+    completed: Option<[usize; 4]>,
+}
+
+impl SseState {
+    /// Creates the state of a started hart, with all events unused and masked.
+    pub const fn new() -> Self {
+        Self {
+            events: [Event::new(); LOCAL_EVENTS.len()],
+            pending: AtomicU32::new(0),
+            masked: true,
+            running: [None; MAX_NESTING],
+            completed: None,
+        }
+    }
+
+    #[inline]
+    fn innermost(&self) -> Option<Slot> {
+        self.running.iter().rev().find_map(|slot| *slot)
+    }
+}
+
+/// Implementation of SBI Supervisor Software Events extension.
+pub(crate) struct SbiSse {
+    /// Global events, shared by all harts.
+    global: Mutex<[Event; GLOBAL_EVENTS.len()]>,
+    /// Bitmap of pending global events.
+    pending: AtomicU32,
+}
+
+impl SbiSse {
+    pub const fn new() -> Self {
+        Self {
+            global: Mutex::new([Event::new(); GLOBAL_EVENTS.len()]),
+            pending: AtomicU32::new(0),
+        }
+    }
+
+    /// Runs `f` on the attributes of the event in `slot`; local events are those of the
+    /// current hart.
+    #[inline]
+    fn with_event<R>(&self, slot: Slot, f: impl FnOnce(&mut Event) -> R) -> R {
+        match slot {
+            Slot::Local(idx) => f(&mut hart_context_mut(current_hartid()).sse.events[idx]),
+            Slot::Global(idx) => f(&mut self.global.lock()[idx]),
+        }
+    }
+
+    #[inline]
+    fn is_pending(&self, slot: Slot) -> bool {
+        match slot {
+            Slot::Local(idx) => {
+                hart_context(current_hartid())
+                    .sse
+                    .pending
+                    .load(Ordering::Relaxed)
+                    & (1 << idx)
+                    != 0
+            }
+            Slot::Global(idx) => self.pending.load(Ordering::Relaxed) & (1 << idx) != 0,
+        }
+    }
+
+    #[inline]
+    fn clear_pending(&self, slot: Slot) {
+        match slot {
+            Slot::Local(idx) => hart_context(current_hartid())
+                .sse
+                .pending
+                .fetch_and(!(1 << idx), Ordering::Relaxed),
+            Slot::Global(idx) => self.pending.fetch_and(!(1 << idx), Ordering::Relaxed),
+        };
+    }
+}
+
+impl rustsbi::Sse for SbiSse {
+    fn read_attrs(
+        &self,
+        event_id: u32,
+        base_attr_id: u32,
+        output: Physical<&mut [usize]>,
+    ) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        let (start, count) = match checked_attrs(base_attr_id, &output) {
+            Ok(buf) => buf,
+            Err(err) => return err,
+        };
+        let pending = self.is_pending(slot);
+        let values = self.with_event(slot, |event| {
+            let mut values = [0; attr_id::COUNT as usize];
+            for (attr, value) in values.iter_mut().enumerate() {
+                *value = read_attr(slot, event, pending, attr as u32);
+            }
+            values
+        });
+        // SAFETY: `checked_attrs` only accepts aligned buffers inside a memory region.
+        let output = unsafe { core::slice::from_raw_parts_mut(start as *mut usize, count) };
+        let base = base_attr_id as usize;
+        output.copy_from_slice(&values[base..base + count]);
+        SbiRet::success(0)
+    }
+
+    fn write_attrs(&self, event_id: u32, base_attr_id: u32, input: Physical<&[usize]>) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        let (start, count) = match checked_attrs(base_attr_id, &input) {
+            Ok(buf) => buf,
+            Err(err) => return err,
+        };
+        // SAFETY: `checked_attrs` only accepts aligned buffers inside a memory region.
+        let input = unsafe { core::slice::from_raw_parts(start as *const usize, count) };
+        let attrs = (base_attr_id..).zip(input.iter().copied());
+        self.with_event(slot, |event| {
+            // Check all the attributes before writing any of them.
+            for (attr, value) in attrs.clone() {
+                if let Err(err) = check_attr_write(slot, event, attr, value) {
+                    return err;
+                }
+            }
+            for (attr, value) in attrs {
+                write_attr(event, attr, value);
+            }
+            SbiRet::success(0)
+        })
+    }
+
+    fn register(&self, event_id: u32, handler_entry_pc: usize, handler_entry_arg: usize) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        if handler_entry_pc & 0x1 != 0 {
+            return SbiRet::invalid_param();
+        }
+        let ret = self.with_event(slot, |event| {
+            if event.state != UNUSED {
+                return SbiRet::invalid_state();
+            }
+            *event = Event {
+                state: REGISTERED,
+                preferred_hart: current_hartid(),
+                entry_pc: handler_entry_pc,
+                entry_arg: handler_entry_arg,
+                ..Event::new()
+            };
+            SbiRet::success(0)
+        });
+        if ret.is_ok() {
+            // Injections before registration are dropped.
+            self.clear_pending(slot);
+        }
+        ret
+    }
+
+    fn unregister(&self, event_id: u32) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        self.with_event(slot, |event| {
+            if event.state != REGISTERED {
+                return SbiRet::invalid_state();
+            }
+            event.state = UNUSED;
+            SbiRet::success(0)
+        })
+    }
+
+    fn enable(&self, event_id: u32) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        let ret = self.with_event(slot, |event| {
+            if event.state != REGISTERED {
+                return SbiRet::invalid_state();
+            }
+            event.state = ENABLED;
+            SbiRet::success(0)
+        });
+        if ret.is_ok() && event_id == event_id::LOCAL_PMU_OVERFLOW {
+            pmu::route_overflow_to_sse(true);
+        }
+        ret
+    }
+
+    fn disable(&self, event_id: u32) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        let ret = self.with_event(slot, |event| {
+            if event.state != ENABLED {
+                return SbiRet::invalid_state();
+            }
+            event.state = REGISTERED;
+            SbiRet::success(0)
+        });
+        if ret.is_ok() && event_id == event_id::LOCAL_PMU_OVERFLOW {
+            pmu::route_overflow_to_sse(false);
+        }
+        ret
+    }
+
+    fn complete(&self) -> SbiRet {
+        let state = &mut hart_context_mut(current_hartid()).sse;
+        // Without a running event there is nothing to complete.
+        let Some(top) = state.running.iter_mut().rev().find(|slot| slot.is_some()) else {
+            return SbiRet::success(0);
+        };
+        let slot = top.take().unwrap();
+        let (interrupted, enabled) = self.with_event(slot, |event| {
+            event.state = if event.config.contains(ConfigFlags::ONESHOT) {
+                REGISTERED
+            } else {
+                ENABLED
+            };
+            (event.interrupted, event.state == ENABLED)
+        });
+        if slot.event_id() == event_id::LOCAL_PMU_OVERFLOW {
+            if enabled {
+                pmu::overflow_event_completed();
+            } else {
+                pmu::route_overflow_to_sse(false);
+            }
+        }
+        hart_context_mut(current_hartid()).sse.completed = Some(interrupted);
+        SbiRet::success(0)
+    }
+
+    fn inject(&self, event_id: u32, hart_id: usize) -> SbiRet {
+        let slot = match lookup(event_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        let target = match slot {
+            Slot::Local(idx) => {
+                if !hart_online(hart_id) {
+                    return SbiRet::invalid_param();
+                }
+                hart_context(hart_id)
+                    .sse
+                    .pending
+                    .fetch_or(1 << idx, Ordering::Relaxed);
+                hart_id
+            }
+            // Global events are injected on their preferred hart; `hart_id` is ignored.
+            Slot::Global(idx) => {
+                let global = self.global.lock();
+                self.pending.fetch_or(1 << idx, Ordering::Relaxed);
+                global[idx].preferred_hart
+            }
+        };
+        // Events pending on the current hart are delivered when this call returns.
+        if target != current_hartid() && set_ipi_type(target, IPI_TYPE_SSE) == 0 {
+            unsafe { PLATFORM.sbi.ipi.as_ref().unwrap().set_msip(target) };
+        }
+        SbiRet::success(0)
+    }
+
+    fn hart_unmask(&self) -> SbiRet {
+        let state = &mut hart_context_mut(current_hartid()).sse;
+        if !state.masked {
+            return SbiRet::already_started();
+        }
+        state.masked = false;
+        SbiRet::success(0)
+    }
+
+    fn hart_mask(&self) -> SbiRet {
+        let state = &mut hart_context_mut(current_hartid()).sse;
+        if state.masked {
+            return SbiRet::already_stopped();
+        }
+        state.masked = true;
+        SbiRet::success(0)
+    }
+}
+
+/// Resolves `event_id` to its slot for the current hart.
+fn lookup(event_id: u32) -> Result<Slot, SbiRet> {
+    if let Some(idx) = LOCAL_EVENTS.iter().position(|&id| id == event_id) {
+        if event_id == event_id::LOCAL_PMU_OVERFLOW
+            && !hart_extension_probe(current_hartid(), Extension::Sscofpmf)
+        {
+            return Err(SbiRet::not_supported());
+        }
+        return Ok(Slot::Local(idx));
+    }
+    if let Some(idx) = GLOBAL_EVENTS.iter().position(|&id| id == event_id) {
+        return Ok(Slot::Global(idx));
+    }
+    // Platform-specific events are valid, but none of them is implemented.
+    if UNSUPPORTED_EVENTS.contains(&event_id) || event_id & event_id::PLATFORM_BIT != 0 {
+        return Err(SbiRet::not_supported());
+    }
+    Err(SbiRet::invalid_param())
+}
+
+/// Checks the attribute range and its shared memory, returning the memory address and the
+/// number of attributes.
+fn checked_attrs<P>(base_attr_id: u32, buf: &Physical<P>) -> Result<(usize, usize), SbiRet> {
+    const ALIGN: usize = size_of::<usize>();
+    let count = buf.num_bytes() / ALIGN;
+    if count == 0 {
+        return Err(SbiRet::invalid_param());
+    }
+    if (base_attr_id as usize)
+        .checked_add(count)
+        .is_none_or(|end| end > attr_id::COUNT as usize)
+    {
+        return Err(SbiRet::bad_range());
+    }
+    let start = buf.phys_addr_lo();
+    if buf.phys_addr_hi() != 0 || start % ALIGN != 0 {
+        return Err(SbiRet::invalid_address());
+    }
+    match unsafe { PLATFORM.info.memory_region_of(start, buf.num_bytes()) } {
+        Some(_) => Ok((start, count)),
+        None => Err(SbiRet::invalid_address()),
+    }
+}
+
+fn read_attr(slot: Slot, event: &Event, pending: bool, attr: u32) -> usize {
+    match attr {
+        attr_id::STATUS => {
+            // All events served here can be injected.
+            let mut flags = StatusFlags::INJECT;
+            flags.set(StatusFlags::PENDING, pending);
+            event.state | flags.bits()
+        }
+        attr_id::PRIORITY => event.priority as usize,
+        attr_id::CONFIG => event.config.bits(),
+        attr_id::PREFERRED_HART => match slot {
+            Slot::Local(_) => current_hartid(),
+            Slot::Global(_) => event.preferred_hart,
+        },
+        attr_id::ENTRY_PC => event.entry_pc,
+        attr_id::ENTRY_ARG => event.entry_arg,
+        attr_id::INTERRUPTED_SEPC..=attr_id::INTERRUPTED_A7 => {
+            event.interrupted[(attr - attr_id::INTERRUPTED_SEPC) as usize]
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn check_attr_write(slot: Slot, event: &Event, attr: u32, value: usize) -> Result<(), SbiRet> {
+    match attr {
+        attr_id::STATUS | attr_id::ENTRY_PC | attr_id::ENTRY_ARG => Err(SbiRet::bad_range()),
+        attr_id::PREFERRED_HART if matches!(slot, Slot::Local(_)) => Err(SbiRet::bad_range()),
+        attr_id::PREFERRED_HART if !hart_online(value) => Err(SbiRet::invalid_param()),
+        attr_id::PREFERRED_HART => Ok(()),
+        attr_id::PRIORITY | attr_id::CONFIG => {
+            let valid = match attr {
+                attr_id::PRIORITY => u32::try_from(value).is_ok(),
+                _ => ConfigFlags::from_bits(value).is_some(),
+            };
+            if !valid {
+                Err(SbiRet::invalid_param())
+            } else if !matches!(event.state, UNUSED | REGISTERED) {
+                Err(SbiRet::invalid_state())
+            } else {
+                Ok(())
+            }
+        }
+        attr_id::INTERRUPTED_SEPC..=attr_id::INTERRUPTED_A7 => {
+            let running_here = event.state == RUNNING
+                && hart_context(current_hartid())
+                    .sse
+                    .running
+                    .contains(&Some(slot));
+            if !running_here {
+                Err(SbiRet::invalid_state())
+            } else if attr == attr_id::INTERRUPTED_FLAGS
+                && InterruptedFlags::from_bits(value).is_none_or(|flags| {
+                    !(InterruptedFlags::SSTATUS_SPP | InterruptedFlags::SSTATUS_SPIE)
+                        .contains(flags)
+                })
+            {
+                // Hypervisor, landing pad and double trap states are not saved.
+                Err(SbiRet::invalid_param())
+            } else {
+                Ok(())
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn write_attr(event: &mut Event, attr: u32, value: usize) {
+    match attr {
+        attr_id::PRIORITY => event.priority = value as u32,
+        attr_id::CONFIG => event.config = ConfigFlags::from_bits_retain(value),
+        attr_id::PREFERRED_HART => event.preferred_hart = value,
+        attr_id::INTERRUPTED_SEPC..=attr_id::INTERRUPTED_A7 => {
+            event.interrupted[(attr - attr_id::INTERRUPTED_SEPC) as usize] = value
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Checks whether `hart_id` is a started hart able to receive events.
+fn hart_online(hart_id: usize) -> bool {
+    let enabled = unsafe { PLATFORM.info.cpu_enabled }
+        .is_some_and(|list| list.get(hart_id).copied().unwrap_or(false));
+    enabled && remote_hsm(hart_id).is_some_and(|hsm| hsm.allow_ipi())
+}
+
+/// Marks a local event pending on the current hart, e.g. from an interrupt handler.
+///
+/// The event is delivered once the handler returns through [`deliver`].
+pub(crate) fn raise_local(event_id: u32) {
+    if let Some(idx) = LOCAL_EVENTS.iter().position(|&id| id == event_id) {
+        hart_context(current_hartid())
+            .sse
+            .pending
+            .fetch_or(1 << idx, Ordering::Relaxed);
+    }
+}
+
+/// Restores the software event state of the current hart for the next `hart_start`.
+pub(crate) fn sse_reset() {
+    let state = &mut hart_context_mut(current_hartid()).sse;
+    if state.events[0].state != UNUSED {
+        pmu::route_overflow_to_sse(false);
+    }
+    *state = SseState::new();
+}
+
+/// Takes the interrupted state of the event completed by the ongoing `sse_complete` call.
+pub(crate) fn take_completed() -> Option<[usize; 4]> {
+    hart_context_mut(current_hartid()).sse.completed.take()
+}
+
+/// Resumes the context interrupted by a completed event, restoring its `sepc`, `sstatus`
+/// bits, `a6` and `a7`.
+///
+/// The handler returns with `sepc` holding the address to resume from, and with `sstatus`
+/// as it was set up when the event was entered.
+pub(crate) fn resume(ctx: &mut FastContext, interrupted: [usize; 4]) {
+    let [interrupted_sepc, flags, a6, a7] = interrupted;
+    let flags = InterruptedFlags::from_bits_retain(flags);
+    let old = mstatus::read().bits();
+    let mut new = old & !(MSTATUS_MPP | MSTATUS_SIE | MSTATUS_SPIE | MSTATUS_SPP);
+    if old & MSTATUS_SPP != 0 {
+        new |= MSTATUS_MPP_SUPERVISOR;
+    }
+    if old & MSTATUS_SPIE != 0 {
+        new |= MSTATUS_SIE;
+    }
+    if flags.contains(InterruptedFlags::SSTATUS_SPIE) {
+        new |= MSTATUS_SPIE;
+    }
+    if flags.contains(InterruptedFlags::SSTATUS_SPP) {
+        new |= MSTATUS_SPP;
+    }
+    unsafe {
+        asm!("csrw mstatus, {}", in(reg) new);
+        mepc::write(sepc::read());
+        sepc::write(interrupted_sepc);
+    }
+    ctx.regs().a[6] = a6;
+    ctx.regs().a[7] = a7;
+}
+
+/// Returns to the interrupted context, entering the highest priority software event
+/// deliverable on the current hart first, if any.
+///
+/// Registers `a0` to `a7` of the interrupted context must have been saved in `ctx`.
+pub(crate) fn deliver(mut ctx: FastContext) -> FastResult {
+    if let Some(sse) = unsafe { PLATFORM.sbi.sse.as_ref() } {
+        sse.deliver(&mut ctx);
+    }
+    ctx.restore()
+}
+
+impl SbiSse {
+    fn deliver(&self, ctx: &mut FastContext) {
+        let hart_id = current_hartid();
+        let state = &mut hart_context_mut(hart_id).sse;
+        let local_pending = state.pending.load(Ordering::Relaxed);
+        let global_pending = self.pending.load(Ordering::Relaxed);
+        if state.masked || (local_pending == 0 && global_pending == 0) {
+            return;
+        }
+        // Events interrupt supervisor and user mode only; virtualization state is not saved.
+        let status = mstatus::read().bits();
+        if status & MSTATUS_MPP == MSTATUS_MPP || status & MSTATUS_MPV != 0 {
+            return;
+        }
+
+        let mut global = self.global.lock();
+        let event = |slot| match slot {
+            Slot::Local(idx) => state.events[idx],
+            Slot::Global(idx) => global[idx],
+        };
+        // Lower priority values and then lower event IDs come first.
+        let order = |slot: Slot| (event(slot).priority, slot.event_id());
+        let locals = (0..LOCAL_EVENTS.len())
+            .filter(|idx| local_pending & (1 << idx) != 0)
+            .map(Slot::Local);
+        let globals = (0..GLOBAL_EVENTS.len())
+            .filter(|&idx| {
+                global_pending & (1 << idx) != 0 && global[idx].preferred_hart == hart_id
+            })
+            .map(Slot::Global);
+        let Some(slot) = locals
+            .chain(globals)
+            .filter(|&slot| event(slot).state == ENABLED)
+            .min_by_key(|&slot| order(slot))
+        else {
+            return;
+        };
+        // Running events are preempted only by higher priority events.
+        if state
+            .innermost()
+            .is_some_and(|top| order(top) <= order(slot))
+        {
+            return;
+        }
+        let Some(free) = state.running.iter_mut().find(|slot| slot.is_none()) else {
+            return;
+        };
+        *free = Some(slot);
+        let event = match slot {
+            Slot::Local(idx) => {
+                state.pending.fetch_and(!(1 << idx), Ordering::Relaxed);
+                &mut state.events[idx]
+            }
+            Slot::Global(idx) => {
+                self.pending.fetch_and(!(1 << idx), Ordering::Relaxed);
+                &mut global[idx]
+            }
+        };
+        enter(ctx, event, hart_id);
+    }
+}
+
+/// Redirects the interrupted context to the handler of `event`, saving its `sepc`, `sstatus`
+/// bits, `a6` and `a7` into the `INTERRUPTED_*` attributes.
+///
+/// The handler is entered in supervisor mode with interrupts disabled, `sepc` holding the
+/// interrupted address, `a6` holding the hart ID and `a7` holding `ENTRY_ARG`.
+fn enter(ctx: &mut FastContext, event: &mut Event, hart_id: usize) {
+    let old = mstatus::read().bits();
+    let mut flags = InterruptedFlags::empty();
+    flags.set(InterruptedFlags::SSTATUS_SPP, old & MSTATUS_SPP != 0);
+    flags.set(InterruptedFlags::SSTATUS_SPIE, old & MSTATUS_SPIE != 0);
+    event.interrupted = [sepc::read(), flags.bits(), ctx.regs().a[6], ctx.regs().a[7]];
+    event.state = RUNNING;
+
+    let mut new = old & !(MSTATUS_MPP | MSTATUS_SIE | MSTATUS_SPIE | MSTATUS_SPP);
+    if old & MSTATUS_MPP == MSTATUS_MPP_SUPERVISOR {
+        new |= MSTATUS_SPP;
+    }
+    if old & MSTATUS_SIE != 0 {
+        new |= MSTATUS_SPIE;
+    }
+    new |= MSTATUS_MPP_SUPERVISOR;
+    unsafe {
+        asm!("csrw mstatus, {}", in(reg) new);
+        sepc::write(mepc::read());
+        mepc::write(event.entry_pc);
+    }
+    ctx.regs().a[6] = hart_id;
+    ctx.regs().a[7] = event.entry_arg;
+}
@@ -11,8 +11,9 @@ use crate::sbi::console;
 use crate::sbi::features::{Extension, hart_extension_probe};
 use crate::sbi::hsm::local_hsm;
 use crate::sbi::ipi;
-use crate::sbi::pmu::pmu_firmware_counter_increment;
+use crate::sbi::pmu::{self, pmu_firmware_counter_increment};
 use crate::sbi::rfence;
+use crate::sbi::sse;
 
 use super::helper::*;
 
@@ -86,7 +87,7 @@ pub fn msoft_handler(ctx: FastContext) -> FastResult {
         }
         _ => {
             msoft_ipi_handler();
-            ctx.restore()
+            sse::deliver(ctx)
         }
     }
 }
@@ -148,7 +149,14 @@ pub fn mext_handler(ctx: FastContext) -> FastResult {
         }
         None => {}
     }
-    ctx.restore()
+    sse::deliver(ctx)
+}
+
+/// Handles a local counter overflow interrupt by raising the PMU overflow software event.
+#[inline]
+pub fn lcofi_handler(ctx: FastContext) -> FastResult {
+    pmu::pmu_overflow_handler();
+    sse::deliver(ctx)
 }
 
 #[inline]
@@ -163,7 +171,7 @@ pub fn sbi_call_handler(
     a6: usize,
     a7: usize,
 ) -> FastResult {
-    use sbi_spec::{base, hsm, legacy};
+    use sbi_spec::{base, hsm, legacy, sse as sse_spec};
     let mut ret = unsafe {
         PLATFORM
             .sbi
@@ -185,6 +193,14 @@ pub fn sbi_call_handler(
                 }
                 _ => {}
             },
+            (sse_spec::EID_SSE, sse_spec::COMPLETE) => {
+                // A completed event resumes its interrupted context instead of returning.
+                if let Some(interrupted) = sse::take_completed() {
+                    ctx.regs().a = [ctx.a0(), a1, a2, a3, a4, a5, a6, a7];
+                    sse::resume(&mut ctx, interrupted);
+                    return sse::deliver(ctx);
+                }
+            }
             _ => {}
         }
     } else {
@@ -210,7 +226,7 @@ pub fn sbi_call_handler(
     ctx.regs().a = [ret.error, ret.value, a2, a3, a4, a5, a6, a7];
     let epc = mepc::read();
     unsafe { mepc::write(epc + get_inst(epc).1) };
-    ctx.restore()
+    sse::deliver(ctx)
 }
 
 #[inline]
@@ -231,6 +247,15 @@ pub fn delegate(ctx: &mut EntireContextSeparated) {
     }
 }
 
+/// Forwards a breakpoint from S-mode or U-mode, raised by `ebreak` or a debug trigger
+/// installed through DBTR, to the S-mode trap handler.
+#[inline]
+pub extern "C" fn breakpoint_handler(raw_ctx: EntireContext) -> EntireResult {
+    let mut ctx = raw_ctx.split().0;
+    delegate(&mut ctx);
+    ctx.restore()
+}
+
 #[inline]
 pub extern "C" fn illegal_instruction_handler(raw_ctx: EntireContext) -> EntireResult {
     let mut ctx = raw_ctx.split().0;
@@ -3,7 +3,7 @@ pub mod handler;
 
 mod helper;
 
-use super::pmu::pmu_firmware_counter_increment;
+use super::pmu::{IRQ_LCOFI, pmu_firmware_counter_increment};
 use crate::fail::unsupported_trap;
 
 use fast_trap::{FastContext, FastResult};
@@ -28,8 +28,14 @@ pub extern "C" fn fast_handler(
     // Save mepc into context
     ctx.regs().pc = mepc::read();
 
-    let cause = match mcause::read().cause().try_into() {
+    let mcause = mcause::read();
+    let cause = match mcause.cause().try_into() {
         Ok(cause) => cause,
+        // The local counter overflow interrupt is not a standard machine interrupt.
+        Err(_) if mcause.is_interrupt() && mcause.code() == IRQ_LCOFI => {
+            ctx.regs().a = [ctx.a0(), a1, a2, a3, a4, a5, a6, a7];
+            return handler::lcofi_handler(ctx);
+        }
         Err(err) => {
             error!("Failed to parse mcause: {:?}", err);
             unsupported_trap(None)
@@ -109,10 +115,12 @@ fn handle_exception(
             save_regs(&mut ctx);
             ctx.continue_with(handler::illegal_instruction_handler, ())
         }
-        // TODO: Handle Breakpoint
         Exception::Breakpoint => {
-            error!("TODO: Unhandled Breakpoint exception");
-            unsupported_trap(Some(Trap::Exception(exception)))
+            if mstatus::read().mpp() == mstatus::MPP::Machine {
+                panic!("Cannot handle breakpoint exception from M-MODE");
+            }
+            save_regs(&mut ctx);
+            ctx.continue_with(handler::breakpoint_handler, ())
         }
         Exception::LoadMisaligned => {
             pmu_firmware_counter_increment(firmware_event::MISALIGNED_LOAD);
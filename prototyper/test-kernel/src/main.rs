@@ -14,7 +14,10 @@ use sbi_spec::{
     binary::{CounterMask, HartMask, SbiRet},
     pmu::firmware_event,
 };
-use sbi_testing::sbi::{self, ConfigFlagsParam, StartFlagsParam, StopFlagsParam};
+use sbi_testing::{
+    ReportFormat,
+    sbi::{self, ConfigFlagsParam, StartFlagsParam, StopFlagsParam},
+};
 // use sbi_spec::pmu::*;
 use uart16550::Uart16550;
 
@@ -80,6 +83,7 @@ extern "C" fn rust_main(hartid: usize, dtb_pa: usize) -> ! {
         smp,
         frequency,
         uart,
+        scratch,
     } = BoardInfo::parse(dtb_pa);
     unsafe { UART = Uart16550Map(uart as _) };
     rcore_console::init_console(&Console);
@@ -103,11 +107,17 @@ extern "C" fn rust_main(hartid: usize, dtb_pa: usize) -> ! {
         hart_mask: (1 << smp) - 1,
         hart_mask_base: 0,
         delay: frequency,
+        // A reserved memory node for the scratch area enables warm reboot and suspend round trips
+        scratch: scratch.map(|addr| unsafe { &mut *(addr as *mut sbi_testing::Scratch) }),
+    };
+    // `SBI_TESTING_REPORT` selects machine-readable test case records at build time
+    let test_result = match option_env!("SBI_TESTING_REPORT") {
+        Some("tap") => testing.report(ReportFormat::Tap, &mut Console),
+        Some("json") => testing.report(ReportFormat::JsonLines, &mut Console),
+        _ => testing.test(),
     };
-    let test_result = testing.test();
 
     pmu_test(smp);
-    fence_test(hartid, smp);
 
     if test_result {
         sbi::system_reset(sbi::Shutdown, sbi::NoReason);
@@ -322,46 +332,6 @@ fn read_pmu_hardware_counter(csr_num: usize) -> u64 {
     }
 }
 
-#[inline]
-// Fence and HFence test
-fn fence_test(hartid: usize, smp: usize) {
-    let self_mask = HartMask::from_mask_base(0x1, hartid);
-
-    // Fence.i test (should succeed, no-op for PMU, but should not panic)
-    let ret = sbi::remote_fence_i(self_mask);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    let invalid_hart = smp + 1;
-    let ret = sbi::remote_fence_i(HartMask::from_mask_base(0x1, invalid_hart));
-    assert_eq!(ret, SbiRet::invalid_param());
-
-    // SFence.vma test (should succeed, no-op for PMU, but should not panic)
-    let ret = sbi::remote_sfence_vma(self_mask, 0, 0);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    let ret = sbi::remote_sfence_vma(HartMask::from_mask_base(0x1, invalid_hart), 0, 0);
-    assert_eq!(ret, SbiRet::invalid_param());
-
-    // SFence.vma with asid test
-    let ret = sbi::remote_sfence_vma_asid(self_mask, 0, 0, 0);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    // HFence tests (if supported)
-    let ret = sbi::remote_hfence_gvma(self_mask, 0, 0);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    let ret = sbi::remote_hfence_gvma_vmid(self_mask, 0, 0, 0);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    let ret = sbi::remote_hfence_vvma(self_mask, 0, 0);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    let ret = sbi::remote_hfence_vvma_asid(self_mask, 0, 0, 0);
-    assert!(ret.is_ok() || ret == SbiRet::not_supported());
-
-    println!("[34m[ INFO] Sbi `RFNC` test pass[0m");
-}
-
 #[cfg_attr(not(test), panic_handler)]
 fn panic(info: &core::panic::PanicInfo) -> ! {
     let (hart_id, pc): (usize, usize);
@@ -374,10 +344,14 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
     loop {}
 }
 
+/// Name of the `/reserved-memory` child node holding the `sbi-testing` scratch area.
+const SCRATCH_NODE: &str = "sbi-testing-scratch";
+
 struct BoardInfo {
     smp: usize,
     frequency: u64,
     uart: usize,
+    scratch: Option<usize>,
 }
 
 impl BoardInfo {
@@ -388,6 +362,7 @@ impl BoardInfo {
             smp: 0,
             frequency: 0,
             uart: 0,
+            scratch: None,
         };
         unsafe {
             Dtb::from_raw_parts_filtered(dtb_pa as _, |e| {
@@ -397,7 +372,11 @@ impl BoardInfo {
         .unwrap()
         .walk(|ctx, obj| match obj {
             DtbObj::SubNode { name } => {
-                if ctx.is_root() && (name == Str::from("cpus") || name == Str::from("soc")) {
+                if ctx.is_root()
+                    && (name == Str::from("cpus")
+                        || name == Str::from("soc")
+                        || name == Str::from("reserved-memory"))
+                {
                     StepInto
                 } else if ctx.name() == Str::from("cpus") && name.starts_with("cpu@") {
                     ans.smp += 1;
@@ -406,6 +385,10 @@ impl BoardInfo {
                     && (name.starts_with("uart") || name.starts_with("serial"))
                 {
                     StepInto
+                } else if ctx.name() == Str::from("reserved-memory")
+                    && name.starts_with(SCRATCH_NODE)
+                {
+                    StepInto
                 } else {
                     StepOver
                 }
@@ -413,6 +396,8 @@ impl BoardInfo {
             DtbObj::Property(Property::Reg(mut reg)) => {
                 if ctx.name().starts_with("uart") || ctx.name().starts_with("serial") {
                     ans.uart = reg.next().unwrap().start;
+                } else if ctx.name().starts_with(SCRATCH_NODE) {
+                    ans.scratch = Some(reg.next().unwrap().start);
                 }
                 StepOut
             }
@@ -458,6 +443,14 @@ impl rcore_console::Console for Console {
     }
 }
 
+impl core::fmt::Write for Console {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        rcore_console::Console::put_str(self, s);
+        Ok(())
+    }
+}
+
 struct Flag {
     inner: usize,
 }
@@ -1,8 +1,10 @@
 mod build;
 mod config;
+mod dtb;
 mod generate;
 mod kernels;
 mod qemu;
+mod report;
 mod scheme;
 mod target;
 
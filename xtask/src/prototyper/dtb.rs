@@ -0,0 +1,279 @@
+//! Device tree of the QEMU machine the test kernel boots on.
+//!
+//! The test kernel keeps `sbi-testing` round trip state across warm reboots
+//! in a scratch area, which it finds as a `/reserved-memory` child node. QEMU
+//! does not generate such a node, so xtask dumps the machine's device tree,
+//! adds the node, and boots QEMU with the patched tree (`-dtb`).
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{qemu, scheme::QemuParams};
+
+/// Node name prefix the test kernel looks for under `/reserved-memory`.
+const SCRATCH_NODE: &str = "sbi-testing-scratch";
+const SCRATCH_COMPATIBLE: &str = "rustsbi,sbi-testing-scratch";
+/// Physical address of the scratch area: above the Prototyper and the
+/// payload, and far below the device tree QEMU places at the top of memory.
+const SCRATCH_BASE: u64 = 0x8100_0000;
+const SCRATCH_SIZE: u64 = 0x1000;
+
+/// A `no-map` child node of `/reserved-memory`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ReservedNode<'a> {
+    /// Node name without unit address.
+    pub name: &'a str,
+    /// Value of the `compatible` property.
+    pub compatible: &'a str,
+    /// Start address of the region.
+    pub base: u64,
+    /// Size of the region in bytes.
+    pub size: u64,
+}
+
+/// Dump the device tree of the QEMU machine booting `smp` harts into `dir`,
+/// add the scratch area node, and return the path of the patched tree.
+pub(super) fn scratch_device_tree(qemu: &QemuParams, smp: usize, dir: &Path) -> Result<PathBuf> {
+    let dumped = dir.join("qemu-machine.dtb");
+    let patched = dir.join("qemu-machine-scratch.dtb");
+    qemu::dump_device_tree(qemu, smp, &dumped)?;
+    let dtb = fs::read(&dumped)
+        .with_context(|| format!("failed to read device tree '{}'", dumped.display()))?;
+    let dtb = add_reserved_memory(
+        &dtb,
+        &ReservedNode {
+            name: SCRATCH_NODE,
+            compatible: SCRATCH_COMPATIBLE,
+            base: SCRATCH_BASE,
+            size: SCRATCH_SIZE,
+        },
+    )
+    .with_context(|| format!("failed to patch device tree '{}'", dumped.display()))?;
+    fs::write(&patched, dtb)
+        .with_context(|| format!("failed to write device tree '{}'", patched.display()))?;
+    Ok(patched)
+}
+
+const FDT_MAGIC: u32 = 0xd00d_feed;
+const FDT_BEGIN_NODE: u32 = 0x1;
+const FDT_END_NODE: u32 = 0x2;
+const FDT_PROP: u32 = 0x3;
+const FDT_NOP: u32 = 0x4;
+const FDT_END: u32 = 0x9;
+/// Size of the flattened device tree header (version 17).
+const HEADER_SIZE: usize = 40;
+
+/// Offsets into the header of the fields rewritten by the patch.
+const TOTALSIZE: usize = 4;
+const OFF_DT_STRUCT: usize = 8;
+const OFF_DT_STRINGS: usize = 12;
+const OFF_MEM_RSVMAP: usize = 16;
+const VERSION: usize = 20;
+const SIZE_DT_STRINGS: usize = 32;
+const SIZE_DT_STRUCT: usize = 36;
+
+/// Add `node` to `/reserved-memory` of a flattened device tree, creating
+/// `/reserved-memory` with two address and size cells if it does not exist.
+///
+/// The tree must use the standard block order (header, memory reservation
+/// block, structure block, strings block), as QEMU and `dtc` produce.
+pub(super) fn add_reserved_memory(dtb: &[u8], node: &ReservedNode) -> Result<Vec<u8>> {
+    if dtb.len() < HEADER_SIZE || read_u32(dtb, 0) != FDT_MAGIC {
+        bail!("not a flattened device tree");
+    }
+    if read_u32(dtb, VERSION) < 17 {
+        bail!(
+            "device tree version {} is not supported",
+            read_u32(dtb, VERSION)
+        );
+    }
+    let field = |offset| read_u32(dtb, offset) as usize;
+    let (off_struct, size_struct) = (field(OFF_DT_STRUCT), field(SIZE_DT_STRUCT));
+    let (off_strings, size_strings) = (field(OFF_DT_STRINGS), field(SIZE_DT_STRINGS));
+    if field(TOTALSIZE) > dtb.len()
+        || field(OFF_MEM_RSVMAP) > off_struct
+        || off_struct + size_struct > off_strings
+        || off_strings + size_strings > field(TOTALSIZE)
+    {
+        bail!("device tree blocks are not in the standard order");
+    }
+    let structure = &dtb[off_struct..off_struct + size_struct];
+    let mut strings = dtb[off_strings..off_strings + size_strings].to_vec();
+
+    let insertion = find_insertion(structure, &strings)?;
+    let mut patch = Vec::new();
+    let (address_cells, size_cells) = match insertion.cells {
+        Some(cells) => cells,
+        None => {
+            begin_node(&mut patch, "reserved-memory");
+            property(
+                &mut patch,
+                &mut strings,
+                "#address-cells",
+                &2u32.to_be_bytes(),
+            );
+            property(&mut patch, &mut strings, "#size-cells", &2u32.to_be_bytes());
+            property(&mut patch, &mut strings, "ranges", &[]);
+            (2, 2)
+        }
+    };
+    begin_node(&mut patch, &format!("{}@{:x}", node.name, node.base));
+    let mut compatible = node.compatible.as_bytes().to_vec();
+    compatible.push(0);
+    property(&mut patch, &mut strings, "compatible", &compatible);
+    let mut reg = encode_cells(node.base, address_cells).context("region base does not fit")?;
+    reg.extend(encode_cells(node.size, size_cells).context("region size does not fit")?);
+    property(&mut patch, &mut strings, "reg", &reg);
+    property(&mut patch, &mut strings, "no-map", &[]);
+    patch.extend(FDT_END_NODE.to_be_bytes());
+    if insertion.cells.is_none() {
+        patch.extend(FDT_END_NODE.to_be_bytes());
+    }
+
+    let size_struct = size_struct + patch.len();
+    let off_strings = off_struct + size_struct;
+    let mut output = dtb[..off_struct].to_vec();
+    output.extend(&structure[..insertion.offset]);
+    output.extend(&patch);
+    output.extend(&structure[insertion.offset..]);
+    output.extend(&strings);
+    write_u32(&mut output, TOTALSIZE, (off_strings + strings.len()) as u32);
+    write_u32(&mut output, OFF_DT_STRINGS, off_strings as u32);
+    write_u32(&mut output, SIZE_DT_STRINGS, strings.len() as u32);
+    write_u32(&mut output, SIZE_DT_STRUCT, size_struct as u32);
+    Ok(output)
+}
+
+/// Where the new node goes in the structure block.
+struct Insertion {
+    /// Offset of the `FDT_END_NODE` token closing the parent node.
+    offset: usize,
+    /// Address and size cells of an existing `/reserved-memory` node.
+    cells: Option<(u32, u32)>,
+}
+
+/// Find the end of `/reserved-memory`, or of the root node if there is none.
+fn find_insertion(structure: &[u8], strings: &[u8]) -> Result<Insertion> {
+    let mut offset = 0;
+    let mut depth = 0usize;
+    // Cells of `/reserved-memory` while walking inside it; the defaults
+    // are those of the device tree specification.
+    let mut reserved: Option<(u32, u32)> = None;
+    while offset + 4 <= structure.len() {
+        let token = read_u32(structure, offset);
+        match token {
+            FDT_BEGIN_NODE => {
+                let name = read_str(structure, offset + 4).context("unterminated node name")?;
+                depth += 1;
+                if depth == 2 && name == "reserved-memory" {
+                    reserved = Some((2, 1));
+                }
+                offset += 4 + align4(name.len() + 1);
+            }
+            FDT_END_NODE => {
+                if depth == 2
+                    && let Some(cells) = reserved
+                {
+                    return Ok(Insertion {
+                        offset,
+                        cells: Some(cells),
+                    });
+                }
+                if depth == 1 {
+                    return Ok(Insertion {
+                        offset,
+                        cells: None,
+                    });
+                }
+                depth = depth.checked_sub(1).context("unbalanced nodes")?;
+                offset += 4;
+            }
+            FDT_PROP => {
+                if offset + 12 > structure.len() {
+                    bail!("truncated property");
+                }
+                let len = read_u32(structure, offset + 4) as usize;
+                let name_offset = read_u32(structure, offset + 8) as usize;
+                let value = structure
+                    .get(offset + 12..offset + 12 + len)
+                    .context("truncated property value")?;
+                if depth == 2
+                    && let Some((address_cells, size_cells)) = &mut reserved
+                    && let [a, b, c, d] = *value
+                {
+                    match read_str(strings, name_offset) {
+                        Some("#address-cells") => *address_cells = u32::from_be_bytes([a, b, c, d]),
+                        Some("#size-cells") => *size_cells = u32::from_be_bytes([a, b, c, d]),
+                        _ => {}
+                    }
+                }
+                offset += 12 + align4(len);
+            }
+            FDT_NOP => offset += 4,
+            FDT_END => break,
+            token => bail!("unknown structure token {token:#x} at offset {offset:#x}"),
+        }
+    }
+    bail!("root node is not closed")
+}
+
+fn begin_node(structure: &mut Vec<u8>, name: &str) {
+    structure.extend(FDT_BEGIN_NODE.to_be_bytes());
+    structure.extend(name.as_bytes());
+    structure.resize(structure.len() + align4(name.len() + 1) - name.len(), 0);
+}
+
+fn property(structure: &mut Vec<u8>, strings: &mut Vec<u8>, name: &str, value: &[u8]) {
+    let name_offset = string_offset(strings, name);
+    structure.extend(FDT_PROP.to_be_bytes());
+    structure.extend((value.len() as u32).to_be_bytes());
+    structure.extend((name_offset as u32).to_be_bytes());
+    structure.extend(value);
+    structure.resize(structure.len() + align4(value.len()) - value.len(), 0);
+}
+
+/// Offset of `name` in the strings block, appending it if absent.
+fn string_offset(strings: &mut Vec<u8>, name: &str) -> usize {
+    let mut offset = 0;
+    for existing in strings.split(|&b| b == 0) {
+        if existing == name.as_bytes() {
+            return offset;
+        }
+        offset += existing.len() + 1;
+    }
+    let offset = strings.len();
+    strings.extend(name.as_bytes());
+    strings.push(0);
+    offset
+}
+
+/// Encode `value` as `cells` big-endian 32-bit cells.
+fn encode_cells(value: u64, cells: u32) -> Option<Vec<u8>> {
+    if cells == 0 || cells > 2 || (cells == 1 && value > u32::MAX as u64) {
+        return None;
+    }
+    let bytes = value.to_be_bytes();
+    Some(bytes[8 - 4 * cells as usize..].to_vec())
+}
+
+fn read_str(bytes: &[u8], offset: usize) -> Option<&str> {
+    let tail = bytes.get(offset..)?;
+    let end = tail.iter().position(|&b| b == 0)?;
+    std::str::from_utf8(&tail[..end]).ok()
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn write_u32(bytes: &mut [u8], offset: usize, value: u32) {
+    bytes[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+}
+
+fn align4(len: usize) -> usize {
+    len.next_multiple_of(4)
+}
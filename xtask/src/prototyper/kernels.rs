@@ -5,7 +5,7 @@ use std::{
     time::Duration,
 };
 
-use crate::utils::{cargo, cargo_target_dir, workspace_root};
+use crate::utils::{CmdOptional, cargo, cargo_target_dir, workspace_root};
 use anyhow::{Context, Result, bail};
 use clap::Args;
 
@@ -13,7 +13,9 @@ use super::{
     PACKAGE_NAME, Target,
     build::{BuildArgs, build_firmware},
     config::resolve,
+    dtb,
     qemu::{self, QemuRun},
+    report::{REPORT_ENV, REPORT_FORMAT},
     scheme::{Action, Scheme},
 };
 
@@ -60,6 +62,19 @@ impl Kernel {
         }
     }
 
+    /// Whether this kernel runs `sbi-testing` suites and prints their case
+    /// records, which xtask verifies case by case.
+    pub(super) fn prints_case_records(self) -> bool {
+        self == Kernel::Test
+    }
+
+    /// Whether this kernel boots with a device tree describing the
+    /// `sbi-testing` scratch area, so that its warm reboot and suspend round
+    /// trips run in QEMU.
+    fn uses_scratch(self) -> bool {
+        self == Kernel::Test
+    }
+
     /// Console output patterns expected from a successful run of this kernel.
     ///
     /// Read from the kernel's `scripts/expected.txt` — the single source
@@ -90,6 +105,9 @@ impl Kernel {
             .package(self.package_name())
             .target(Target::Kernel.triple())
             .release()
+            .optional(self.prints_case_records(), |cargo| {
+                cargo.env(REPORT_ENV, REPORT_FORMAT)
+            })
             .status()
             .with_context(|| {
                 format!(
@@ -261,6 +279,11 @@ pub struct KernelArgs {
     /// Specify the path to a custom configuration file for the firmware
     #[arg(long, short = 'c')]
     pub config_file: Option<PathBuf>,
+
+    /// Write a JUnit XML report of the test kernel's cases to this path
+    /// (test only)
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<PathBuf>,
 }
 
 /// One QEMU run, with CLI overrides resolved against the Scheme.
@@ -321,6 +344,12 @@ pub(super) fn run(kernel: Kernel, args: &KernelArgs) -> Result<ExitStatus> {
     let scheme = Scheme::default();
     let run_opts = ResolvedRun::resolve(args, kernel, &scheme);
     run_opts.validate()?;
+    if args.junit.is_some() && !kernel.prints_case_records() {
+        bail!(
+            "--junit is not supported by the {} kernel, which prints no test case records",
+            kernel.command_name()
+        );
+    }
     let firmware_options = FirmwareOptions {
         debug: args.debug,
         config_file: args.config_file.clone(),
@@ -348,8 +377,18 @@ pub(super) fn run(kernel: Kernel, args: &KernelArgs) -> Result<ExitStatus> {
         let firmware_elf = spec
             .artifact_dir()
             .join(format!("{PACKAGE_NAME}-{}.elf", spec.artifact_suffix()));
+        let dtb = if kernel.uses_scratch() {
+            Some(dtb::scratch_device_tree(
+                &scheme.qemu,
+                run_opts.smp,
+                &spec.artifact_dir(),
+            )?)
+        } else {
+            None
+        };
         qemu::run(&QemuRun {
             bios: firmware_elf,
+            dtb,
             qemu: scheme.qemu.clone(),
             smp: run_opts.smp,
             timeout: Duration::from_secs(run_opts.timeout_secs),
@@ -357,6 +396,8 @@ pub(super) fn run(kernel: Kernel, args: &KernelArgs) -> Result<ExitStatus> {
             expected: kernel.expected_patterns(run_opts.smp)?,
             forbidden: forbidden_patterns()?,
             label: kernel.command_name().to_string(),
+            records: kernel.prints_case_records(),
+            junit: args.junit.clone(),
         })?;
     }
 
@@ -7,13 +7,13 @@
 
 use std::{
     io::Read,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Child, Command, Stdio},
     thread,
     time::{Duration, Instant},
 };
 
-use super::scheme::QemuParams;
+use super::{report, scheme::QemuParams};
 use anyhow::{Context, Result, bail};
 
 /// Poll interval while waiting for a QEMU process to exit.
@@ -25,6 +25,8 @@ const LOG_TAIL_LINES: usize = 120;
 pub(super) struct QemuRun {
     /// Firmware ELF passed as `-bios`.
     pub bios: PathBuf,
+    /// Device tree passed as `-dtb` instead of the one QEMU generates.
+    pub dtb: Option<PathBuf>,
     /// QEMU invocation parameters (`-machine`, `-m`).
     pub qemu: QemuParams,
     /// Number of harts (`-smp`).
@@ -42,6 +44,11 @@ pub(super) struct QemuRun {
     pub forbidden: Vec<String>,
     /// Human readable label used in log messages (e.g. `test`).
     pub label: String,
+    /// Whether the kernel prints `sbi-testing` case records; if so, the
+    /// run also fails on any failed case, and a per-case summary is printed.
+    pub records: bool,
+    /// Path to write a JUnit XML report of the case records to.
+    pub junit: Option<PathBuf>,
 }
 
 /// Outcome of one QEMU attempt.
@@ -131,9 +138,15 @@ pub(super) fn run(run: &QemuRun) -> Result<()> {
             Attempt::Exited {
                 success: true,
                 output,
-            } => Some(verify_output(output, &run.expected, &run.forbidden)),
+            } => Some(
+                verify_output(output, &run.expected, &run.forbidden)
+                    .and_then(|()| verify_case_records(run, output)),
+            ),
             _ => None,
         };
+        if !attempts_left || !matches!(outcome, Attempt::TimedOut { .. }) {
+            report_case_records(run, outcome.output())?;
+        }
         match next_step(
             &outcome,
             verification.as_ref().map(Result::is_ok),
@@ -191,13 +204,18 @@ pub(super) fn run(run: &QemuRun) -> Result<()> {
 /// stdout and stderr are drained on dedicated threads while the child runs;
 /// reading only after exit would deadlock once QEMU fills the pipe buffer.
 fn run_once(run: &QemuRun) -> Result<Attempt> {
-    let mut child = Command::new("qemu-system-riscv64")
+    let mut command = Command::new("qemu-system-riscv64");
+    command
         .arg("-machine")
         .arg(&run.qemu.machine)
         .arg("-m")
         .arg(format!("{}M", run.qemu.memory_mb))
         .args(["-smp", &run.smp.to_string(), "-nographic", "-bios"])
-        .arg(&run.bios)
+        .arg(&run.bios);
+    if let Some(dtb) = &run.dtb {
+        command.arg("-dtb").arg(dtb);
+    }
+    let mut child = command
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -244,6 +262,32 @@ fn run_once(run: &QemuRun) -> Result<Attempt> {
     }
 }
 
+/// Dump the device tree QEMU generates for `qemu` booting `smp` harts to
+/// `path`, without running the machine.
+pub(super) fn dump_device_tree(qemu: &QemuParams, smp: usize, path: &Path) -> Result<()> {
+    let status = Command::new("qemu-system-riscv64")
+        .arg("-machine")
+        .arg(format!("{},dumpdtb={}", qemu.machine, path.display()))
+        .arg("-m")
+        .arg(format!("{}M", qemu.memory_mb))
+        .args(["-smp", &smp.to_string(), "-nographic"])
+        .stdout(Stdio::null())
+        .status()
+        .context(
+            "failed to execute qemu-system-riscv64; please install QEMU \
+             (e.g. `sudo apt install qemu-system-misc` on Debian/Ubuntu) \
+             and make sure qemu-system-riscv64 is on PATH",
+        )?;
+    if !status.success() {
+        bail!(
+            "QEMU failed to dump the device tree of machine `{}` to '{}'",
+            qemu.machine,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
 /// Which child stream a reader thread drains.
 enum Stream {
     Stdout,
@@ -306,6 +350,37 @@ pub(super) fn verify_output(output: &str, expected: &[String], forbidden: &[Stri
     Ok(())
 }
 
+/// Verify the case records of a run printing them; a no-op otherwise.
+fn verify_case_records(run: &QemuRun, output: &str) -> Result<()> {
+    if !run.records {
+        return Ok(());
+    }
+    report::verify_records(&report::parse_records(output)?)
+}
+
+/// Print the per-case summary and write the JUnit report of the final
+/// attempt of a run printing case records.
+///
+/// Malformed records are reported as a warning here; verification fails on
+/// them separately.
+fn report_case_records(run: &QemuRun, output: &str) -> Result<()> {
+    if !run.records {
+        return Ok(());
+    }
+    let records = match report::parse_records(output) {
+        Ok(records) => records,
+        Err(err) => {
+            warn!("failed to parse test case records: {err:#}");
+            return Ok(());
+        }
+    };
+    report::print_summary(&run.label, &records);
+    if let Some(path) = run.junit.as_deref() {
+        report::write_junit(path, &run.label, &records)?;
+    }
+    Ok(())
+}
+
 /// Print the console lines carrying the kernel's results (e.g. test passes,
 /// benchmark numbers): lines matching any expected pattern.
 fn print_results(label: &str, output: &str, expected: &[String]) {
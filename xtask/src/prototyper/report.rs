@@ -0,0 +1,244 @@
+//! Structured test case records printed by the test kernel.
+//!
+//! The test kernel is built with `SBI_TESTING_REPORT=json`, so `sbi-testing`
+//! prints each test case event as one JSON object per console line. This
+//! module extracts those records from the captured console output, decides
+//! the run verdict from them, and renders per-case summaries and JUnit XML.
+
+use std::{collections::BTreeMap, fmt::Write as _, fs, path::Path};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+/// Environment variable selecting the `sbi-testing` report format of the
+/// test kernel at build time.
+pub(super) const REPORT_ENV: &str = "SBI_TESTING_REPORT";
+/// Report format xtask builds the test kernel with.
+pub(super) const REPORT_FORMAT: &str = "json";
+
+/// Start of every record line; other console lines are ignored.
+const RECORD_PREFIX: &str = r#"{"suite":"#;
+
+/// Verdict of one test case event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Verdict {
+    Pass,
+    Skip,
+    Fail,
+}
+
+/// One test case event reported by `sbi-testing`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(super) struct CaseRecord {
+    /// Test suite, e.g. `HSM`.
+    pub suite: String,
+    /// `Case` variant name, e.g. `HartStarted`.
+    pub case: String,
+    /// Verdict, derived from the log level of the case event.
+    pub verdict: Verdict,
+    /// Human readable description of the event.
+    pub message: String,
+    /// `SbiRet.error` as a signed value, present if the case carries an `SbiRet`.
+    #[serde(default)]
+    pub error: Option<i64>,
+    /// `SbiRet.value`, present if the case carries an `SbiRet`.
+    #[serde(default)]
+    pub value: Option<u64>,
+}
+
+/// Extract all case records from captured console output.
+///
+/// Records may follow other output on the same line (e.g. after a carriage
+/// return); a line carrying the record prefix but failing to parse is an
+/// error, as it means the console output was corrupted.
+pub(super) fn parse_records(output: &str) -> Result<Vec<CaseRecord>> {
+    output
+        .lines()
+        .filter_map(|line| {
+            line.find(RECORD_PREFIX)
+                .map(|start| line[start..].trim_end())
+        })
+        .map(|record| {
+            serde_json::from_str(record)
+                .with_context(|| format!("malformed test case record: {record}"))
+        })
+        .collect()
+}
+
+/// Verify the records of a test kernel run: at least one record must be
+/// present and no case may have failed.
+pub(super) fn verify_records(records: &[CaseRecord]) -> Result<()> {
+    if records.is_empty() {
+        bail!("kernel output contains no test case records");
+    }
+    let failed: Vec<String> = records
+        .iter()
+        .filter(|record| record.verdict == Verdict::Fail)
+        .map(|record| format!("{}/{}", record.suite, record.case))
+        .collect();
+    if !failed.is_empty() {
+        bail!(
+            "{} test case(s) failed: {}",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Print one line per case record and the totals of each verdict.
+pub(super) fn print_summary(label: &str, records: &[CaseRecord]) {
+    println!("----- {label} kernel test cases -----");
+    for record in records {
+        let verdict = match record.verdict {
+            Verdict::Pass => "PASS",
+            Verdict::Skip => "SKIP",
+            Verdict::Fail => "FAIL",
+        };
+        print!(
+            "[{verdict}] {}/{}: {}",
+            record.suite, record.case, record.message
+        );
+        if let (Some(error), Some(value)) = (record.error, record.value) {
+            print!(" (error {error}, value {value:#x})");
+        }
+        println!();
+    }
+    let count = |verdict| records.iter().filter(|r| r.verdict == verdict).count();
+    println!(
+        "----- {} passed, {} skipped, {} failed -----",
+        count(Verdict::Pass),
+        count(Verdict::Skip),
+        count(Verdict::Fail)
+    );
+}
+
+/// Render case records as a JUnit XML document, one `<testsuite>` per
+/// `sbi-testing` suite in order of first appearance.
+pub(super) fn render_junit(label: &str, records: &[CaseRecord]) -> String {
+    let mut suites: Vec<(&str, Vec<&CaseRecord>)> = Vec::new();
+    for record in records {
+        match suites.iter_mut().find(|(suite, _)| *suite == record.suite) {
+            Some((_, cases)) => cases.push(record),
+            None => suites.push((&record.suite, vec![record])),
+        }
+    }
+    let totals = Totals::of(records.iter());
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="{}" tests="{}" failures="{}" skipped="{}">"#,
+        escape(label),
+        totals.tests,
+        totals.failures,
+        totals.skipped
+    );
+    for (suite, cases) in &suites {
+        let totals = Totals::of(cases.iter().copied());
+        let _ = writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}">"#,
+            escape(suite),
+            totals.tests,
+            totals.failures,
+            totals.skipped
+        );
+        // Repeated cases (e.g. `HartStarted` per hart) get a sequence suffix
+        // so each `<testcase>` name is unique within its suite.
+        let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+        for record in cases {
+            let index = seen.entry(&record.case).or_default();
+            *index += 1;
+            let name = if *index == 1 {
+                record.case.clone()
+            } else {
+                format!("{}#{}", record.case, index)
+            };
+            let _ = write!(
+                xml,
+                r#"    <testcase classname="{}" name="{}""#,
+                escape(suite),
+                escape(&name)
+            );
+            let mut detail = record.message.clone();
+            if let (Some(error), Some(value)) = (record.error, record.value) {
+                let _ = write!(detail, " (error {error}, value {value:#x})");
+            }
+            match record.verdict {
+                Verdict::Pass => xml.push_str(" />\n"),
+                Verdict::Skip => {
+                    let _ = writeln!(xml, ">\n      <skipped message=\"{}\" />", escape(&detail));
+                    xml.push_str("    </testcase>\n");
+                }
+                Verdict::Fail => {
+                    let _ = writeln!(xml, ">\n      <failure message=\"{}\" />", escape(&detail));
+                    xml.push_str("    </testcase>\n");
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Write the JUnit XML report of `records` to `path`, creating parent
+/// directories as needed.
+pub(super) fn write_junit(path: &Path, label: &str, records: &[CaseRecord]) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory '{}'", parent.display()))?;
+    }
+    fs::write(path, render_junit(label, records))
+        .with_context(|| format!("failed to write JUnit report '{}'", path.display()))?;
+    info!("JUnit report written to: {}", path.display());
+    Ok(())
+}
+
+struct Totals {
+    tests: usize,
+    failures: usize,
+    skipped: usize,
+}
+
+impl Totals {
+    fn of<'a>(records: impl Iterator<Item = &'a CaseRecord>) -> Self {
+        let mut totals = Totals {
+            tests: 0,
+            failures: 0,
+            skipped: 0,
+        };
+        for record in records {
+            totals.tests += 1;
+            match record.verdict {
+                Verdict::Pass => {}
+                Verdict::Skip => totals.skipped += 1,
+                Verdict::Fail => totals.failures += 1,
+            }
+        }
+        totals
+    }
+}
+
+/// Escape text for XML attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 does not allow most control characters, even escaped.
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -9,10 +9,13 @@ use clap::Parser;
 use super::{
     BuildArgs, BuildMode, BuildPaths, PlatformAddresses, PrototyperCommand, Target,
     build::remove_stale_payload_artifacts,
+    dtb::{ReservedNode, add_reserved_memory},
     generate_build_inputs,
     kernels::{self, Kernel, KernelArgs, ResolvedRun, forbidden_patterns},
     qemu::{Attempt, NextStep, next_step, verify_output},
-    render_linker_script, resolve_in,
+    render_linker_script,
+    report::{Verdict, parse_records, render_junit, verify_records},
+    resolve_in,
     scheme::{Action, Scheme},
 };
 use crate::utils::cargo_target_dir_in;
@@ -125,6 +128,13 @@ fn cli_kernel_options_flow_and_resolve_via_scheme() {
     assert_eq!(args.smp, Some(2));
     assert_eq!(args.timeout, Some(30));
     assert_eq!(args.retries, Some(1));
+    assert_eq!(args.junit, None);
+
+    let args = match parse(&["prototyper", "test", "--junit", "out/junit.xml"]).unwrap() {
+        PrototyperCommand::Test(args) => args,
+        _ => panic!("expected `test` subcommand"),
+    };
+    assert_eq!(args.junit, Some(PathBuf::from("out/junit.xml")));
 
     // Absent flags are None; resolution happens against the Scheme.
     let scheme = Scheme::default();
@@ -166,6 +176,44 @@ fn qemu_output_verification_checks_expected_and_forbidden_patterns() {
     assert!(verify_output("", &expected, &forbidden).is_err());
 }
 
+#[test]
+fn case_records_are_parsed_verified_and_rendered_as_junit() {
+    let output = "Hello RustSBI!\n\
+                  [ INFO] not a record\n\
+                  {\"suite\":\"Base\",\"case\":\"Pass\",\"verdict\":\"pass\",\"message\":\"Sbi `Base` test pass\"}\n\
+                  \r{\"suite\":\"HSM\",\"case\":\"HartStarted\",\"verdict\":\"pass\",\"message\":\"hart 1 started\"}\n\
+                  {\"suite\":\"HSM\",\"case\":\"HartStarted\",\"verdict\":\"pass\",\"message\":\"hart 2 started\"}\n\
+                  {\"suite\":\"PMU\",\"case\":\"NoCounters\",\"verdict\":\"skip\",\"message\":\"no <counter>\"}\n";
+    let records = parse_records(output).unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[1].suite, "HSM");
+    assert_eq!(records[3].verdict, Verdict::Skip);
+    assert_eq!((records[0].error, records[0].value), (None, None));
+    assert!(verify_records(&records).is_ok());
+
+    let xml = render_junit("test", &records);
+    assert!(xml.contains(r#"<testsuites name="test" tests="4" failures="0" skipped="1">"#));
+    assert!(xml.contains(r#"<testsuite name="HSM" tests="2" failures="0" skipped="0">"#));
+    assert!(xml.contains(r#"<testcase classname="HSM" name="HartStarted" />"#));
+    assert!(xml.contains(r#"<testcase classname="HSM" name="HartStarted#2" />"#));
+    assert!(xml.contains(r#"<skipped message="no &lt;counter&gt;" />"#));
+
+    // A failed case fails verification and carries its `SbiRet` values.
+    let failed = parse_records(
+        r#"{"suite":"SSE","case":"Failed","verdict":"fail","message":"inject failed","error":-2,"value":0}"#,
+    )
+    .unwrap();
+    assert_eq!((failed[0].error, failed[0].value), (Some(-2), Some(0)));
+    let err = verify_records(&failed).unwrap_err();
+    assert!(format!("{err:#}").contains("SSE/Failed"));
+    let xml = render_junit("test", &failed);
+    assert!(xml.contains(r#"<failure message="inject failed (error -2, value 0x0)" />"#));
+
+    // No records, or a corrupted record line, fail closed.
+    assert!(verify_records(&parse_records("Hello RustSBI!\n").unwrap()).is_err());
+    assert!(parse_records(r#"{"suite":"Base","case":"Pa"#).is_err());
+}
+
 #[test]
 fn scheme_defaults_drive_kernel_runs() {
     let scheme = Scheme::default();
@@ -534,6 +582,7 @@ fn resolved_run_prefers_cli_overrides_and_falls_back_to_scheme() {
         retries: Some(1),
         debug: false,
         config_file: None,
+        junit: None,
     };
     let run = ResolvedRun::resolve(&args, Kernel::Test, &scheme);
     assert_eq!((run.smp, run.timeout_secs, run.attempts), (8, 30, 1));
@@ -574,3 +623,183 @@ fn pattern_files_must_yield_at_least_one_pattern() {
     );
     let _ = fs::remove_dir_all(&root);
 }
+
+/// Build a flattened device tree from `(depth, node name)` and
+/// `(property name, value)` items; nodes close when a shallower item follows.
+fn build_dtb(items: &[(usize, &str, Option<&[u8]>)]) -> Vec<u8> {
+    let mut structure = Vec::new();
+    let mut strings: Vec<u8> = Vec::new();
+    let mut depth = 0;
+    let word = |buf: &mut Vec<u8>, value: u32| buf.extend(value.to_be_bytes());
+    let pad = |buf: &mut Vec<u8>| buf.resize(buf.len().next_multiple_of(4), 0);
+    for &(level, name, value) in items.iter().chain([&(0, "", None)]) {
+        match value {
+            None => {
+                while depth >= level && depth > 0 {
+                    word(&mut structure, 2);
+                    depth -= 1;
+                }
+                if level == 0 {
+                    break;
+                }
+                word(&mut structure, 1);
+                structure.extend(name.as_bytes());
+                structure.push(0);
+                pad(&mut structure);
+                depth = level;
+            }
+            Some(value) => {
+                let offset = strings.len() as u32;
+                strings.extend(name.as_bytes());
+                strings.push(0);
+                word(&mut structure, 3);
+                word(&mut structure, value.len() as u32);
+                word(&mut structure, offset);
+                structure.extend(value);
+                pad(&mut structure);
+            }
+        }
+    }
+    word(&mut structure, 9);
+
+    let off_struct = 40 + 16;
+    let off_strings = off_struct + structure.len();
+    let mut dtb = Vec::new();
+    for value in [
+        0xd00d_feed,
+        (off_strings + strings.len()) as u32,
+        off_struct as u32,
+        off_strings as u32,
+        40,
+        17,
+        16,
+        0,
+        strings.len() as u32,
+        structure.len() as u32,
+    ] {
+        word(&mut dtb, value);
+    }
+    dtb.extend([0; 16]);
+    dtb.extend(structure);
+    dtb.extend(strings);
+    dtb
+}
+
+/// Walk a flattened device tree into `path/property = value` entries.
+fn walk_dtb(dtb: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let word = |offset: usize| u32::from_be_bytes(dtb[offset..offset + 4].try_into().unwrap());
+    let c_str = |offset: usize| {
+        let end = dtb[offset..].iter().position(|&b| b == 0).unwrap();
+        String::from_utf8(dtb[offset..offset + end].to_vec()).unwrap()
+    };
+    assert_eq!(word(4) as usize, dtb.len());
+    let (off_struct, off_strings) = (word(8) as usize, word(12) as usize);
+    assert_eq!(off_struct + word(36) as usize, off_strings);
+    let mut path: Vec<String> = Vec::new();
+    let mut entries = Vec::new();
+    let mut offset = off_struct;
+    loop {
+        match word(offset) {
+            1 => {
+                let name = c_str(offset + 4);
+                offset += 4 + (name.len() + 1).next_multiple_of(4);
+                path.push(name);
+            }
+            2 => {
+                path.pop();
+                offset += 4;
+            }
+            3 => {
+                let len = word(offset + 4) as usize;
+                let name = c_str(off_strings + word(offset + 8) as usize);
+                let value = dtb[offset + 12..offset + 12 + len].to_vec();
+                entries.push((format!("{}/{name}", path.join("/")), value));
+                offset += 12 + len.next_multiple_of(4);
+            }
+            9 => break,
+            token => panic!("unexpected token {token:#x}"),
+        }
+    }
+    assert!(path.is_empty(), "unbalanced nodes");
+    entries
+}
+
+const SCRATCH: ReservedNode = ReservedNode {
+    name: "scratch",
+    compatible: "test,scratch",
+    base: 0x8100_0000,
+    size: 0x1000,
+};
+
+#[test]
+fn reserved_memory_is_created_under_root() {
+    let dtb = build_dtb(&[
+        (1, "", None),
+        (0, "#address-cells", Some(&2u32.to_be_bytes())),
+        (2, "memory@80000000", None),
+        (0, "device_type", Some(b"memory\0")),
+    ]);
+    let entries = walk_dtb(&add_reserved_memory(&dtb, &SCRATCH).unwrap());
+    let get = |key: &str| {
+        entries
+            .iter()
+            .find(|(path, _)| path == key)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(get("/#address-cells"), Some(2u32.to_be_bytes().to_vec()));
+    assert_eq!(
+        get("/memory@80000000/device_type"),
+        Some(b"memory\0".to_vec())
+    );
+    assert_eq!(
+        get("/reserved-memory/#size-cells"),
+        Some(2u32.to_be_bytes().to_vec())
+    );
+    assert_eq!(get("/reserved-memory/ranges"), Some(Vec::new()));
+    assert_eq!(
+        get("/reserved-memory/scratch@81000000/compatible"),
+        Some(b"test,scratch\0".to_vec())
+    );
+    assert_eq!(
+        get("/reserved-memory/scratch@81000000/reg"),
+        Some([0, 0, 0, 0, 0x81, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10, 0].to_vec())
+    );
+    assert_eq!(
+        get("/reserved-memory/scratch@81000000/no-map"),
+        Some(Vec::new())
+    );
+}
+
+#[test]
+fn reserved_memory_node_is_extended_with_its_cells() {
+    let dtb = build_dtb(&[
+        (1, "", None),
+        (2, "reserved-memory", None),
+        (0, "#address-cells", Some(&1u32.to_be_bytes())),
+        (0, "#size-cells", Some(&1u32.to_be_bytes())),
+        (3, "firmware@80000000", None),
+        (0, "no-map", Some(b"")),
+        (2, "chosen", None),
+    ]);
+    let entries = walk_dtb(&add_reserved_memory(&dtb, &SCRATCH).unwrap());
+    let paths: Vec<&str> = entries.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "/reserved-memory/#address-cells",
+            "/reserved-memory/#size-cells",
+            "/reserved-memory/firmware@80000000/no-map",
+            "/reserved-memory/scratch@81000000/compatible",
+            "/reserved-memory/scratch@81000000/reg",
+            "/reserved-memory/scratch@81000000/no-map",
+        ]
+    );
+    assert_eq!(entries[4].1, [0x81, 0, 0, 0, 0, 0, 0x10, 0]);
+
+    let too_high = ReservedNode {
+        base: 0x1_0000_0000,
+        ..SCRATCH
+    };
+    assert!(add_reserved_memory(&dtb, &too_high).is_err());
+    assert!(add_reserved_memory(&dtb[4..], &SCRATCH).is_err());
+}